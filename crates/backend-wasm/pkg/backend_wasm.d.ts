@@ -1,18 +1,60 @@
 /* tslint:disable */
 /* eslint-disable */
 export function greet(): void;
-export function load_ocel_json(ocel_json: Uint8Array): void;
-export function load_ocel_xml(ocel_xml: Uint8Array): number;
-export function unload_ocel(): void;
-export function get_edge_violation_percentage(edge_json: string): string;
-export function get_edge_violation_percentage_perf(edge_json: string): number;
+/**
+ * Get the default drift detection options (JSON of `DriftOptions`)
+ */
+export function get_default_drift_options(): string;
+/**
+ * Get the default discovery options (JSON of `DiscoveryOptions`)
+ */
+export function get_default_discovery_options(): string;
 export function get_edge_as_template_text(edge_json: string): string;
-export function get_all_edge_violation_percentage(edge_json: string): string[];
-export function get_all_edge_violation_percentage_perf(edge_json: string): Float64Array;
-export function get_ot_act_involvements(): string;
-export function discover_oc_declare_constraints(noise_thresh: number): string;
+export function get_edges_from_template_text(model_text: string): string;
+export function get_template_text_for_edges(edges_json: string): string;
+/**
+ * Get the semantic diff (JSON of `ModelDiff`) between an old and a new model (JSON arrays of `OCDeclareConstraint`)
+ */
+export function get_model_diff(old_edges_json: string, new_edges_json: string): string;
+/**
+ * Get the conflicting arcs (JSON array of `Conflict`) of a model (JSON array of `OCDeclareConstraint`)
+ */
+export function get_edge_conflicts(edges_json: string): string;
 export function initThreadPool(num_threads: number): Promise<any>;
 export function wbg_rayon_start_worker(receiver: number): void;
+export class OCELSession {
+  private constructor();
+  free(): void;
+  /**
+   * Load an OCEL 2.0 JSON file into a new session
+   */
+  static load_ocel_json(ocel_json: Uint8Array): OCELSession;
+  /**
+   * Load an OCEL 2.0 XML file into a new session
+   */
+  static load_ocel_xml(ocel_xml: Uint8Array): OCELSession;
+  get_edge_violation_percentage(edge_json: string): string;
+  get_edge_violation_percentage_perf(edge_json: string): number;
+  get_edge_violation_report(edge_json: string): string;
+  get_all_edge_violation_percentage(edge_json: string): string[];
+  get_all_edge_violation_percentage_perf(edge_json: string): Float64Array;
+  get_ot_act_involvements(): string;
+  /**
+   * Discover OC-DECLARE constraints using the given options (JSON of `DiscoveryOptions`, missing fields use the defaults)
+   */
+  discover_oc_declare_constraints(options_json: string): string;
+  /**
+   * Discover OC-DECLARE constraints like [`Self::discover_oc_declare_constraints`], additionally returning
+   * the arcs removed by the reduction and why they were removed (JSON of `DiscoveryResult`)
+   */
+  discover_oc_declare_constraints_with_provenance(options_json: string): string;
+  /**
+   * Get the conformance of the edges per time window (JSON of `DriftReport`)
+   *
+   * The options are JSON of `DriftOptions` (missing fields use the defaults).
+   */
+  get_edge_drift(edges_json: string, options_json: string): string;
+}
 export class wbg_rayon_PoolBuilder {
   private constructor();
   free(): void;
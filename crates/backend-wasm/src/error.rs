@@ -0,0 +1,127 @@
+use std::fmt::Display;
+
+use serde::{de::DeserializeOwned, Serialize};
+use shared::{drift::InvalidDriftOptions, template::ParseError};
+use wasm_bindgen::JsValue;
+
+/// Errors returned by the exported functions of the WASM backend
+///
+/// On the JS side, these are thrown as `Error` objects with the variant as `name`
+/// and the serialized error as `details` (e.g., `{ type: "InvalidArcJson", path: "[0].label", message: "..." }`).
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum BackendError {
+    /// The OCEL could not be imported
+    OcelImport { format: String, message: String },
+    /// The provided arc JSON could not be deserialized
+    InvalidArcJson { path: String, message: String },
+    /// The provided options JSON could not be deserialized or contains invalid values
+    InvalidOptionsJson { path: String, message: String },
+    /// The provided textual model could not be parsed
+    InvalidModelText {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// A result could not be serialized
+    Serialization { message: String },
+}
+
+impl BackendError {
+    fn name(&self) -> &'static str {
+        match self {
+            BackendError::OcelImport { .. } => "OcelImport",
+            BackendError::InvalidArcJson { .. } => "InvalidArcJson",
+            BackendError::InvalidOptionsJson { .. } => "InvalidOptionsJson",
+            BackendError::InvalidModelText { .. } => "InvalidModelText",
+            BackendError::Serialization { .. } => "Serialization",
+        }
+    }
+}
+
+impl Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::OcelImport { format, message } => {
+                write!(f, "Could not import OCEL ({format}): {message}")
+            }
+            BackendError::InvalidArcJson { path, message } => {
+                write!(f, "Invalid arc JSON at '{path}': {message}")
+            }
+            BackendError::InvalidOptionsJson { path, message } => {
+                write!(f, "Invalid options JSON at '{path}': {message}")
+            }
+            BackendError::InvalidModelText {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Invalid model at line {line}, column {column}: {message}"
+            ),
+            BackendError::Serialization { message } => {
+                write!(f, "Could not serialize result: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+impl From<ParseError> for BackendError {
+    fn from(value: ParseError) -> Self {
+        BackendError::InvalidModelText {
+            line: value.line,
+            column: value.column,
+            message: value.message,
+        }
+    }
+}
+
+impl From<InvalidDriftOptions> for BackendError {
+    fn from(value: InvalidDriftOptions) -> Self {
+        BackendError::InvalidOptionsJson {
+            path: value.field,
+            message: value.message,
+        }
+    }
+}
+
+impl From<BackendError> for JsValue {
+    fn from(value: BackendError) -> Self {
+        let error = js_sys::Error::new(&value.to_string());
+        error.set_name(value.name());
+        if let Ok(details) = serde_json::to_string(&value)
+            .map_err(|_| ())
+            .and_then(|json| js_sys::JSON::parse(&json).map_err(|_| ()))
+        {
+            let _ = js_sys::Reflect::set(&error, &JsValue::from_str("details"), &details);
+        }
+        error.into()
+    }
+}
+
+/// Deserialize arc JSON, reporting the path of the invalid part on failure
+pub fn parse_arc_json<T: DeserializeOwned>(json: &str) -> Result<T, BackendError> {
+    let deserializer = &mut serde_json::Deserializer::from_str(json);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| BackendError::InvalidArcJson {
+        path: e.path().to_string(),
+        message: e.inner().to_string(),
+    })
+}
+
+/// Deserialize options JSON, reporting the path of the invalid part on failure
+pub fn parse_options_json<T: DeserializeOwned>(json: &str) -> Result<T, BackendError> {
+    let deserializer = &mut serde_json::Deserializer::from_str(json);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| BackendError::InvalidOptionsJson {
+        path: e.path().to_string(),
+        message: e.inner().to_string(),
+    })
+}
+
+/// Serialize a result to JSON
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, BackendError> {
+    serde_json::to_string(value).map_err(|e| BackendError::Serialization {
+        message: e.to_string(),
+    })
+}
@@ -1,17 +1,24 @@
+mod error;
 mod utils;
+pub use error::BackendError;
 pub use wasm_bindgen_rayon::init_thread_pool;
 
-use std::sync::RwLock;
-
+use error::{parse_arc_json, parse_options_json, to_json};
 use shared::{
-    OCDeclareArc, OCDeclareDiscoveryOptions, discover_behavior_constraints, get_activity_object_involvements, preprocess_ocel, process_mining::{
+    consistency::check_consistency,
+    constraint::OCDeclareConstraint,
+    diagnostics::get_violation_report,
+    diff::diff_models,
+    discovery::{discover_with_options, discover_with_provenance, DiscoveryOptions},
+    drift::{detect_drift, DriftOptions},
+    get_activity_object_involvements, preprocess_ocel,
+    process_mining::{
         import_ocel_json_from_slice, import_ocel_xml_slice, ocel::linked_ocel::IndexLinkedOCEL,
-    }, reduction::reduce_oc_arcs
+    },
+    template::{parse_model, print_model},
 };
 use wasm_bindgen::prelude::*;
 
-static WASM_MEMORY_THINGY: RwLock<Option<IndexLinkedOCEL>> = RwLock::new(Option::None);
-
 #[wasm_bindgen]
 extern "C" {
     fn alert(s: &str);
@@ -22,131 +29,167 @@ pub fn greet() {
     alert("Hello, backend-wasm!");
 }
 
+/// A loaded and preprocessed OCEL
+///
+/// Multiple sessions can be used side by side, each owning its own OCEL.
+/// The memory of a session is released by calling `free()` on it from JS.
 #[wasm_bindgen]
-pub fn load_ocel_json(ocel_json: &[u8]) {
-    let ocel = import_ocel_json_from_slice(ocel_json).unwrap();
-    let locel: IndexLinkedOCEL = preprocess_ocel(ocel);
-    // unsafe {
-    *WASM_MEMORY_THINGY.write().unwrap() = Some(locel);
-    // }
+pub struct OCELSession {
+    locel: IndexLinkedOCEL,
 }
 
 #[wasm_bindgen]
-pub fn load_ocel_xml(ocel_xml: &[u8]) -> usize {
-    let ocel = import_ocel_xml_slice(ocel_xml);
-    let num_objs: usize = ocel.objects.len();
-    let locel: IndexLinkedOCEL = preprocess_ocel(ocel);
-    // unsafe {
-    *WASM_MEMORY_THINGY.write().unwrap() = Some(locel);
-    num_objs
-}
+impl OCELSession {
+    /// Load an OCEL 2.0 JSON file into a new session
+    pub fn load_ocel_json(ocel_json: &[u8]) -> Result<OCELSession, BackendError> {
+        let ocel =
+            import_ocel_json_from_slice(ocel_json).map_err(|e| BackendError::OcelImport {
+                format: String::from("JSON"),
+                message: e.to_string(),
+            })?;
+        let locel: IndexLinkedOCEL = preprocess_ocel(ocel);
+        Ok(OCELSession { locel })
+    }
 
-#[wasm_bindgen]
-pub fn unload_ocel() {
-    // unsafe {
-    WASM_MEMORY_THINGY.write().unwrap().take();
-    // }
+    /// Load an OCEL 2.0 XML file into a new session
+    pub fn load_ocel_xml(ocel_xml: &[u8]) -> Result<OCELSession, BackendError> {
+        let ocel = import_ocel_xml_slice(ocel_xml);
+        // The XML importer skips invalid parts instead of failing
+        if ocel.events.is_empty() && ocel.objects.is_empty() {
+            return Err(BackendError::OcelImport {
+                format: String::from("XML"),
+                message: String::from("No events or objects could be read from the file."),
+            });
+        }
+        let locel: IndexLinkedOCEL = preprocess_ocel(ocel);
+        Ok(OCELSession { locel })
+    }
+
+    pub fn get_edge_violation_percentage(&self, edge_json: String) -> Result<String, BackendError> {
+        let edge: OCDeclareConstraint = parse_arc_json(&edge_json)?;
+        let all_res = edge.get_for_all_evs_perf(&self.locel);
+        to_json(&all_res)
+    }
+
+    pub fn get_edge_violation_percentage_perf(
+        &self,
+        edge_json: String,
+    ) -> Result<f64, BackendError> {
+        let edge: OCDeclareConstraint = parse_arc_json(&edge_json)?;
+        Ok(edge.get_for_all_evs_perf(&self.locel))
+    }
+
+    pub fn get_edge_violation_report(&self, edge_json: String) -> Result<String, BackendError> {
+        let edge: OCDeclareConstraint = parse_arc_json(&edge_json)?;
+        let report = get_violation_report(&edge, &self.locel);
+        to_json(&report)
+    }
+
+    pub fn get_all_edge_violation_percentage(
+        &self,
+        edge_json: String,
+    ) -> Result<Vec<String>, BackendError> {
+        let edges: Vec<OCDeclareConstraint> = parse_arc_json(&edge_json)?;
+        edges
+            .iter()
+            .map(|edge| to_json(&edge.get_for_all_evs_perf(&self.locel)))
+            .collect()
+    }
+
+    pub fn get_all_edge_violation_percentage_perf(
+        &self,
+        edge_json: String,
+    ) -> Result<Vec<f64>, BackendError> {
+        let edges: Vec<OCDeclareConstraint> = parse_arc_json(&edge_json)?;
+        Ok(edges
+            .iter()
+            .map(|edge| edge.get_for_all_evs_perf(&self.locel))
+            .collect())
+    }
+
+    pub fn get_ot_act_involvements(&self) -> Result<String, BackendError> {
+        let ot_act_involvement = get_activity_object_involvements(&self.locel);
+        to_json(&ot_act_involvement)
+    }
+
+    /// Discover OC-DECLARE constraints using the given options (JSON of `DiscoveryOptions`, missing fields use the defaults)
+    pub fn discover_oc_declare_constraints(
+        &self,
+        options_json: String,
+    ) -> Result<String, BackendError> {
+        let options: DiscoveryOptions = parse_options_json(&options_json)?;
+        let discovered_arcs = discover_with_options(&self.locel, &options);
+        to_json(&discovered_arcs)
+    }
+
+    /// Discover OC-DECLARE constraints like [`Self::discover_oc_declare_constraints`], additionally returning
+    /// the arcs removed by the reduction and why they were removed (JSON of `DiscoveryResult`)
+    pub fn discover_oc_declare_constraints_with_provenance(
+        &self,
+        options_json: String,
+    ) -> Result<String, BackendError> {
+        let options: DiscoveryOptions = parse_options_json(&options_json)?;
+        to_json(&discover_with_provenance(&self.locel, &options))
+    }
+
+    /// Get the conformance of the edges per time window (JSON of `DriftReport`)
+    ///
+    /// The options are JSON of `DriftOptions` (missing fields use the defaults).
+    pub fn get_edge_drift(
+        &self,
+        edges_json: String,
+        options_json: String,
+    ) -> Result<String, BackendError> {
+        let edges: Vec<OCDeclareConstraint> = parse_arc_json(&edges_json)?;
+        let options: DriftOptions = parse_options_json(&options_json)?;
+        to_json(&detect_drift(&edges, &self.locel, &options)?)
+    }
 }
 
+/// Get the default drift detection options (JSON of `DriftOptions`)
 #[wasm_bindgen]
-pub fn get_edge_violation_percentage(edge_json: String) -> String {
-    let locel_guard = WASM_MEMORY_THINGY.read().unwrap();
-    if let Some(locel) = locel_guard.as_ref() {
-        let edge: OCDeclareArc = serde_json::from_str(&edge_json).unwrap();
-        let all_res = edge.get_for_all_evs_perf(locel);
-
-        serde_json::to_string(&all_res).unwrap()
-    } else {
-        String::from("Failed")
-    }
+pub fn get_default_drift_options() -> Result<String, BackendError> {
+    to_json(&DriftOptions::default())
 }
 
+/// Get the default discovery options (JSON of `DiscoveryOptions`)
 #[wasm_bindgen]
-pub fn get_edge_violation_percentage_perf(edge_json: String) -> Result<f64, String> {
-    let locel_guard = WASM_MEMORY_THINGY.read().unwrap();
-    if let Some(locel) = locel_guard.as_ref() {
-        let edge: OCDeclareArc = serde_json::from_str(&edge_json).unwrap();
-        let viol_frac = edge.get_for_all_evs_perf(locel);
-
-        Ok(viol_frac)
-    } else {
-        Err(String::from("Failed"))
-    }
+pub fn get_default_discovery_options() -> Result<String, BackendError> {
+    to_json(&DiscoveryOptions::default())
 }
 
 #[wasm_bindgen]
-pub fn get_edge_as_template_text(edge_json: String) -> Result<String, String> {
-    let edge: OCDeclareArc = serde_json::from_str(&edge_json).map_err(|e| e.to_string())?;
+pub fn get_edge_as_template_text(edge_json: String) -> Result<String, BackendError> {
+    let edge: OCDeclareConstraint = parse_arc_json(&edge_json)?;
     Ok(edge.as_template_string())
 }
 
 #[wasm_bindgen]
-pub fn get_all_edge_violation_percentage(edge_json: String) -> Result<Vec<String>, String> {
-    let locel_guard = WASM_MEMORY_THINGY.read().unwrap();
-    if let Some(locel) = locel_guard.as_ref() {
-        let edge_json: Vec<OCDeclareArc> = serde_json::from_str(&edge_json).unwrap();
-        let res = edge_json
-            .iter()
-            .map(|edge| {
-                //    let edge: OCDeclareArc = serde_json::from_str(json).unwrap();
-                let all_res = edge.get_for_all_evs_perf(locel);
-                serde_json::to_string(&all_res).unwrap()
-            })
-            .collect();
-        Ok(res)
-        // let edge: OCDeclareArc = serde_json::from_str(&edge_json).unwrap();
-
-        // return serde_json::to_string(&all_res).unwrap();
-    } else {
-        Err(String::from("Failed"))
-    }
+pub fn get_edges_from_template_text(model_text: String) -> Result<String, BackendError> {
+    let edges = parse_model(&model_text)?;
+    to_json(&edges)
 }
 
 #[wasm_bindgen]
-pub fn get_all_edge_violation_percentage_perf(edge_json: String) -> Result<Vec<f64>, String> {
-    let locel_guard = WASM_MEMORY_THINGY.read().unwrap();
-    if let Some(locel) = locel_guard.as_ref() {
-        let edge_json: Vec<OCDeclareArc> = serde_json::from_str(&edge_json).unwrap();
-        let res = edge_json
-            .iter()
-            .map(|edge| {
-                //    let edge: OCDeclareArc = serde_json::from_str(json).unwrap();
-                let viol_frac = edge.get_for_all_evs_perf(locel);
-                viol_frac
-            })
-            .collect();
-        Ok(res)
-        // let edge: OCDeclareArc = serde_json::from_str(&edge_json).unwrap();
-
-        // return serde_json::to_string(&all_res).unwrap();
-    } else {
-        Err(String::from("Failed"))
-    }
+pub fn get_template_text_for_edges(edges_json: String) -> Result<String, BackendError> {
+    let edges: Vec<OCDeclareConstraint> = parse_arc_json(&edges_json)?;
+    Ok(print_model(&edges))
 }
 
+/// Get the semantic diff (JSON of `ModelDiff`) between an old and a new model (JSON arrays of `OCDeclareConstraint`)
 #[wasm_bindgen]
-pub fn get_ot_act_involvements() -> String {
-    let locel_guard = WASM_MEMORY_THINGY.read().unwrap();
-    if let Some(locel) = locel_guard.as_ref() {
-        let ot_act_involvement = get_activity_object_involvements(locel);
-        serde_json::to_string(&ot_act_involvement).unwrap()
-    } else {
-        String::from("Failed")
-    }
+pub fn get_model_diff(
+    old_edges_json: String,
+    new_edges_json: String,
+) -> Result<String, BackendError> {
+    let old_edges: Vec<OCDeclareConstraint> = parse_arc_json(&old_edges_json)?;
+    let new_edges: Vec<OCDeclareConstraint> = parse_arc_json(&new_edges_json)?;
+    to_json(&diff_models(&old_edges, &new_edges))
 }
 
+/// Get the conflicting arcs (JSON array of `Conflict`) of a model (JSON array of `OCDeclareConstraint`)
 #[wasm_bindgen]
-pub fn discover_oc_declare_constraints(noise_thresh: f64) -> Result<String, String> {
-    let locel_guard = WASM_MEMORY_THINGY.read().unwrap();
-    if let Some(locel) = locel_guard.as_ref() {
-        let mut options =  OCDeclareDiscoveryOptions::default();
-        options.noise_threshold = noise_thresh;
-        // options.counts_for_generation.1 = Some(20);
-        options.counts_for_filter.1 = Some(5);
-        let discovered_arcs = discover_behavior_constraints(locel,options);
-        let reduced = reduce_oc_arcs(&discovered_arcs);
-        Ok(serde_json::to_string(&reduced).unwrap())
-    } else {
-        Err(String::from("Failed"))
-    }
+pub fn get_edge_conflicts(edges_json: String) -> Result<String, BackendError> {
+    let edges: Vec<OCDeclareConstraint> = parse_arc_json(&edges_json)?;
+    to_json(&check_consistency(&edges))
 }
@@ -0,0 +1,713 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use shared::{
+    constraint::OCDeclareConstraint,
+    diagnostics::get_violation_report,
+    discovery::{discover_with_options, DiscoveryOptions},
+    graph::{to_dot, to_mermaid},
+    monitor::{ConformanceMonitor, MonitorState, MonitorUpdate, StreamItem},
+    preprocess_ocel,
+    process_mining::{
+        import_ocel_json_from_path, import_ocel_sqlite_from_path, import_ocel_xml_file,
+        ocel::linked_ocel::IndexLinkedOCEL,
+    },
+    reduction::ReductionMode,
+    template::{parse_model, print_constraint, print_model, print_predicate},
+    O2OMode,
+};
+
+/// Discovery and conformance checking of OC-DECLARE constraints on OCEL 2.0 event logs
+#[derive(Debug, Parser)]
+#[command(name = "oc-declare", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Discover OC-DECLARE constraints from an OCEL 2.0 file
+    Discover {
+        /// Path to the OCEL 2.0 file (.json, .xml or .sqlite)
+        ocel: PathBuf,
+        #[command(flatten)]
+        options: DiscoveryArgs,
+        /// Reduction to apply to the discovered constraints
+        #[arg(long, value_enum, default_value_t = ReductionArg::None)]
+        reduction: ReductionArg,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Check an OC-DECLARE model against an OCEL 2.0 file and print the conformance of each arc
+    Check {
+        /// Path to the OCEL 2.0 file (.json, .xml or .sqlite)
+        ocel: PathBuf,
+        /// Path to the model (.json for JSON, otherwise the textual template format)
+        model: PathBuf,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Reduce an OC-DECLARE model by removing implied arcs
+    Reduce {
+        /// Path to the model (.json for JSON, otherwise the textual template format)
+        model: PathBuf,
+        /// Reduction to apply
+        #[arg(long, value_enum, default_value_t = ReductionArg::OcArcs)]
+        reduction: ReductionArg,
+        /// Print why each removed arc is implied by the remaining arcs (to stderr)
+        #[arg(long)]
+        explain: bool,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Monitor an OC-DECLARE model on a stream of events and print state changes as JSON lines
+    ///
+    /// Each line of the stream is an OCEL 2.0 JSON event or object (objects before the events involving them),
+    /// with events ordered by their timestamps.
+    Monitor {
+        /// Path to the model (.json for JSON, otherwise the textual template format)
+        model: PathBuf,
+        /// Path to the JSONL event stream (default: stdin)
+        stream: Option<PathBuf>,
+        /// Only print updates changing the state of a whole constraint
+        #[arg(long)]
+        constraint_changes: bool,
+        /// Output file (default: stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Args)]
+struct DiscoveryArgs {
+    /// Maximum fraction of violating source events for a discovered constraint
+    #[arg(long, default_value_t = 0.2)]
+    noise_threshold: f64,
+    /// Use object-to-object (O2O) relationships for discovering object type associations
+    #[arg(long, value_enum, default_value_t = O2OModeArg::None)]
+    o2o_mode: O2OModeArg,
+    /// Only consider the given activities (can be passed multiple times, default: all activities)
+    #[arg(long = "activity")]
+    activities: Vec<String>,
+    /// Counts used for generating candidate constraints, as `MIN,MAX` (`*` for unbounded)
+    #[arg(long, value_parser = parse_counts)]
+    counts_for_generation: Option<(Option<usize>, Option<usize>)>,
+    /// Counts used for filtering candidate constraints, as `MIN,MAX` (`*` for unbounded)
+    #[arg(long, value_parser = parse_counts)]
+    counts_for_filter: Option<(Option<usize>, Option<usize>)>,
+    /// Propose tight time bounds for the discovered EF/EP/DF/DP constraints
+    #[arg(long)]
+    time_bounds: bool,
+}
+
+impl DiscoveryArgs {
+    fn to_options(&self, reduction: ReductionArg) -> DiscoveryOptions {
+        let mut options = DiscoveryOptions {
+            noise_threshold: self.noise_threshold,
+            o2o_mode: O2OMode::from(self.o2o_mode).into(),
+            reduction: reduction.into(),
+            time_bounds: self.time_bounds,
+            ..Default::default()
+        };
+        if !self.activities.is_empty() {
+            options.acts_to_use = Some(self.activities.clone());
+        }
+        if let Some(counts) = self.counts_for_generation {
+            options.counts_for_generation = counts;
+        }
+        if let Some(counts) = self.counts_for_filter {
+            options.counts_for_filter = counts;
+        }
+        options
+    }
+}
+
+#[derive(Debug, Args)]
+struct OutputArgs {
+    /// Output format
+    #[arg(long, short, value_enum, default_value_t = OutputFormat::Template)]
+    format: OutputFormat,
+    /// Output file (default: stdout)
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Json,
+    Csv,
+    Template,
+    Dot,
+    Mermaid,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum O2OModeArg {
+    None,
+    Direct,
+    Reversed,
+    Bidirectional,
+}
+
+impl From<O2OModeArg> for O2OMode {
+    fn from(value: O2OModeArg) -> Self {
+        match value {
+            O2OModeArg::None => O2OMode::None,
+            O2OModeArg::Direct => O2OMode::Direct,
+            O2OModeArg::Reversed => O2OMode::Reversed,
+            O2OModeArg::Bidirectional => O2OMode::Bidirectional,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReductionArg {
+    /// Keep all arcs
+    None,
+    /// Use `reduce_oc_arcs`
+    OcArcs,
+    /// Use `perform_transitive_reduction`
+    Transitive,
+    /// Use `reduce_by_entailment` (only removes arcs implied by the remaining arcs)
+    Entailment,
+}
+
+impl From<ReductionArg> for ReductionMode {
+    fn from(value: ReductionArg) -> Self {
+        match value {
+            ReductionArg::None => ReductionMode::None,
+            ReductionArg::OcArcs => ReductionMode::OcArcs,
+            ReductionArg::Transitive => ReductionMode::Transitive,
+            ReductionArg::Entailment => ReductionMode::Entailment,
+        }
+    }
+}
+
+fn parse_count(s: &str) -> Result<Option<usize>, String> {
+    match s.trim() {
+        "*" | "∞" | "inf" => Ok(None),
+        s => s
+            .parse()
+            .map(Some)
+            .map_err(|e| format!("Invalid count '{s}': {e}")),
+    }
+}
+
+fn parse_counts(s: &str) -> Result<(Option<usize>, Option<usize>), String> {
+    let (min, max) = s
+        .split_once(',')
+        .ok_or_else(|| format!("Expected counts as MIN,MAX but got '{s}'"))?;
+    Ok((parse_count(min)?, parse_count(max)?))
+}
+
+type CliResult<T> = Result<T, String>;
+
+fn import_ocel(path: &Path) -> CliResult<IndexLinkedOCEL> {
+    let now = Instant::now();
+    let ocel = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => import_ocel_json_from_path(path).map_err(|e| e.to_string())?,
+        Some("xml") => import_ocel_xml_file(path),
+        Some("sqlite" | "sqlite3" | "db") => {
+            import_ocel_sqlite_from_path(path).map_err(|e| e.to_string())?
+        }
+        _ => {
+            return Err(format!(
+                "Invalid OCEL file {path:?}! Currently only .json, .xml and .sqlite files are supported."
+            ))
+        }
+    };
+    let locel = preprocess_ocel(ocel);
+    eprintln!("Imported OCEL in {:?}", now.elapsed());
+    Ok(locel)
+}
+
+fn read_model(path: &Path) -> CliResult<Vec<OCDeclareConstraint>> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| format!("Could not read {path:?}: {e}"))?;
+    if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&contents).map_err(|e| format!("Invalid model JSON {path:?}: {e}"))
+    } else {
+        parse_model(&contents).map_err(|e| format!("Invalid model {path:?}:{e}"))
+    }
+}
+
+fn open_output(output: &OutputArgs) -> CliResult<Box<dyn Write>> {
+    match &output.output {
+        Some(path) => Ok(Box::new(BufWriter::new(
+            File::create(path).map_err(|e| format!("Could not create {path:?}: {e}"))?,
+        ))),
+        None => Ok(Box::new(std::io::stdout().lock())),
+    }
+}
+
+/// CSV row for a single arc
+#[derive(Debug, Serialize)]
+struct ArcRow {
+    arc: String,
+    arc_type: String,
+    from: String,
+    to: String,
+    each: String,
+    all: String,
+    any: String,
+    min_count: Option<usize>,
+    max_count: Option<usize>,
+    min_delay_ms: Option<i64>,
+    max_delay_ms: Option<i64>,
+    from_predicate: Option<String>,
+    to_predicate: Option<String>,
+}
+
+impl From<&OCDeclareConstraint> for ArcRow {
+    fn from(constraint: &OCDeclareConstraint) -> Self {
+        let arc = &constraint.arc;
+        let join = |assocs: &Vec<shared::ObjectTypeAssociation>| {
+            assocs
+                .iter()
+                .map(|a| a.as_template_string())
+                .collect::<Vec<_>>()
+                .join(";")
+        };
+        let time_bounds = constraint.time_bounds.unwrap_or_default();
+        Self {
+            arc: print_constraint(constraint),
+            arc_type: arc.arc_type.get_name().to_string(),
+            from: arc.from.as_str().to_string(),
+            to: arc.to.as_str().to_string(),
+            each: join(&arc.label.each),
+            all: join(&arc.label.all),
+            any: join(&arc.label.any),
+            min_count: arc.counts.0,
+            max_count: arc.counts.1,
+            min_delay_ms: time_bounds.min_delay_ms,
+            max_delay_ms: time_bounds.max_delay_ms,
+            from_predicate: constraint.from_predicate.as_ref().map(print_predicate),
+            to_predicate: constraint.to_predicate.as_ref().map(print_predicate),
+        }
+    }
+}
+
+/// Conformance result of a single arc
+#[derive(Debug, Serialize)]
+struct CheckRow {
+    #[serde(flatten)]
+    arc: ArcRow,
+    #[serde(flatten)]
+    result: CheckResult,
+}
+
+/// Conformance metrics of a [`CheckRow`]
+#[derive(Debug, Serialize)]
+struct CheckResult {
+    source_events: usize,
+    violating_events: usize,
+    violation_fraction: f64,
+}
+
+impl CheckRow {
+    fn new(constraint: &OCDeclareConstraint, locel: &IndexLinkedOCEL) -> Self {
+        let report = get_violation_report(constraint, locel);
+        Self {
+            arc: ArcRow::from(constraint),
+            result: CheckResult {
+                source_events: report.num_source_events,
+                violating_events: report.num_violating_events,
+                violation_fraction: report.violation_fraction,
+            },
+        }
+    }
+}
+
+/// Output line of the monitor
+#[derive(Debug, Serialize)]
+struct MonitorRow<'a> {
+    arc: &'a str,
+    #[serde(flatten)]
+    update: MonitorUpdate,
+}
+
+fn monitor_stream(
+    constraints: Vec<OCDeclareConstraint>,
+    reader: Box<dyn BufRead>,
+    mut writer: Box<dyn Write>,
+    constraint_changes: bool,
+) -> CliResult<()> {
+    let arcs: Vec<String> = constraints.iter().map(print_constraint).collect();
+    let mut monitor = ConformanceMonitor::new(constraints);
+    let mut states = monitor.constraint_states();
+    let mut write_updates = |updates: Vec<MonitorUpdate>, writer: &mut Box<dyn Write>| {
+        for update in updates {
+            let i = update.constraint;
+            if constraint_changes && states[i] == update.constraint_state {
+                continue;
+            }
+            states[i] = update.constraint_state;
+            let row = MonitorRow {
+                arc: &arcs[i],
+                update,
+            };
+            serde_json::to_writer(&mut *writer, &row).map_err(|e| e.to_string())?;
+            writeln!(writer).map_err(|e| e.to_string())?;
+        }
+        writer.flush().map_err(|e| e.to_string())
+    };
+    for (line_num, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| e.to_string())?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let item: StreamItem = serde_json::from_str(&line)
+            .map_err(|e| format!("Invalid stream item in line {}: {e}", line_num + 1))?;
+        let updates = monitor
+            .add(&item)
+            .map_err(|e| format!("Line {}: {e}", line_num + 1))?;
+        write_updates(updates, &mut writer)?;
+    }
+    write_updates(monitor.finish(), &mut writer)?;
+    for ((arc, state), (activations, violated)) in arcs
+        .iter()
+        .zip(monitor.constraint_states())
+        .zip(monitor.activation_counts())
+    {
+        let marker = if state == MonitorState::PermanentlyViolated {
+            "violated"
+        } else {
+            "satisfied"
+        };
+        eprintln!("# {marker} ({violated} of {activations} activations violated)\n{arc}");
+    }
+    Ok(())
+}
+
+fn write_csv<T: Serialize>(writer: Box<dyn Write>, rows: &[T]) -> CliResult<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    for row in rows {
+        csv_writer.serialize(row).map_err(|e| e.to_string())?;
+    }
+    csv_writer.flush().map_err(|e| e.to_string())
+}
+
+/// Write the arcs of the constraints as a DOT or Mermaid graph (time bounds and predicates are not shown)
+fn write_graph(
+    mut writer: Box<dyn Write>,
+    format: OutputFormat,
+    constraints: &[OCDeclareConstraint],
+    violation_fractions: Option<&[f64]>,
+) -> CliResult<()> {
+    let arcs: Vec<_> = constraints.iter().map(|c| c.arc.clone()).collect();
+    let graph = match format {
+        OutputFormat::Mermaid => to_mermaid(&arcs, violation_fractions),
+        _ => to_dot(&arcs, violation_fractions),
+    };
+    write!(writer, "{graph}").map_err(|e| e.to_string())
+}
+
+fn write_model(output: &OutputArgs, arcs: &[OCDeclareConstraint]) -> CliResult<()> {
+    let mut writer = open_output(output)?;
+    match output.format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, arcs).map_err(|e| e.to_string())?;
+            writeln!(writer).map_err(|e| e.to_string())
+        }
+        OutputFormat::Csv => write_csv(writer, &arcs.iter().map(ArcRow::from).collect::<Vec<_>>()),
+        OutputFormat::Template => {
+            write!(writer, "{}", print_model(arcs)).map_err(|e| e.to_string())
+        }
+        OutputFormat::Dot | OutputFormat::Mermaid => write_graph(writer, output.format, arcs, None),
+    }
+}
+
+fn run(cli: Cli) -> CliResult<()> {
+    match cli.command {
+        Command::Discover {
+            ocel,
+            options,
+            reduction,
+            output,
+        } => {
+            let locel = import_ocel(&ocel)?;
+            let now = Instant::now();
+            let constraints = discover_with_options(&locel, &options.to_options(reduction));
+            eprintln!(
+                "Discovered {} constraints in {:?}",
+                constraints.len(),
+                now.elapsed()
+            );
+            write_model(&output, &constraints)
+        }
+        Command::Check {
+            ocel,
+            model,
+            output,
+        } => {
+            let arcs = read_model(&model)?;
+            let locel = import_ocel(&ocel)?;
+            let rows: Vec<CheckRow> = arcs.iter().map(|arc| CheckRow::new(arc, &locel)).collect();
+            let mut writer = open_output(&output)?;
+            match output.format {
+                OutputFormat::Json => {
+                    serde_json::to_writer_pretty(&mut writer, &rows).map_err(|e| e.to_string())?;
+                    writeln!(writer).map_err(|e| e.to_string())
+                }
+                // The CSV writer does not support flattened structs, so each row is written as a pair
+                OutputFormat::Csv => write_csv(
+                    writer,
+                    &rows
+                        .iter()
+                        .map(|row| (&row.arc, &row.result))
+                        .collect::<Vec<_>>(),
+                ),
+                OutputFormat::Template => {
+                    for row in &rows {
+                        writeln!(
+                            writer,
+                            "# {:.2}% violated ({} of {} source events)\n{}",
+                            row.result.violation_fraction * 100.0,
+                            row.result.violating_events,
+                            row.result.source_events,
+                            row.arc.arc
+                        )
+                        .map_err(|e| e.to_string())?;
+                    }
+                    Ok(())
+                }
+                OutputFormat::Dot | OutputFormat::Mermaid => {
+                    let violation_fractions: Vec<f64> = rows
+                        .iter()
+                        .map(|row| row.result.violation_fraction)
+                        .collect();
+                    write_graph(writer, output.format, &arcs, Some(&violation_fractions))
+                }
+            }
+        }
+        Command::Reduce {
+            model,
+            reduction,
+            explain,
+            output,
+        } => {
+            let arcs = read_model(&model)?;
+            let num_arcs = arcs.len();
+            let (reduced, removed) =
+                ReductionMode::from(reduction).apply_to_constraints_explained(arcs);
+            eprintln!("Reduced {} to {} constraints", num_arcs, reduced.len());
+            if explain {
+                for removed in &removed {
+                    eprintln!(
+                        "Removed {}: {}",
+                        removed.arc.as_template_string(),
+                        removed.explanation
+                    );
+                }
+            }
+            write_model(&output, &reduced)
+        }
+        Command::Monitor {
+            model,
+            stream,
+            constraint_changes,
+            output,
+        } => {
+            let constraints = read_model(&model)?;
+            let reader: Box<dyn BufRead> = match &stream {
+                Some(path) => Box::new(BufReader::new(
+                    File::open(path).map_err(|e| format!("Could not open {path:?}: {e}"))?,
+                )),
+                None => Box::new(std::io::stdin().lock()),
+            };
+            let writer = open_output(&OutputArgs {
+                format: OutputFormat::Json,
+                output,
+            })?;
+            monitor_stream(constraints, reader, writer, constraint_changes)
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(cli) {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use super::*;
+
+    /// `o1` is placed and paid, `o2` is only placed
+    const OCEL_JSON: &str = r#"{
+        "objectTypes": [{"name": "order", "attributes": []}],
+        "eventTypes": [{"name": "place order", "attributes": []}, {"name": "pay order", "attributes": []}],
+        "objects": [
+            {"id": "o1", "type": "order", "attributes": [], "relationships": []},
+            {"id": "o2", "type": "order", "attributes": [], "relationships": []}
+        ],
+        "events": [
+            {"id": "e1", "type": "place order", "time": "2024-01-01T08:00:00Z", "attributes": [], "relationships": [{"objectId": "o1", "qualifier": ""}]},
+            {"id": "e2", "type": "pay order", "time": "2024-01-02T08:00:00Z", "attributes": [], "relationships": [{"objectId": "o1", "qualifier": ""}]},
+            {"id": "e3", "type": "place order", "time": "2024-01-03T08:00:00Z", "attributes": [], "relationships": [{"objectId": "o2", "qualifier": ""}]}
+        ]
+    }"#;
+
+    const MODEL: &str = "EF(place order, pay order, Each(order),1,1)\n";
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("oc-declare").chain(args.iter().copied()))
+    }
+
+    /// Run `check` on the test OCEL and model with the given output format and return the output
+    fn check(format: &str) -> String {
+        let dir =
+            std::env::temp_dir().join(format!("oc-declare-cli-{}-{format}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (ocel, model, output) = (dir.join("log.json"), dir.join("model.txt"), dir.join("out"));
+        std::fs::write(&ocel, OCEL_JSON).unwrap();
+        std::fs::write(&model, MODEL).unwrap();
+        let args: Vec<OsString> = vec![
+            "oc-declare".into(),
+            "check".into(),
+            ocel.into(),
+            model.into(),
+            "--format".into(),
+            format.into(),
+            "--output".into(),
+            output.clone().into(),
+        ];
+        run(Cli::try_parse_from(args).unwrap()).unwrap();
+        let ret = std::fs::read_to_string(&output).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        ret
+    }
+
+    #[test]
+    fn arguments() {
+        let cli = parse(&[
+            "discover",
+            "log.sqlite",
+            "--noise-threshold",
+            "0.1",
+            "--activity",
+            "place order",
+            "--activity",
+            "pay order",
+            "--counts-for-filter",
+            "1,*",
+            "--reduction",
+            "entailment",
+            "-f",
+            "csv",
+        ])
+        .unwrap();
+        let Command::Discover {
+            ocel,
+            options,
+            reduction,
+            output,
+        } = cli.command
+        else {
+            panic!("Expected discover command");
+        };
+        assert_eq!(ocel, PathBuf::from("log.sqlite"));
+        assert!(matches!(output.format, OutputFormat::Csv));
+        assert_eq!(output.output, None);
+        let options = options.to_options(reduction);
+        assert_eq!(options.noise_threshold, 0.1);
+        assert_eq!(
+            options.acts_to_use,
+            Some(vec![String::from("place order"), String::from("pay order")])
+        );
+        assert_eq!(options.counts_for_filter, (Some(1), None));
+        assert_eq!(
+            options.counts_for_generation,
+            DiscoveryOptions::default().counts_for_generation
+        );
+        assert_eq!(options.reduction, ReductionMode::Entailment);
+
+        let cli = parse(&["reduce", "model.txt"]).unwrap();
+        let Command::Reduce {
+            reduction,
+            explain,
+            output,
+            ..
+        } = cli.command
+        else {
+            panic!("Expected reduce command");
+        };
+        assert_eq!(reduction, ReductionArg::OcArcs);
+        assert!(!explain);
+        assert!(matches!(output.format, OutputFormat::Template));
+
+        assert!(parse(&["discover", "log.json", "--counts-for-filter", "1"]).is_err());
+        assert!(parse(&["discover", "log.json", "--counts-for-filter", "1,x"]).is_err());
+        assert!(parse(&["check", "log.json"]).is_err());
+        assert!(parse(&["check", "log.json", "model.txt", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn check_json() {
+        let rows: serde_json::Value = serde_json::from_str(&check("json")).unwrap();
+        assert_eq!(
+            rows,
+            serde_json::json!([{
+                "arc": "EF(place order, pay order, Each(order),1,1)",
+                "arc_type": "EF",
+                "from": "place order",
+                "to": "pay order",
+                "each": "order",
+                "all": "",
+                "any": "",
+                "min_count": 1,
+                "max_count": 1,
+                "min_delay_ms": null,
+                "max_delay_ms": null,
+                "from_predicate": null,
+                "to_predicate": null,
+                "source_events": 2,
+                "violating_events": 1,
+                "violation_fraction": 0.5
+            }])
+        );
+    }
+
+    #[test]
+    fn check_csv() {
+        assert_eq!(
+            check("csv"),
+            "arc,arc_type,from,to,each,all,any,min_count,max_count,min_delay_ms,max_delay_ms,from_predicate,to_predicate,source_events,violating_events,violation_fraction\n\
+             \"EF(place order, pay order, Each(order),1,1)\",EF,place order,pay order,order,,,1,1,,,,,2,1,0.5\n"
+        );
+    }
+
+    #[test]
+    fn check_template() {
+        assert_eq!(
+            check("template"),
+            "# 50.00% violated (1 of 2 source events)\nEF(place order, pay order, Each(order),1,1)\n"
+        );
+    }
+
+    #[test]
+    fn check_dot() {
+        let dot = check("dot");
+        assert!(dot.starts_with("digraph oc_declare {"));
+        assert!(dot.contains("n0 -> n1 [label=\"EF\\nEACH: order\\n[1,1]\\n50.00% violated\""));
+    }
+
+    #[test]
+    fn check_mermaid() {
+        let mermaid = check("mermaid");
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("n0 -->|\"EF<br>EACH: order<br>[1,1]<br>50.00% violated\"| n1"));
+    }
+}
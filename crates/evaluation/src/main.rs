@@ -1,8 +1,26 @@
-use std::{env, fs::File, hint::black_box, path::PathBuf, time::Instant};
+use std::{env, fs::File, hint::black_box, path::{Path, PathBuf}, time::Instant};
 
 use shared::{O2OMode, OCDeclareDiscoveryOptions, process_mining::import_ocel_json_from_path};
 use serde::{Deserialize, Serialize};
-use shared::{discover_behavior_constraints, preprocess_ocel, reduction::reduce_oc_arcs};
+use shared::{
+    constraint::OCDeclareConstraint,
+    discover_behavior_constraints,
+    discovery::{get_arc_metrics, ConstraintWithMetrics},
+    preprocess_ocel,
+    process_mining::{import_ocel_sqlite_from_path, ocel::ocel_struct::OCEL},
+    reduction::reduce_oc_arcs,
+    OCDeclareArc,
+};
+
+/// Import an OCEL 2.0 JSON file, falling back to the SQLite version (same name, `.sqlite` extension) if it does not exist
+fn import_ocel(path: &Path) -> OCEL {
+    let sqlite_path = path.with_extension("sqlite");
+    if !path.exists() && sqlite_path.exists() {
+        import_ocel_sqlite_from_path(sqlite_path).unwrap()
+    } else {
+        import_ocel_json_from_path(path).unwrap()
+    }
+}
 
 fn main() {
     let base_path: Option<String> = env::args().skip(1).next();
@@ -24,7 +42,7 @@ fn main() {
             ];
             for (name, path) in event_logs {
                 println!("Evaluating on {name}.");
-                let ocel = import_ocel_json_from_path(path).unwrap();
+                let ocel = import_ocel(&path);
                 let locel = preprocess_ocel(ocel);
                 for o2o_mode in [O2OMode::None, O2OMode::Direct] {
                     println!("{:?}", o2o_mode);
@@ -62,13 +80,26 @@ fn main() {
                         File::create(format!("{}-{:?}-summary.json", name, o2o_mode)).unwrap();
                     serde_json::to_writer_pretty(summary_file, &eval_res).unwrap();
 
+                    // Results include the quality metrics of each arc (computed outside of the timed runs)
+                    let with_metrics = |arcs: &[OCDeclareArc]| -> Vec<ConstraintWithMetrics> {
+                        arcs.iter()
+                            .map(|arc| {
+                                let constraint = OCDeclareConstraint::new(arc.clone());
+                                let metrics = get_arc_metrics(&constraint, &locel);
+                                ConstraintWithMetrics {
+                                    constraint,
+                                    metrics,
+                                }
+                            })
+                            .collect()
+                    };
                     let results_file =
                         File::create(format!("{}-{:?}-results.json", name, o2o_mode)).unwrap();
-                    serde_json::to_writer_pretty(results_file, &res).unwrap();
+                    serde_json::to_writer_pretty(results_file, &with_metrics(&res)).unwrap();
                     let reduced_file =
                         File::create(format!("{}-{:?}-reduced-results.json", name, o2o_mode))
                             .unwrap();
-                    serde_json::to_writer_pretty(reduced_file, &reduced).unwrap();
+                    serde_json::to_writer_pretty(reduced_file, &with_metrics(&reduced)).unwrap();
                 }
             }
         }
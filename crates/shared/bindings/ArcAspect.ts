@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Aspect of an arc which can change between two models
+ */
+export type ArcAspect = "ArcType" | "Label" | "Counts" | "TimeBounds" | "Predicates";
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ArcAspect } from "./ArcAspect";
+import type { ArcChangeKind } from "./ArcChangeKind";
+
+/**
+ * Diff entry for a single (matched, added or removed) arc
+ */
+export type ArcDiff = { kind: ArcChangeKind, 
+/**
+ * Index of the arc in the old model (`null` for added arcs)
+ */
+old_index: number | null, 
+/**
+ * Index of the arc in the new model (`null` for removed arcs)
+ */
+new_index: number | null, 
+/**
+ * Aspects that differ between the old and the new arc (empty for added/removed arcs)
+ */
+changed_aspects: Array<ArcAspect>, };
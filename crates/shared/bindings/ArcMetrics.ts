@@ -0,0 +1,29 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Quality metrics of an arc w.r.t. a log, e.g., for ranking or filtering discovered arcs
+ */
+export type ArcMetrics = { 
+/**
+ * Number of source events the arc applies to
+ */
+num_source_events: number, 
+/**
+ * Number of source events satisfying the arc
+ */
+num_satisfying_events: number, 
+/**
+ * Fraction of violating source events (`null` if there are no source events)
+ */
+violation_fraction: number | null, 
+/**
+ * Fraction of satisfying source events divided by the baseline frequency of the target activity
+ *
+ * `lift = (num_satisfying_events / num_source_events) / (num_target_events / num_events)`,
+ * where `num_events` counts all events of the log and `num_target_events` the events of the target activity.
+ * Both only count the real events of the log, not the `<init>`/`<exit>` events added by the pre-processing.
+ *
+ * Values above 1 indicate that the target activity occurs as required more often than its overall frequency suggests.
+ * `null` if there are no source events or no (real) events of the target activity (e.g., for `<init>`/`<exit>` targets).
+ */
+lift: number | null, };
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EventViolation } from "./EventViolation";
+
+/**
+ * Structured conformance result of one [`OCDeclareConstraint`] on an OCEL
+ *
+ * In contrast to `get_for_all_evs_perf`, which only yields the violation fraction,
+ * this lists every violating source event together with the object bindings that caused the violation.
+ */
+export type ArcViolationReport = { 
+/**
+ * Number of source events (i.e., events of the `from` activity satisfying the `from` predicate) which were checked
+ */
+num_source_events: number, 
+/**
+ * Number of source events violating the arc for at least one binding
+ */
+num_violating_events: number, 
+/**
+ * Fraction of violating source events (`0.0` if there are no source events)
+ */
+violation_fraction: number, 
+/**
+ * All violating source events
+ */
+violations: Array<EventViolation>, };
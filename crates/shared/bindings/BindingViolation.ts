@@ -0,0 +1,36 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BoundObject } from "./BoundObject";
+import type { ViolationKind } from "./ViolationKind";
+
+/**
+ * A single violated object binding of a source event
+ */
+export type BindingViolation = { 
+/**
+ * The objects bound through the EACH label (one per EACH object type association)
+ */
+each_objects: Array<BoundObject>, 
+/**
+ * Objects which all need to be involved in a target event (ALL label)
+ */
+all_objects: Array<BoundObject>, 
+/**
+ * Objects of which at least one needs to be involved in a target event (ANY label)
+ */
+any_objects: Array<BoundObject>, 
+/**
+ * IDs of the target events matching this binding
+ */
+matching_events: Array<string>, 
+/**
+ * Observed number of matching target events
+ */
+observed_count: number, 
+/**
+ * Required (min, max) number of matching target events
+ */
+required_count: [number | null, number | null], 
+/**
+ * Whether too few or too many target events were observed
+ */
+kind: ViolationKind, };
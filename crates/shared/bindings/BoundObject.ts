@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An object bound in a label, together with the association through which it was bound
+ */
+export type BoundObject = { 
+/**
+ * Object ID
+ */
+object_id: string, 
+/**
+ * Object type of the object
+ */
+object_type: string, 
+/**
+ * Template string of the object type association (e.g., `orders`) binding the object
+ */
+association: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Comparison operator of an [`EventPredicate::Compare`]
+ */
+export type ComparisonOp = "Eq" | "Ne" | "Lt" | "Le" | "Gt" | "Ge";
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConflictKind } from "./ConflictKind";
+
+/**
+ * A set of arcs which can not be satisfied together
+ */
+export type Conflict = { kind: ConflictKind, 
+/**
+ * Indices of the conflicting arcs
+ */
+arcs: Array<number>, 
+/**
+ * Human-readable explanation of the conflict
+ */
+explanation: string, };
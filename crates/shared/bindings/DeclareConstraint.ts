@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DeclareTemplate } from "./DeclareTemplate";
+
+/**
+ * A classic Declare constraint
+ */
+export type DeclareConstraint = { template: DeclareTemplate, 
+/**
+ * Activities of the constraint (one for unary, two for binary templates)
+ */
+activities: Array<string>, 
+/**
+ * Activation, target and time conditions (empty if none of them is set)
+ */
+conditions: Array<string>, };
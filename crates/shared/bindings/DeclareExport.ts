@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DeclareModel } from "./DeclareModel";
+import type { Unsupported } from "./Unsupported";
+
+/**
+ * Result of converting OC-DECLARE arcs to a Declare model
+ */
+export type DeclareExport = { model: DeclareModel, unsupported: Array<Unsupported>, };
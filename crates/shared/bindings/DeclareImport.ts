@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OCDeclareArc } from "./OCDeclareArc";
+import type { Unsupported } from "./Unsupported";
+
+/**
+ * Result of converting a Declare model to OC-DECLARE arcs
+ */
+export type DeclareImport = { arcs: Array<OCDeclareArc>, unsupported: Array<Unsupported>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DeclareConstraint } from "./DeclareConstraint";
+
+/**
+ * A classic Declare model
+ */
+export type DeclareModel = { activities: Array<string>, constraints: Array<DeclareConstraint>, };
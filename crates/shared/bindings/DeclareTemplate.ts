@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A classic Declare template
+ */
+export type DeclareTemplate = { "Existence": number } | { "Absence": number } | { "Exactly": number } | "Init" | "End" | "RespondedExistence" | "CoExistence" | "Response" | "Precedence" | "Succession" | "ChainResponse" | "ChainPrecedence" | "ChainSuccession" | "AlternateResponse" | "AlternatePrecedence" | "AlternateSuccession" | "Choice" | "ExclusiveChoice" | "NotRespondedExistence" | "NotCoExistence" | "NotResponse" | "NotPrecedence" | "NotSuccession" | "NotChainResponse" | "NotChainPrecedence" | "NotChainSuccession";
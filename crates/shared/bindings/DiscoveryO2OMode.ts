@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Serializable counterpart of [`O2OMode`]
+ */
+export type DiscoveryO2OMode = "None" | "Direct" | "Reversed" | "Bidirectional";
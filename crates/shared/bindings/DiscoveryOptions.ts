@@ -0,0 +1,43 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DiscoveryO2OMode } from "./DiscoveryO2OMode";
+import type { ReductionMode } from "./ReductionMode";
+
+/**
+ * Serializable discovery options, used by the WASM and Python frontends
+ *
+ * Mirrors [`OCDeclareDiscoveryOptions`] and additionally specifies the reduction applied to the discovered arcs.
+ * Missing fields are filled with the defaults of [`OCDeclareDiscoveryOptions`].
+ */
+export type DiscoveryOptions = { 
+/**
+ * Maximum fraction of violating source events for a discovered arc
+ */
+noise_threshold: number, 
+/**
+ * Which object-to-object (O2O) relationships to use for object type associations
+ */
+o2o_mode: DiscoveryO2OMode, 
+/**
+ * Activities to consider (all if `null`)
+ */
+acts_to_use: Array<string> | null, 
+/**
+ * (min, max) counts used for generating candidate arcs
+ */
+counts_for_generation: [number | null, number | null], 
+/**
+ * (min, max) counts used for filtering candidate arcs
+ */
+counts_for_filter: [number | null, number | null], 
+/**
+ * Reduction applied to the discovered arcs
+ */
+reduction: ReductionMode, 
+/**
+ * Propose time bounds for the discovered `EF`/`EP`/`DF`/`DP` arcs (see [`propose_time_bounds`])
+ */
+time_bounds: boolean, 
+/**
+ * Compute quality metrics for the discovered arcs (see [`get_arc_metrics`])
+ */
+metrics: boolean, };
@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ArcMetrics } from "./ArcMetrics";
+import type { OCDeclareConstraint } from "./OCDeclareConstraint";
+import type { RemovedArc } from "./RemovedArc";
+
+/**
+ * Discovered constraints together with the provenance of the arcs removed by the reduction
+ */
+export type DiscoveryResult = { constraints: Array<OCDeclareConstraint>, 
+/**
+ * Quality metrics of the constraints (in the same order), empty unless [`DiscoveryOptions::metrics`] is enabled
+ */
+metrics: Array<ArcMetrics>, 
+/**
+ * Arcs removed by the configured reduction (empty if no reduction is applied)
+ */
+removed: Array<RemovedArc>, };
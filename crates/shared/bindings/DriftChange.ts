@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Change of a constraint compared to the previous non-empty window
+ */
+export type DriftChange = "ViolationIncrease" | "ViolationDecrease" | "Emerged" | "Disappeared";
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Options for [`detect_drift`]
+ */
+export type DriftOptions = { 
+/**
+ * Length of each window (in milliseconds)
+ */
+window_ms: number, 
+/**
+ * Offset between the starts of consecutive windows (in milliseconds, defaults to `window_ms`)
+ */
+step_ms: number | null, 
+/**
+ * Maximum violation fraction for a constraint to hold in a window
+ */
+noise_threshold: number, 
+/**
+ * Minimum absolute z-score of the two-proportion z-test for a significant change of the violation rate
+ */
+z_threshold: number, };
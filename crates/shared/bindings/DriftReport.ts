@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TimeWindow } from "./TimeWindow";
+import type { WindowConformance } from "./WindowConformance";
+
+/**
+ * Conformance of a set of constraints over time
+ */
+export type DriftReport = { windows: Array<TimeWindow>, 
+/**
+ * One point per constraint and window (ordered by constraint, then window)
+ */
+points: Array<WindowConformance>, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Justification why an arc is implied by a set of arcs (see [`entails`])
+ */
+export type Entailment = { 
+/**
+ * Indices of the implying arcs, forming a chain from the source to the target activity of the implied arc
+ *
+ * Contains a single arc if the implied arc is dominated by it, and is empty if the implied arc holds trivially.
+ */
+chain: Array<number>, };
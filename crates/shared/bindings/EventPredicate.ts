@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ComparisonOp } from "./ComparisonOp";
+import type { PredicateValue } from "./PredicateValue";
+
+/**
+ * Predicate on the attributes of an event, restricting which events of an activity a node refers to
+ */
+export type EventPredicate = { "type": "Compare", attribute: string, op: ComparisonOp, value: PredicateValue, } | { "type": "And", predicates: Array<EventPredicate>, } | { "type": "Or", predicates: Array<EventPredicate>, } | { "type": "Not", predicate: EventPredicate, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BindingViolation } from "./BindingViolation";
+
+/**
+ * A source event violating an arc
+ */
+export type EventViolation = { 
+/**
+ * ID of the source event
+ */
+source_event: string, 
+/**
+ * All bindings of the source event for which the target count is out of bounds
+ */
+bindings: Array<BindingViolation>, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ArcDiff } from "./ArcDiff";
+
+/**
+ * Semantic diff between two OC-DECLARE models
+ *
+ * Contains one entry for every arc of the old model (in order), followed by the added arcs of the new model.
+ */
+export type ModelDiff = { arcs: Array<ArcDiff>, };
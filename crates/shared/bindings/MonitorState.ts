@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * State of a constraint (or of a single activation) on the prefix of the stream observed so far
+ */
+export type MonitorState = "PermanentlySatisfied" | "TemporarilySatisfied" | "TemporarilyViolated" | "PermanentlyViolated";
@@ -0,0 +1,31 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MonitorState } from "./MonitorState";
+
+/**
+ * Notification about a changed state of an activation
+ */
+export type MonitorUpdate = { 
+/**
+ * Index of the constraint in the monitored model
+ */
+constraint: number, 
+/**
+ * ID of the source event of the activation
+ */
+source_event: string, 
+/**
+ * IDs of the EACH objects of the activation (one per EACH association)
+ */
+each_objects: Array<string>, 
+/**
+ * Number of matching target events observed so far
+ */
+observed_count: number, 
+/**
+ * New state of the activation
+ */
+state: MonitorState, 
+/**
+ * State of the whole constraint after the update
+ */
+constraint_state: MonitorState, };
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EventPredicate } from "./EventPredicate";
+import type { OCDeclareArcLabel } from "./OCDeclareArcLabel";
+import type { OCDeclareArcType } from "./OCDeclareArcType";
+import type { OCDeclareNode } from "./OCDeclareNode";
+import type { TimeBounds } from "./TimeBounds";
+
+/**
+ * An OC-DECLARE arc together with additional restrictions not expressible by [`OCDeclareArc`] alone
+ *
+ * Serializes to the same JSON as the plain arc, with the additional (optional) fields next to the arc fields.
+ * Thus, every serialized [`OCDeclareArc`] is also a valid serialized constraint.
+ */
+export type OCDeclareConstraint = { 
+/**
+ * Bounds on the time distance between source and matching target events
+ */
+time_bounds?: TimeBounds, 
+/**
+ * Predicate on the attributes of source events (only source events satisfying it are checked)
+ */
+from_predicate?: EventPredicate, 
+/**
+ * Predicate on the attributes of target events (only target events satisfying it are counted)
+ */
+to_predicate?: EventPredicate, from: OCDeclareNode, to: OCDeclareNode, arc_type: OCDeclareArcType, label: OCDeclareArcLabel, counts: [number | null, number | null], };
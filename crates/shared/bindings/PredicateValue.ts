@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Constant value of an [`EventPredicate::Compare`]
+ *
+ * Numbers are compared to integer and float attributes, strings lexicographically and timestamps chronologically.
+ */
+export type PredicateValue = { "type": "Number", "value": number } | { "type": "String", "value": string } | { "type": "Boolean", "value": boolean } | { "type": "Time", "value": string };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Reduction applied to a set of arcs (e.g., after discovery)
+ */
+export type ReductionMode = "None" | "OcArcs" | "Transitive" | "Entailment";
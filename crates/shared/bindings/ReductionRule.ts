@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Rule by which a reduction removed an arc `a -> c` because of the arcs `a -> b` and `b -> c`
+ */
+export type ReductionRule = "DominatedByPair" | "StrictlyDominatedByPair" | "FollowsChain" | "PrecedesChain" | "AssociationChain";
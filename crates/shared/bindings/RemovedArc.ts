@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OCDeclareArc } from "./OCDeclareArc";
+import type { ReductionRule } from "./ReductionRule";
+
+/**
+ * Provenance of an arc removed by a reduction
+ */
+export type RemovedArc = { 
+/**
+ * The removed arc (`a -> c`)
+ */
+arc: OCDeclareArc, 
+/**
+ * The implying arc starting at the source activity of the removed arc (`a -> b`)
+ */
+a: OCDeclareArc, 
+/**
+ * The implying arc ending at the target activity of the removed arc (`b -> c`)
+ */
+b: OCDeclareArc, rule: ReductionRule, 
+/**
+ * Human-readable explanation (e.g., "implied by EF(a, b, ...) and EF(b, c, ...)" or "dominated by EF(a, b, ...) and EF(b, c, ...)")
+ */
+explanation: string, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Minimum/maximum time distance (in milliseconds) between a source event and its matching target events
+ *
+ * For `EF`/`DF` arcs, the distance is measured from the source to the target event,
+ * for `EP`/`DP` from the target to the source event (i.e., it is never negative).
+ * For `AS` arcs, the absolute distance is used.
+ */
+export type TimeBounds = { 
+/**
+ * Minimum time distance (inclusive, unbounded if `null`)
+ */
+min_delay_ms: number | null, 
+/**
+ * Maximum time distance (inclusive, unbounded if `null`)
+ */
+max_delay_ms: number | null, };
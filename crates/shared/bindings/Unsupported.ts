@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A constraint or arc which can not be represented in the other formalism
+ */
+export type Unsupported = { 
+/**
+ * Index of the constraint or arc in the input
+ */
+index: number, 
+/**
+ * Why it can not be represented
+ */
+reason: string, };
@@ -0,0 +1,35 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DriftChange } from "./DriftChange";
+
+/**
+ * Conformance of one constraint in one window (one point of the time series)
+ */
+export type WindowConformance = { 
+/**
+ * Index of the constraint
+ */
+constraint: number, 
+/**
+ * Index of the window
+ */
+window: number, 
+/**
+ * Number of source events in the window
+ */
+num_source_events: number, 
+/**
+ * Number of violating source events in the window
+ */
+num_violating_events: number, 
+/**
+ * Fraction of violating source events (`null` if there are no source events in the window)
+ */
+violation_fraction: number | null, 
+/**
+ * Whether the constraint holds in the window (i.e., there are source events and the violation fraction is within the noise threshold)
+ */
+holds: boolean, 
+/**
+ * Changes compared to the previous window with source events
+ */
+changes: Array<DriftChange>, };
@@ -0,0 +1,284 @@
+//! Consistency checking of OC-DECLARE models
+//!
+//! Detects sets of arcs which can not be satisfied together by any source event they apply to
+//! (such that the source activity can never occur in a conforming log):
+//! - Count contradictions: For two arcs with the same activity pair, where the targets of one arc are a subset of the targets of the other
+//!   (dominated arc type and label, see [`crate::reduction`]), the stricter arc requires more targets than the other permits.
+//! - Infinite cycles: `EF`/`DF` (or `EP`/`DP`) arcs requiring at least one target event form a cycle
+//!   in which every arc binds the same object type through EACH or ALL, requiring infinitely many events of that object.
+//! - Lifecycle contradictions: An `EF`/`DF` arc from the `<exit>` event of an object type (or an `EP`/`DP` arc from the `<init>` event)
+//!   requires target events of the same object after its last (or before its first) event.
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    constraint::OCDeclareConstraint, template::print_constraint, OCDeclareArcType,
+    ObjectTypeAssociation,
+};
+
+/// Kind of a [`Conflict`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ConflictKind {
+    CountContradiction,
+    InfiniteCycle,
+    LifecycleContradiction,
+}
+
+/// A set of arcs which can not be satisfied together
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Conflict {
+    pub kind: ConflictKind,
+    /// Indices of the conflicting arcs
+    pub arcs: Vec<usize>,
+    /// Human-readable explanation of the conflict
+    pub explanation: String,
+}
+
+/// Object types bound to every target event of an arc (through EACH or ALL)
+fn required_object_types(constraint: &OCDeclareConstraint) -> HashSet<&str> {
+    let label = &constraint.arc.label;
+    label
+        .each
+        .iter()
+        .chain(label.all.iter())
+        .filter_map(|assoc| match assoc {
+            ObjectTypeAssociation::Simple { object_type } => Some(object_type.as_str()),
+            ObjectTypeAssociation::O2O { .. } => None,
+        })
+        .collect()
+}
+
+fn is_forward(arc_type: &OCDeclareArcType) -> bool {
+    matches!(arc_type, OCDeclareArcType::EF | OCDeclareArcType::DF)
+}
+
+fn is_backward(arc_type: &OCDeclareArcType) -> bool {
+    matches!(arc_type, OCDeclareArcType::EP | OCDeclareArcType::DP)
+}
+
+/// Check if every target event of `strict` is also a target event of `weak` (for the same source event and binding),
+/// such that `strict` never observes more target events than `weak`
+fn targets_subset(strict: &OCDeclareConstraint, weak: &OCDeclareConstraint) -> bool {
+    strict.arc.from == weak.arc.from
+        && strict.arc.to == weak.arc.to
+        && weak
+            .arc
+            .arc_type
+            .is_dominated_by_or_eq(&strict.arc.arc_type)
+        && weak.arc.label.is_dominated_by(&strict.arc.label)
+        && (weak.to_predicate.is_none() || weak.to_predicate == strict.to_predicate)
+        && (weak.time_bounds.is_none() || weak.time_bounds == strict.time_bounds)
+        && (weak.from_predicate.is_none()
+            || strict.from_predicate.is_none()
+            || weak.from_predicate == strict.from_predicate)
+}
+
+/// Minimum count required by `strict` and maximum count allowed by `weak`, if `strict` requires more targets than `weak` allows
+fn contradicting_counts(
+    strict: &OCDeclareConstraint,
+    weak: &OCDeclareConstraint,
+) -> Option<(usize, usize)> {
+    let (Some(min), Some(max)) = (strict.arc.counts.0, weak.arc.counts.1) else {
+        return None;
+    };
+    (min > max && targets_subset(strict, weak)).then_some((min, max))
+}
+
+fn count_contradictions(constraints: &[OCDeclareConstraint]) -> Vec<Conflict> {
+    let mut ret = Vec::new();
+    for (i, strict) in constraints.iter().enumerate() {
+        for (j, weak) in constraints.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let Some((min, max)) = contradicting_counts(strict, weak) else {
+                continue;
+            };
+            ret.push(Conflict {
+                kind: ConflictKind::CountContradiction,
+                arcs: vec![i, j],
+                explanation: format!(
+                    "{} requires at least {min} target events, but {} allows at most {max} of (a superset of) them",
+                    print_constraint(strict),
+                    print_constraint(weak)
+                ),
+            });
+        }
+    }
+    ret
+}
+
+fn infinite_cycles(constraints: &[OCDeclareConstraint]) -> Vec<Conflict> {
+    let mut ret = Vec::new();
+    let object_types: Vec<&str> = {
+        let mut ots: Vec<&str> = constraints.iter().flat_map(required_object_types).collect();
+        ots.sort();
+        ots.dedup();
+        ots
+    };
+    let activities: Vec<&str> = {
+        let mut acts: Vec<&str> = constraints
+            .iter()
+            .flat_map(|c| [c.arc.from.as_str(), c.arc.to.as_str()])
+            .collect();
+        acts.sort();
+        acts.dedup();
+        acts
+    };
+    let act_index = |act: &str| activities.binary_search(&act).unwrap();
+    for (direction, is_direction) in [
+        ("after", is_forward as fn(&OCDeclareArcType) -> bool),
+        ("before", is_backward),
+    ] {
+        for ot in &object_types {
+            // Arcs requiring another event of an object of type `ot` (which is again a source event)
+            let edges: Vec<usize> = (0..constraints.len())
+                .filter(|i| {
+                    let c = &constraints[*i];
+                    is_direction(&c.arc.arc_type)
+                        && c.arc.counts.0.is_some_and(|min| min >= 1)
+                        && c.from_predicate.is_none()
+                        && required_object_types(c).contains(ot)
+                })
+                .collect();
+            let n = activities.len();
+            let mut reach = vec![vec![false; n]; n];
+            for i in &edges {
+                let arc = &constraints[*i].arc;
+                reach[act_index(arc.from.as_str())][act_index(arc.to.as_str())] = true;
+            }
+            // Transitive closure (Floyd-Warshall)
+            for k in 0..n {
+                let via = reach[k].clone();
+                for row in reach.iter_mut().filter(|row| row[k]) {
+                    for (r, v) in row.iter_mut().zip(&via) {
+                        *r |= *v;
+                    }
+                }
+            }
+            // Arcs on a cycle, grouped by their (strongly connected) set of activities
+            let mut reported: HashSet<usize> = HashSet::new();
+            for (a, row) in reach.iter().enumerate() {
+                if !row[a] || reported.contains(&a) {
+                    continue;
+                }
+                let component: HashSet<usize> =
+                    (0..n).filter(|b| row[*b] && reach[*b][a]).collect();
+                reported.extend(component.iter().copied());
+                let arcs: Vec<usize> = edges
+                    .iter()
+                    .filter(|i| {
+                        let arc = &constraints[**i].arc;
+                        component.contains(&act_index(arc.from.as_str()))
+                            && component.contains(&act_index(arc.to.as_str()))
+                    })
+                    .copied()
+                    .collect();
+                ret.push(Conflict {
+                    kind: ConflictKind::InfiniteCycle,
+                    explanation: format!(
+                        "{} require each other to occur {direction} them for the same {ot} object, requiring infinitely many events",
+                        arcs.iter()
+                            .map(|i| print_constraint(&constraints[*i]))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    arcs,
+                });
+            }
+        }
+    }
+    ret
+}
+
+fn lifecycle_contradictions(constraints: &[OCDeclareConstraint]) -> Vec<Conflict> {
+    constraints
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| {
+            let (prefix, direction) = if is_forward(&c.arc.arc_type) {
+                ("<exit> ", "after the last")
+            } else if is_backward(&c.arc.arc_type) {
+                ("<init> ", "before the first")
+            } else {
+                return None;
+            };
+            let ot = c.arc.from.as_str().strip_prefix(prefix)?;
+            if c.arc.counts.0.is_some_and(|min| min >= 1) && required_object_types(c).contains(ot) {
+                Some(Conflict {
+                    kind: ConflictKind::LifecycleContradiction,
+                    arcs: vec![i],
+                    explanation: format!(
+                        "{} requires events of the {ot} object {direction} event of it",
+                        print_constraint(c)
+                    ),
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Check a model for arcs which can not be satisfied together
+///
+/// The returned conflicts are sound, but not complete: Every reported set of arcs is violated by every source event
+/// of the (first) arc for which the labels bind objects, but not every unsatisfiable model is detected.
+pub fn check_consistency(constraints: &[OCDeclareConstraint]) -> Vec<Conflict> {
+    let mut ret = count_contradictions(constraints);
+    ret.extend(infinite_cycles(constraints));
+    ret.extend(lifecycle_contradictions(constraints));
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::template::parse_model;
+
+    use super::*;
+
+    #[test]
+    fn conflicts() {
+        let constraints = parse_model(
+            "EF(place order, pay order, Each(order),1,*)\n\
+             AS(place order, pay order, Each(order),0,0)\n\
+             EF(pay order, ship order, Each(order),1,*)\n\
+             DF(ship order, pay order, All(order),1,1)\n\
+             EF(place order, ship order, Any(order),1,*)\n\
+             EF(<exit> order, place order, Each(order),1,*)\n\
+             EF(pay order, pay order, Each(item),0,0)\n\
+             EF(pay order, pay order, Each(item),0,0)",
+        )
+        .unwrap();
+        let conflicts: Vec<(ConflictKind, Vec<usize>)> = check_consistency(&constraints)
+            .into_iter()
+            .map(|c| (c.kind, c.arcs))
+            .collect();
+        assert_eq!(
+            conflicts,
+            vec![
+                (ConflictKind::CountContradiction, vec![0, 1]),
+                (ConflictKind::InfiniteCycle, vec![2, 3]),
+                (ConflictKind::LifecycleContradiction, vec![5]),
+            ]
+        );
+    }
+
+    #[test]
+    fn dominated_count_contradiction() {
+        // DF targets are a subset of EF targets
+        let constraints = parse_model(
+            "EF(place order, pay order, Each(order),0,1)\n\
+             DF(place order, pay order, Each(order),2,*)\n\
+             DF(place order, pay order, Any(order),2,*)",
+        )
+        .unwrap();
+        let conflicts = check_consistency(&constraints);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].arcs, vec![1, 0]);
+    }
+}
@@ -0,0 +1,138 @@
+use process_mining::ocel::linked_ocel::{
+    index_linked_ocel::EventIndex, IndexLinkedOCEL, LinkedOCELAccess,
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    diagnostics::get_violation_fraction, predicate::EventPredicate, template::print_constraint,
+    OCDeclareArc, OCDeclareArcType,
+};
+
+/// An OC-DECLARE arc together with additional restrictions not expressible by [`OCDeclareArc`] alone
+///
+/// Serializes to the same JSON as the plain arc, with the additional (optional) fields next to the arc fields.
+/// Thus, every serialized [`OCDeclareArc`] is also a valid serialized constraint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct OCDeclareConstraint {
+    #[serde(flatten)]
+    pub arc: OCDeclareArc,
+    /// Bounds on the time distance between source and matching target events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub time_bounds: Option<TimeBounds>,
+    /// Predicate on the attributes of source events (only source events satisfying it are checked)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub from_predicate: Option<EventPredicate>,
+    /// Predicate on the attributes of target events (only target events satisfying it are counted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub to_predicate: Option<EventPredicate>,
+}
+
+/// Minimum/maximum time distance (in milliseconds) between a source event and its matching target events
+///
+/// For `EF`/`DF` arcs, the distance is measured from the source to the target event,
+/// for `EP`/`DP` from the target to the source event (i.e., it is never negative).
+/// For `AS` arcs, the absolute distance is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TimeBounds {
+    /// Minimum time distance (inclusive, unbounded if `null`)
+    #[ts(type = "number | null")]
+    pub min_delay_ms: Option<i64>,
+    /// Maximum time distance (inclusive, unbounded if `null`)
+    #[ts(type = "number | null")]
+    pub max_delay_ms: Option<i64>,
+}
+
+impl TimeBounds {
+    pub fn new(min_delay_ms: Option<i64>, max_delay_ms: Option<i64>) -> Self {
+        Self {
+            min_delay_ms,
+            max_delay_ms,
+        }
+    }
+
+    /// Check if a time distance lies within the bounds
+    pub fn contains(&self, delay_ms: i64) -> bool {
+        self.min_delay_ms.is_none_or(|min| delay_ms >= min)
+            && self.max_delay_ms.is_none_or(|max| delay_ms <= max)
+    }
+
+    /// Check if neither a minimum nor a maximum is set
+    pub fn is_unbounded(&self) -> bool {
+        self.min_delay_ms.is_none() && self.max_delay_ms.is_none()
+    }
+}
+
+/// Time distance between a source event and a target event, as bounded by [`TimeBounds`]
+pub(crate) fn time_distance(
+    arc_type: &OCDeclareArcType,
+    locel: &IndexLinkedOCEL,
+    source: &EventIndex,
+    target: &EventIndex,
+) -> i64 {
+    let diff = (locel.get_ev(target).time - locel.get_ev(source).time).num_milliseconds();
+    match arc_type {
+        OCDeclareArcType::EF | OCDeclareArcType::DF => diff,
+        OCDeclareArcType::EP | OCDeclareArcType::DP => -diff,
+        OCDeclareArcType::ASS => diff.abs(),
+    }
+}
+
+impl OCDeclareConstraint {
+    pub fn new(arc: OCDeclareArc) -> Self {
+        Self {
+            arc,
+            time_bounds: None,
+            from_predicate: None,
+            to_predicate: None,
+        }
+    }
+
+    pub fn with_time_bounds(mut self, time_bounds: TimeBounds) -> Self {
+        self.time_bounds = Some(time_bounds).filter(|b| !b.is_unbounded());
+        self
+    }
+
+    pub fn with_predicates(
+        mut self,
+        from_predicate: Option<EventPredicate>,
+        to_predicate: Option<EventPredicate>,
+    ) -> Self {
+        self.from_predicate = from_predicate.map(EventPredicate::normalized);
+        self.to_predicate = to_predicate.map(EventPredicate::normalized);
+        self
+    }
+
+    /// Check if the constraint only consists of the plain arc (i.e., there are no additional restrictions)
+    pub fn is_plain_arc(&self) -> bool {
+        self.time_bounds.is_none() && self.from_predicate.is_none() && self.to_predicate.is_none()
+    }
+
+    /// Get the fraction of violating source events (see `OCDeclareArc::get_for_all_evs_perf`)
+    ///
+    /// For plain arcs, this directly uses `OCDeclareArc::get_for_all_evs_perf`.
+    /// Constraints with additional restrictions are checked using [`get_violation_fraction`].
+    pub fn get_for_all_evs_perf(&self, locel: &IndexLinkedOCEL) -> f64 {
+        if self.is_plain_arc() {
+            self.arc.get_for_all_evs_perf(locel)
+        } else {
+            get_violation_fraction(self, locel)
+        }
+    }
+
+    /// Get the template string of the constraint (see [`crate::template`])
+    pub fn as_template_string(&self) -> String {
+        print_constraint(self)
+    }
+}
+
+impl From<OCDeclareArc> for OCDeclareConstraint {
+    fn from(arc: OCDeclareArc) -> Self {
+        Self::new(arc)
+    }
+}
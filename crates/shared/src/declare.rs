@@ -0,0 +1,566 @@
+//! Conversion between classic Declare models (`.decl` files) and OC-DECLARE arcs
+//!
+//! Classic Declare constraints are interpreted on the traces of the objects of one chosen object type
+//! (i.e., on the log flattened onto that object type), which corresponds to OC-DECLARE arcs with a single `Each(..)`
+//! label of the object type. Unary templates use the `<init>`/`<exit>` events of the object type as source:
+//!
+//! | Declare | OC-DECLARE (with `Each(ot)`) |
+//! |---|---|
+//! | `ExistenceN[a]` / `AbsenceN[a]` / `ExactlyN[a]` | `AS(<init> ot, a, N, ∞)` / `AS(<init> ot, a, 0, N-1)` / `AS(<init> ot, a, N, N)` |
+//! | `Init[a]` / `End[a]` | `DF(<init> ot, a, 1, ∞)` / `DP(<exit> ot, a, 1, ∞)` |
+//! | `Responded Existence[a, b]` / `Co-Existence[a, b]` | `AS(a, b, 1, ∞)` / and `AS(b, a, 1, ∞)` |
+//! | `Response[a, b]` / `Precedence[a, b]` / `Succession[a, b]` | `EF(a, b, 1, ∞)` / `EP(b, a, 1, ∞)` / both |
+//! | `Chain Response[a, b]` / `Chain Precedence[a, b]` / `Chain Succession[a, b]` | `DF(a, b, 1, ∞)` / `DP(b, a, 1, ∞)` / both |
+//! | `Not Responded Existence[a, b]`, `Not Co-Existence[a, b]` | `AS(a, b, 0, 0)` |
+//! | `Not Response[a, b]`, `Not Succession[a, b]` / `Not Precedence[a, b]` | `EF(a, b, 0, 0)` / `EP(b, a, 0, 0)` |
+//! | `Not Chain Response[a, b]`, `Not Chain Succession[a, b]` / `Not Chain Precedence[a, b]` | `DF(a, b, 0, 0)` / `DP(b, a, 0, 0)` |
+//!
+//! Alternate and choice templates, constraints with data or time conditions, and arcs with other labels or
+//! count bounds can not be represented and are reported as [`Unsupported`].
+//!
+//! The `.decl` format contains one `activity <name>` line per activity and one constraint per line, e.g.,
+//! `Response[place order, pay order] | | |`. Attribute declarations (`bind ..` and `<attribute>: ..` lines) are skipped.
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    template::ParseError, OCDeclareArc, OCDeclareArcLabel, OCDeclareArcType, OCDeclareNode,
+    ObjectTypeAssociation, EXIT_EVENT_PREFIX, INIT_EVENT_PREFIX,
+};
+
+/// A classic Declare template
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum DeclareTemplate {
+    /// At least the given number of occurrences
+    Existence(usize),
+    /// Less than the given number of occurrences
+    Absence(usize),
+    /// Exactly the given number of occurrences
+    Exactly(usize),
+    Init,
+    End,
+    RespondedExistence,
+    CoExistence,
+    Response,
+    Precedence,
+    Succession,
+    ChainResponse,
+    ChainPrecedence,
+    ChainSuccession,
+    AlternateResponse,
+    AlternatePrecedence,
+    AlternateSuccession,
+    Choice,
+    ExclusiveChoice,
+    NotRespondedExistence,
+    NotCoExistence,
+    NotResponse,
+    NotPrecedence,
+    NotSuccession,
+    NotChainResponse,
+    NotChainPrecedence,
+    NotChainSuccession,
+}
+
+/// Binary templates with their names in `.decl` files
+const BINARY_TEMPLATES: [(DeclareTemplate, &str); 21] = [
+    (DeclareTemplate::RespondedExistence, "Responded Existence"),
+    (DeclareTemplate::CoExistence, "Co-Existence"),
+    (DeclareTemplate::Response, "Response"),
+    (DeclareTemplate::Precedence, "Precedence"),
+    (DeclareTemplate::Succession, "Succession"),
+    (DeclareTemplate::ChainResponse, "Chain Response"),
+    (DeclareTemplate::ChainPrecedence, "Chain Precedence"),
+    (DeclareTemplate::ChainSuccession, "Chain Succession"),
+    (DeclareTemplate::AlternateResponse, "Alternate Response"),
+    (DeclareTemplate::AlternatePrecedence, "Alternate Precedence"),
+    (DeclareTemplate::AlternateSuccession, "Alternate Succession"),
+    (DeclareTemplate::Choice, "Choice"),
+    (DeclareTemplate::ExclusiveChoice, "Exclusive Choice"),
+    (
+        DeclareTemplate::NotRespondedExistence,
+        "Not Responded Existence",
+    ),
+    (DeclareTemplate::NotCoExistence, "Not Co-Existence"),
+    (DeclareTemplate::NotResponse, "Not Response"),
+    (DeclareTemplate::NotPrecedence, "Not Precedence"),
+    (DeclareTemplate::NotSuccession, "Not Succession"),
+    (DeclareTemplate::NotChainResponse, "Not Chain Response"),
+    (DeclareTemplate::NotChainPrecedence, "Not Chain Precedence"),
+    (DeclareTemplate::NotChainSuccession, "Not Chain Succession"),
+];
+
+/// Normalize a template name for matching (ignoring case, spaces, `-` and `_`)
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+impl DeclareTemplate {
+    /// Name of the template in `.decl` files (e.g., `Chain Response` or `Existence2`)
+    pub fn name(&self) -> String {
+        let with_count = |name: &str, n: usize| {
+            if n == 1 {
+                name.to_string()
+            } else {
+                format!("{name}{n}")
+            }
+        };
+        match self {
+            Self::Existence(n) => with_count("Existence", *n),
+            Self::Absence(n) => with_count("Absence", *n),
+            Self::Exactly(n) => with_count("Exactly", *n),
+            Self::Init => "Init".to_string(),
+            Self::End => "End".to_string(),
+            binary => BINARY_TEMPLATES
+                .iter()
+                .find(|(t, _)| t == binary)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Parse a template name (case-insensitive, ignoring spaces, `-` and `_`)
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = normalize_name(name);
+        if let Some((_, t)) = BINARY_TEMPLATES
+            .iter()
+            .map(|(t, n)| (normalize_name(n), t))
+            .find(|(n, _)| *n == name)
+        {
+            return Some(*t);
+        }
+        match name.as_str() {
+            "init" => return Some(Self::Init),
+            "end" => return Some(Self::End),
+            _ => {}
+        }
+        for (prefix, template) in [
+            ("existence", Self::Existence as fn(usize) -> Self),
+            ("absence", Self::Absence),
+            ("exactly", Self::Exactly),
+        ] {
+            if let Some(n) = name.strip_prefix(prefix) {
+                let n = if n.is_empty() { 1 } else { n.parse().ok()? };
+                return (n > 0).then(|| template(n));
+            }
+        }
+        None
+    }
+
+    /// Number of activities of the template (1 for unary, 2 for binary templates)
+    pub fn arity(&self) -> usize {
+        match self {
+            Self::Existence(_) | Self::Absence(_) | Self::Exactly(_) | Self::Init | Self::End => 1,
+            _ => 2,
+        }
+    }
+}
+
+/// A classic Declare constraint
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DeclareConstraint {
+    pub template: DeclareTemplate,
+    /// Activities of the constraint (one for unary, two for binary templates)
+    pub activities: Vec<String>,
+    /// Activation, target and time conditions (empty if none of them is set)
+    #[serde(default)]
+    pub conditions: Vec<String>,
+}
+
+impl DeclareConstraint {
+    pub fn new(template: DeclareTemplate, activities: Vec<String>) -> Self {
+        Self {
+            template,
+            activities,
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Check if the constraint has any non-empty activation, target or time condition
+    pub fn has_conditions(&self) -> bool {
+        self.conditions.iter().any(|c| !c.is_empty())
+    }
+}
+
+/// A classic Declare model
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DeclareModel {
+    pub activities: Vec<String>,
+    pub constraints: Vec<DeclareConstraint>,
+}
+
+/// A constraint or arc which can not be represented in the other formalism
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Unsupported {
+    /// Index of the constraint or arc in the input
+    pub index: usize,
+    /// Why it can not be represented
+    pub reason: String,
+}
+
+/// Result of converting a Declare model to OC-DECLARE arcs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DeclareImport {
+    pub arcs: Vec<OCDeclareArc>,
+    pub unsupported: Vec<Unsupported>,
+}
+
+/// Result of converting OC-DECLARE arcs to a Declare model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DeclareExport {
+    pub model: DeclareModel,
+    pub unsupported: Vec<Unsupported>,
+}
+
+/// Parse a Declare model in the `.decl` format
+pub fn parse_decl(input: &str) -> Result<DeclareModel, ParseError> {
+    let mut model = DeclareModel::default();
+    for (i, line) in input.lines().enumerate() {
+        let error = |column: usize, message: String| ParseError {
+            line: i + 1,
+            column: column + 1,
+            message,
+        };
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("bind ") {
+            continue;
+        }
+        if let Some(activity) = trimmed.strip_prefix("activity ") {
+            model.activities.push(activity.trim().to_string());
+            continue;
+        }
+        let Some(open) = trimmed.find('[') else {
+            if trimmed.contains(':') {
+                // Attribute declaration (e.g., `price: integer between 0 and 1000`)
+                continue;
+            }
+            return Err(error(
+                0,
+                format!("Expected a constraint but got '{trimmed}'"),
+            ));
+        };
+        let close = trimmed
+            .find(']')
+            .ok_or_else(|| error(trimmed.len(), "Missing ']'".to_string()))?;
+        let template = DeclareTemplate::parse(&trimmed[..open]).ok_or_else(|| {
+            error(
+                0,
+                format!("Unknown Declare template '{}'", trimmed[..open].trim()),
+            )
+        })?;
+        let activities: Vec<String> = trimmed[open + 1..close]
+            .split(',')
+            .map(|a| a.trim().to_string())
+            .collect();
+        if activities.len() != template.arity() || activities.iter().any(|a| a.is_empty()) {
+            return Err(error(
+                open + 1,
+                format!(
+                    "{} expects {} activities",
+                    template.name(),
+                    template.arity()
+                ),
+            ));
+        }
+        let mut conditions: Vec<String> = trimmed[close + 1..]
+            .split('|')
+            .skip(1)
+            .map(|c| c.trim().to_string())
+            .collect();
+        if conditions.iter().all(|c| c.is_empty()) {
+            conditions.clear();
+        }
+        model.constraints.push(DeclareConstraint {
+            template,
+            activities,
+            conditions,
+        });
+    }
+    Ok(model)
+}
+
+/// Print a Declare model in the `.decl` format
+pub fn print_decl(model: &DeclareModel) -> String {
+    let mut ret = String::new();
+    for activity in &model.activities {
+        ret.push_str(&format!("activity {activity}\n"));
+    }
+    for constraint in &model.constraints {
+        let num_conditions = if constraint.template.arity() == 1 {
+            2
+        } else {
+            3
+        };
+        let conditions: Vec<&str> = (0..num_conditions)
+            .map(|i| constraint.conditions.get(i).map_or("", String::as_str))
+            .collect();
+        ret.push_str(&format!(
+            "{}[{}] |{}\n",
+            constraint.template.name(),
+            constraint.activities.join(", "),
+            conditions.join(" |")
+        ));
+    }
+    ret
+}
+
+fn each_arc(
+    object_type: &str,
+    arc_type: OCDeclareArcType,
+    from: &str,
+    to: &str,
+    counts: (Option<usize>, Option<usize>),
+) -> OCDeclareArc {
+    OCDeclareArc {
+        from: OCDeclareNode::new(from),
+        to: OCDeclareNode::new(to),
+        arc_type,
+        label: OCDeclareArcLabel {
+            each: vec![ObjectTypeAssociation::new_simple(object_type)],
+            ..Default::default()
+        },
+        counts,
+    }
+}
+
+/// Convert a Declare model to OC-DECLARE arcs for the given object type
+pub fn declare_to_oc(model: &DeclareModel, object_type: &str) -> DeclareImport {
+    use DeclareTemplate::*;
+    use OCDeclareArcType::*;
+    let init = format!("{INIT_EVENT_PREFIX} {object_type}");
+    let exit = format!("{EXIT_EVENT_PREFIX} {object_type}");
+    let mut ret = DeclareImport {
+        arcs: Vec::new(),
+        unsupported: Vec::new(),
+    };
+    let required = (Some(1), None);
+    let forbidden = (Some(0), Some(0));
+    for (index, constraint) in model.constraints.iter().enumerate() {
+        if constraint.has_conditions() {
+            ret.unsupported.push(Unsupported {
+                index,
+                reason: format!(
+                    "{} has data or time conditions, which are not supported",
+                    constraint.template.name()
+                ),
+            });
+            continue;
+        }
+        let a = constraint.activities[0].as_str();
+        let b = constraint.activities.get(1).map_or(a, String::as_str);
+        let arc = |arc_type, from, to, counts| each_arc(object_type, arc_type, from, to, counts);
+        let arcs = match constraint.template {
+            Existence(n) => vec![arc(ASS, &init, a, (Some(n), None))],
+            Absence(n) => vec![arc(ASS, &init, a, (Some(0), Some(n - 1)))],
+            Exactly(n) => vec![arc(ASS, &init, a, (Some(n), Some(n)))],
+            Init => vec![arc(DF, &init, a, required)],
+            End => vec![arc(DP, &exit, a, required)],
+            RespondedExistence => vec![arc(ASS, a, b, required)],
+            CoExistence => vec![arc(ASS, a, b, required), arc(ASS, b, a, required)],
+            Response => vec![arc(EF, a, b, required)],
+            Precedence => vec![arc(EP, b, a, required)],
+            Succession => vec![arc(EF, a, b, required), arc(EP, b, a, required)],
+            ChainResponse => vec![arc(DF, a, b, required)],
+            ChainPrecedence => vec![arc(DP, b, a, required)],
+            ChainSuccession => vec![arc(DF, a, b, required), arc(DP, b, a, required)],
+            NotRespondedExistence | NotCoExistence => vec![arc(ASS, a, b, forbidden)],
+            NotResponse | NotSuccession => vec![arc(EF, a, b, forbidden)],
+            NotPrecedence => vec![arc(EP, b, a, forbidden)],
+            NotChainResponse | NotChainSuccession => vec![arc(DF, a, b, forbidden)],
+            NotChainPrecedence => vec![arc(DP, b, a, forbidden)],
+            AlternateResponse | AlternatePrecedence | AlternateSuccession | Choice
+            | ExclusiveChoice => {
+                ret.unsupported.push(Unsupported {
+                    index,
+                    reason: format!(
+                        "{} can not be expressed in OC-DECLARE",
+                        constraint.template.name()
+                    ),
+                });
+                continue;
+            }
+        };
+        ret.arcs.extend(arcs);
+    }
+    ret
+}
+
+/// Convert OC-DECLARE arcs to a Declare model for the given object type
+///
+/// Only arcs with a single `Each(object_type)` label and the count bounds listed in the
+/// [module documentation](self) can be represented.
+pub fn oc_to_declare(arcs: &[OCDeclareArc], object_type: &str) -> DeclareExport {
+    use DeclareTemplate::*;
+    use OCDeclareArcType::*;
+    let init = format!("{INIT_EVENT_PREFIX} {object_type}");
+    let exit = format!("{EXIT_EVENT_PREFIX} {object_type}");
+    let expected_label = OCDeclareArcLabel {
+        each: vec![ObjectTypeAssociation::new_simple(object_type)],
+        ..Default::default()
+    };
+    let is_object_event = |act: &str| {
+        [INIT_EVENT_PREFIX, EXIT_EVENT_PREFIX]
+            .iter()
+            .any(|prefix| act.starts_with(prefix))
+    };
+    let mut ret = DeclareExport {
+        model: DeclareModel::default(),
+        unsupported: Vec::new(),
+    };
+    for (index, arc) in arcs.iter().enumerate() {
+        let (from, to) = (arc.from.as_str(), arc.to.as_str());
+        let min = arc.counts.0.unwrap_or(0);
+        let max = arc.counts.1;
+        let unary = |template| Some(vec![DeclareConstraint::new(template, vec![to.to_string()])]);
+        let binary = |template, a: &str, b: &str| {
+            Some(vec![DeclareConstraint::new(
+                template,
+                vec![a.to_string(), b.to_string()],
+            )])
+        };
+        let constraints = if arc.label != expected_label || is_object_event(to) {
+            None
+        } else if from == init {
+            match (arc.arc_type, min, max) {
+                (ASS, 0, Some(max)) => unary(Absence(max + 1)),
+                (ASS, min, None) if min > 0 => unary(Existence(min)),
+                (ASS, min, Some(max)) if min == max => unary(Exactly(min)),
+                (ASS, min, Some(max)) if min > 0 && max > min => Some(vec![
+                    DeclareConstraint::new(Existence(min), vec![to.to_string()]),
+                    DeclareConstraint::new(Absence(max + 1), vec![to.to_string()]),
+                ]),
+                (DF, 1.., None | Some(1..)) => unary(Init),
+                _ => None,
+            }
+        } else if from == exit {
+            match (arc.arc_type, min, max) {
+                (DP, 1.., None | Some(1..)) => unary(End),
+                _ => None,
+            }
+        } else if is_object_event(from) {
+            None
+        } else {
+            match (arc.arc_type, min, max) {
+                (ASS, 1, None) => binary(RespondedExistence, from, to),
+                (EF, 1, None) => binary(Response, from, to),
+                (EP, 1, None) => binary(Precedence, to, from),
+                (DF, 1.., None | Some(1..)) => binary(ChainResponse, from, to),
+                (DP, 1.., None | Some(1..)) => binary(ChainPrecedence, to, from),
+                (ASS, 0, Some(0)) => binary(NotRespondedExistence, from, to),
+                (EF, 0, Some(0)) => binary(NotResponse, from, to),
+                (EP, 0, Some(0)) => binary(NotPrecedence, to, from),
+                (DF, 0, Some(0)) => binary(NotChainResponse, from, to),
+                (DP, 0, Some(0)) => binary(NotChainPrecedence, to, from),
+                _ => None,
+            }
+        };
+        match constraints {
+            Some(constraints) => {
+                for constraint in &constraints {
+                    for activity in &constraint.activities {
+                        if !ret.model.activities.contains(activity) {
+                            ret.model.activities.push(activity.clone());
+                        }
+                    }
+                }
+                ret.model.constraints.extend(constraints);
+            }
+            None => ret.unsupported.push(Unsupported {
+                index,
+                reason: if arc.label != expected_label {
+                    format!(
+                        "{} does not have the label Each({object_type})",
+                        arc.as_template_string()
+                    )
+                } else {
+                    format!(
+                        "{} has no corresponding Declare template",
+                        arc.as_template_string()
+                    )
+                },
+            }),
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::template::parse_model;
+
+    use super::*;
+
+    const DECL: &str = "activity place order\n\
+        activity pay order\n\
+        activity ship order\n\
+        bind pay order: amount\n\
+        amount: float between 0 and 1000\n\
+        Existence[place order] | |\n\
+        Absence2[ship order] | |\n\
+        Init[place order] | |\n\
+        Response[place order, pay order] | | |\n\
+        Chain Precedence[pay order, ship order] | | |\n\
+        Not Co-Existence[ship order, place order] | | |\n\
+        Alternate Response[place order, ship order] | | |\n\
+        Response[pay order, ship order] |A.amount > 10 | |\n";
+
+    #[test]
+    fn import() {
+        let model = parse_decl(DECL).unwrap();
+        assert_eq!(model.activities.len(), 3);
+        let import = declare_to_oc(&model, "orders");
+        let expected = parse_model(
+            "AS(<init> orders, place order, Each(orders),1,∞)\n\
+             AS(<init> orders, ship order, Each(orders),0,1)\n\
+             DF(<init> orders, place order, Each(orders),1,∞)\n\
+             EF(place order, pay order, Each(orders),1,∞)\n\
+             DP(ship order, pay order, Each(orders),1,∞)\n\
+             AS(ship order, place order, Each(orders),0,0)",
+        )
+        .unwrap();
+        assert_eq!(
+            import.arcs,
+            expected.into_iter().map(|c| c.arc).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            import
+                .unsupported
+                .iter()
+                .map(|u| u.index)
+                .collect::<Vec<_>>(),
+            vec![6, 7]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let model = parse_decl(DECL).unwrap();
+        let import = declare_to_oc(&model, "orders");
+        let export = oc_to_declare(&import.arcs, "orders");
+        assert!(export.unsupported.is_empty());
+        assert_eq!(declare_to_oc(&export.model, "orders").arcs, import.arcs);
+        assert_eq!(
+            parse_decl(&print_decl(&export.model)).unwrap(),
+            export.model
+        );
+        // Other labels and count bounds can not be represented
+        let arcs: Vec<OCDeclareArc> = parse_model(
+            "EF(place order, pay order, Any(orders),1,∞)\n\
+             EF(place order, pay order, Each(orders),2,∞)",
+        )
+        .unwrap()
+        .into_iter()
+        .map(|c| c.arc)
+        .collect();
+        assert_eq!(oc_to_declare(&arcs, "orders").unsupported.len(), 2);
+    }
+}
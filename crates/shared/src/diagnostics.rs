@@ -0,0 +1,716 @@
+use std::{collections::HashSet, hash::Hash};
+
+use chrono::{DateTime, FixedOffset};
+use itertools::Itertools;
+use process_mining::ocel::linked_ocel::{
+    index_linked_ocel::{EventIndex, ObjectIndex},
+    IndexLinkedOCEL, LinkedOCELAccess,
+};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    constraint::{time_distance, OCDeclareConstraint},
+    OCDeclareArc, OCDeclareArcLabel, OCDeclareArcType, ObjectTypeAssociation,
+};
+
+/// Structured conformance result of one [`OCDeclareConstraint`] on an OCEL
+///
+/// In contrast to `get_for_all_evs_perf`, which only yields the violation fraction,
+/// this lists every violating source event together with the object bindings that caused the violation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ArcViolationReport {
+    /// Number of source events (i.e., events of the `from` activity satisfying the `from` predicate) which were checked
+    pub num_source_events: usize,
+    /// Number of source events violating the arc for at least one binding
+    pub num_violating_events: usize,
+    /// Fraction of violating source events (`0.0` if there are no source events)
+    pub violation_fraction: f64,
+    /// All violating source events
+    pub violations: Vec<EventViolation>,
+}
+
+/// A source event violating an arc
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct EventViolation {
+    /// ID of the source event
+    pub source_event: String,
+    /// All bindings of the source event for which the target count is out of bounds
+    pub bindings: Vec<BindingViolation>,
+}
+
+/// A single violated object binding of a source event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BindingViolation {
+    /// The objects bound through the EACH label (one per EACH object type association)
+    pub each_objects: Vec<BoundObject>,
+    /// Objects which all need to be involved in a target event (ALL label)
+    pub all_objects: Vec<BoundObject>,
+    /// Objects of which at least one needs to be involved in a target event (ANY label)
+    pub any_objects: Vec<BoundObject>,
+    /// IDs of the target events matching this binding
+    pub matching_events: Vec<String>,
+    /// Observed number of matching target events
+    pub observed_count: usize,
+    /// Required (min, max) number of matching target events
+    pub required_count: (Option<usize>, Option<usize>),
+    /// Whether too few or too many target events were observed
+    pub kind: ViolationKind,
+}
+
+/// An object bound in a label, together with the association through which it was bound
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BoundObject {
+    /// Object ID
+    pub object_id: String,
+    /// Object type of the object
+    pub object_type: String,
+    /// Template string of the object type association (e.g., `orders`) binding the object
+    pub association: String,
+}
+
+/// Kind of count violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ViolationKind {
+    /// Fewer target events than the min count
+    TooFew,
+    /// More target events than the max count
+    TooMany,
+}
+
+/// Conformance of a single source event of a constraint (e.g., one row of a tabular conformance result)
+///
+/// In contrast to [`EventViolation`], this is also available for satisfied source events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceEventConformance {
+    /// ID of the source event
+    pub event_id: String,
+    /// Activity of the source event
+    pub activity: String,
+    /// Timestamp of the source event
+    pub timestamp: DateTime<FixedOffset>,
+    /// IDs of all objects involved in the source event
+    pub objects: Vec<String>,
+    /// Required (min, max) number of matching target events per binding
+    pub required_count: (Option<usize>, Option<usize>),
+    /// Smallest number of matching target events over all bindings (`None` if there are no bindings)
+    pub min_observed_count: Option<usize>,
+    /// Largest number of matching target events over all bindings (`None` if there are no bindings)
+    pub max_observed_count: Option<usize>,
+    /// Number of bindings of the source event
+    pub num_bindings: usize,
+    /// Number of violated bindings of the source event
+    pub num_violated_bindings: usize,
+    /// Whether the source event violates the constraint (i.e., has at least one violated binding)
+    pub violated: bool,
+}
+
+/// One concrete binding of a source event: One object per EACH association and the objects of every ALL/ANY association
+///
+/// Objects are stored together with the index of the association (in the label of the arc) through which they are bound.
+///
+/// The objects are generic, so that bindings can also be built on an event stream (see [`crate::monitor`]).
+#[derive(Debug, Clone)]
+pub(crate) struct Binding<O = ObjectIndex> {
+    pub each: Vec<(O, usize)>,
+    pub all: Vec<(O, usize)>,
+    pub any: Vec<(O, usize)>,
+}
+
+/// Object requirement on target events, derived from a [`Binding`] (as `SetFilter` in `process_mining`)
+#[derive(Debug, Clone)]
+pub(crate) enum SetFilter<O = ObjectIndex> {
+    /// All of the objects need to be involved (trivially satisfied if empty)
+    All(Vec<O>),
+    /// At least one of the objects needs to be involved (never satisfied if empty)
+    Any(Vec<O>),
+}
+
+impl<O: Eq + Hash> SetFilter<O> {
+    pub(crate) fn check(&self, obs: &HashSet<O>) -> bool {
+        match self {
+            SetFilter::All(items) => items.iter().all(|o| obs.contains(o)),
+            SetFilter::Any(items) => items.iter().any(|o| obs.contains(o)),
+        }
+    }
+}
+
+impl<O: Copy> Binding<O> {
+    /// Object requirements on target events, in the order used by `process_mining`:
+    /// One filter per ALL association, one for the EACH objects and one per ANY association
+    ///
+    /// ALL and ANY associations are ordered by the number of objects of their (target) object type (descending),
+    /// as given by `num_objects`.
+    /// The order matters, as target candidates are looked up through the first filter.
+    pub(crate) fn filters(
+        &self,
+        label: &OCDeclareArcLabel,
+        num_objects: impl Fn(&str) -> usize,
+    ) -> Vec<SetFilter<O>> {
+        let by_num_objects = |assocs: &[ObjectTypeAssociation]| -> Vec<usize> {
+            let mut indices: Vec<usize> = (0..assocs.len()).collect();
+            indices.sort_by_key(|i| {
+                let object_type = match &assocs[*i] {
+                    ObjectTypeAssociation::Simple { object_type } => object_type,
+                    ObjectTypeAssociation::O2O { second, .. } => second,
+                };
+                std::cmp::Reverse(num_objects(object_type))
+            });
+            indices
+        };
+        let objects_of = |obs: &[(O, usize)], assoc_i: usize| -> Vec<O> {
+            obs.iter()
+                .filter(|(_, i)| *i == assoc_i)
+                .map(|(o, _)| *o)
+                .collect()
+        };
+        let mut ret: Vec<SetFilter<O>> = by_num_objects(&label.all)
+            .into_iter()
+            .map(|i| SetFilter::All(objects_of(&self.all, i)))
+            .collect();
+        if !self.each.is_empty() {
+            ret.push(SetFilter::All(self.each.iter().map(|(o, _)| *o).collect()));
+        }
+        ret.extend(
+            by_num_objects(&label.any)
+                .into_iter()
+                .map(|i| SetFilter::Any(objects_of(&self.any, i))),
+        );
+        ret
+    }
+}
+
+/// Get all objects bound to an event through an object type association
+///
+/// As in `process_mining`, objects related to several involved objects (for O2O associations) are returned multiple times.
+pub(crate) fn objects_for_association(
+    locel: &IndexLinkedOCEL,
+    ev: &EventIndex,
+    assoc: &ObjectTypeAssociation,
+) -> Vec<ObjectIndex> {
+    match assoc {
+        ObjectTypeAssociation::Simple { object_type } => locel
+            .get_e2o(ev)
+            .map(|(_q, o)| *o)
+            .filter(|o| &locel.get_ob(o).object_type == object_type)
+            .collect(),
+        ObjectTypeAssociation::O2O {
+            first,
+            second,
+            reversed,
+        } => locel
+            .get_e2o(ev)
+            .map(|(_q, o)| *o)
+            .filter(|o| &locel.get_ob(o).object_type == first)
+            .flat_map(|o| {
+                let related: Vec<ObjectIndex> = if *reversed {
+                    locel.get_o2o_rev(&o).map(|(_q, o2)| *o2).collect()
+                } else {
+                    locel.get_o2o(&o).map(|(_q, o2)| *o2).collect()
+                };
+                related
+            })
+            .filter(|o2| &locel.get_ob(o2).object_type == second)
+            .collect(),
+    }
+}
+
+/// Get the set of objects involved in an event
+pub(crate) fn event_objects(locel: &IndexLinkedOCEL, ev: &EventIndex) -> HashSet<ObjectIndex> {
+    locel.get_e2o(ev).map(|(_q, o)| *o).collect()
+}
+
+/// Get all bindings of a source event for the label of an arc
+///
+/// Every combination of one object per EACH association yields one binding
+/// (i.e., there are no bindings if an EACH association does not bind any object).
+/// If the arc has no EACH associations, a single binding is returned.
+pub(crate) fn get_bindings(
+    arc: &OCDeclareArc,
+    locel: &IndexLinkedOCEL,
+    ev: &EventIndex,
+) -> Vec<Binding> {
+    bindings_for(&arc.label, |assoc| {
+        objects_for_association(locel, ev, assoc)
+    })
+}
+
+/// Same as [`get_bindings`], with the objects bound through an association given by `objects_for`
+pub(crate) fn bindings_for<O: Copy>(
+    label: &OCDeclareArcLabel,
+    objects_for: impl Fn(&ObjectTypeAssociation) -> Vec<O>,
+) -> Vec<Binding<O>> {
+    let collect = |assocs: &Vec<ObjectTypeAssociation>| -> Vec<(O, usize)> {
+        assocs
+            .iter()
+            .enumerate()
+            .flat_map(|(i, assoc)| objects_for(assoc).into_iter().map(move |o| (o, i)))
+            .collect()
+    };
+    let all = collect(&label.all);
+    let any = collect(&label.any);
+    label
+        .each
+        .iter()
+        .enumerate()
+        .map(|(i, assoc)| objects_for(assoc).into_iter().map(move |o| (o, i)))
+        .multi_cartesian_product()
+        .map(|each| Binding {
+            each,
+            all: all.clone(),
+            any: any.clone(),
+        })
+        .collect()
+}
+
+/// Get all target events matching a binding of the source event `ev`
+///
+/// The semantics are the ones of `OCDeclareArc::get_for_all_evs_perf` in `process_mining`:
+/// A target event matches if it has the `to` activity of the arc, satisfies all object requirements of the binding
+/// (involving the EACH objects and all objects of every ALL association, and at least one object of every ANY association),
+/// and is in the temporal relation given by the arc type:
+/// - `ASS`: No temporal restriction (i.e., the source event itself can also match)
+/// - `EF`/`EP`: The target event occurs strictly after/before the source event (events with the same timestamp are unordered)
+/// - `DF`/`DP`: The target event is the nearest event strictly after/before the source event satisfying all object requirements
+///
+/// As in `process_mining`, a target event is matched once per object if the label only consists of a single
+/// ANY association binding several objects, and no target events match if the (first) ALL association binds no objects.
+///
+/// Additionally, the time distance to the source event needs to be within the time bounds of the constraint (if any)
+/// and the target event needs to satisfy the `to` predicate of the constraint (if any).
+pub(crate) fn get_matching_events(
+    constraint: &OCDeclareConstraint,
+    locel: &IndexLinkedOCEL,
+    ev: &EventIndex,
+    binding: &Binding,
+) -> Vec<EventIndex> {
+    let arc = &constraint.arc;
+    let to_act = arc.to.as_str();
+    let own_time = locel.get_ev(ev).time;
+    let filters = binding.filters(&arc.label, |object_type| {
+        locel.get_obs_of_type(object_type).count()
+    });
+    let is_direct = matches!(arc.arc_type, OCDeclareArcType::DF | OCDeclareArcType::DP);
+    let in_relation = |e2: &EventIndex| {
+        let time = locel.get_ev(e2).time;
+        match arc.arc_type {
+            OCDeclareArcType::ASS => true,
+            OCDeclareArcType::EF | OCDeclareArcType::DF => time > own_time,
+            OCDeclareArcType::EP | OCDeclareArcType::DP => time < own_time,
+        }
+    };
+    // Candidates are looked up through the first filter (for DF/DP, events of all activities are candidates)
+    let of_activity = |e2: &EventIndex| is_direct || locel.get_ev(e2).event_type == to_act;
+    let candidates: Vec<EventIndex> = match filters.first() {
+        None if is_direct => locel.get_all_evs().collect(),
+        None => locel.get_evs_of_type(to_act).copied().collect(),
+        Some(SetFilter::Any(items)) => items
+            .iter()
+            .flat_map(|o| locel.get_e2o_rev(o).map(|(_q, e)| *e))
+            .filter(|e2| of_activity(e2) && in_relation(e2))
+            .collect(),
+        Some(SetFilter::All(items)) => items
+            .first()
+            .into_iter()
+            .flat_map(|o| locel.get_e2o_rev(o).map(|(_q, e)| *e))
+            .filter(|e2| of_activity(e2) && in_relation(e2))
+            .collect(),
+    };
+    let candidates = candidates.into_iter().filter(|e2| {
+        let e2_obs = event_objects(locel, e2);
+        filters.iter().all(|f| f.check(&e2_obs))
+    });
+    let mut ret: Vec<EventIndex> = if is_direct {
+        // The first (DF) or last (DP) of the nearest candidates
+        let nearest = if arc.arc_type == OCDeclareArcType::DF {
+            candidates.min_by_key(|e2| locel.get_ev(e2).time)
+        } else {
+            candidates.max_by_key(|e2| locel.get_ev(e2).time)
+        };
+        nearest
+            .filter(|e2| locel.get_ev(e2).event_type == to_act)
+            .into_iter()
+            .collect()
+    } else {
+        candidates.filter(in_relation).collect()
+    };
+    ret.retain(|e2| {
+        constraint
+            .to_predicate
+            .as_ref()
+            .is_none_or(|p| p.evaluate(locel, e2))
+            && constraint
+                .time_bounds
+                .is_none_or(|bounds| bounds.contains(time_distance(&arc.arc_type, locel, ev, e2)))
+    });
+    ret.sort();
+    ret
+}
+
+/// Check if a count satisfies the (min, max) count bounds of an arc
+pub(crate) fn check_count(
+    count: usize,
+    counts: &(Option<usize>, Option<usize>),
+) -> Option<ViolationKind> {
+    if counts.0.is_some_and(|min| count < min) {
+        Some(ViolationKind::TooFew)
+    } else if counts.1.is_some_and(|max| count > max) {
+        Some(ViolationKind::TooMany)
+    } else {
+        None
+    }
+}
+
+fn bound_objects(
+    locel: &IndexLinkedOCEL,
+    obs: &[(ObjectIndex, usize)],
+    assocs: &[ObjectTypeAssociation],
+) -> Vec<BoundObject> {
+    obs.iter()
+        .map(|(o, assoc_i)| {
+            let ob = locel.get_ob(o);
+            BoundObject {
+                object_id: ob.id.clone(),
+                object_type: ob.object_type.clone(),
+                association: assocs[*assoc_i].as_template_string(),
+            }
+        })
+        .collect()
+}
+
+/// Get all violated bindings of a single source event
+pub fn get_event_violation(
+    constraint: &OCDeclareConstraint,
+    locel: &IndexLinkedOCEL,
+    ev: &EventIndex,
+) -> Option<EventViolation> {
+    let arc = &constraint.arc;
+    let bindings: Vec<BindingViolation> = get_bindings(arc, locel, ev)
+        .into_iter()
+        .filter_map(|binding| {
+            let matching = get_matching_events(constraint, locel, ev, &binding);
+            let kind = check_count(matching.len(), &arc.counts)?;
+            Some(BindingViolation {
+                each_objects: bound_objects(locel, &binding.each, &arc.label.each),
+                all_objects: bound_objects(locel, &binding.all, &arc.label.all),
+                any_objects: bound_objects(locel, &binding.any, &arc.label.any),
+                matching_events: matching
+                    .iter()
+                    .map(|e| locel.get_ev(e).id.clone())
+                    .collect(),
+                observed_count: matching.len(),
+                required_count: arc.counts,
+                kind,
+            })
+        })
+        .collect();
+    if bindings.is_empty() {
+        None
+    } else {
+        Some(EventViolation {
+            source_event: locel.get_ev(ev).id.clone(),
+            bindings,
+        })
+    }
+}
+
+/// Get the conformance of every source event of a constraint, including satisfied ones
+pub fn get_source_event_conformance(
+    constraint: &OCDeclareConstraint,
+    locel: &IndexLinkedOCEL,
+) -> Vec<SourceEventConformance> {
+    let arc = &constraint.arc;
+    get_source_events(constraint, locel)
+        .par_iter()
+        .map(|ev| {
+            let counts: Vec<usize> = get_bindings(arc, locel, ev)
+                .iter()
+                .map(|binding| get_matching_events(constraint, locel, ev, binding).len())
+                .collect();
+            let num_violated_bindings = counts
+                .iter()
+                .filter(|count| check_count(**count, &arc.counts).is_some())
+                .count();
+            let event = locel.get_ev(ev);
+            SourceEventConformance {
+                event_id: event.id.clone(),
+                activity: event.event_type.clone(),
+                timestamp: event.time,
+                objects: locel
+                    .get_e2o(ev)
+                    .map(|(_q, o)| locel.get_ob(o).id.clone())
+                    .collect(),
+                required_count: arc.counts,
+                min_observed_count: counts.iter().min().copied(),
+                max_observed_count: counts.iter().max().copied(),
+                num_bindings: counts.len(),
+                num_violated_bindings,
+                violated: num_violated_bindings > 0,
+            }
+        })
+        .collect()
+}
+
+/// Check if a source event violates a constraint for at least one of its bindings
+fn is_violated(constraint: &OCDeclareConstraint, locel: &IndexLinkedOCEL, ev: &EventIndex) -> bool {
+    get_bindings(&constraint.arc, locel, ev)
+        .iter()
+        .any(|binding| {
+            let count = get_matching_events(constraint, locel, ev, binding).len();
+            check_count(count, &constraint.arc.counts).is_some()
+        })
+}
+
+/// Get the fraction of violating source events of a constraint (`0.0` if there are no source events)
+///
+/// Used for constraints with time bounds or predicates (see [`OCDeclareConstraint::get_for_all_evs_perf`]);
+/// plain arcs are checked by `OCDeclareArc::get_for_all_evs_perf` of `process_mining`.
+/// For plain arcs, the results of this module agree with `process_mining`
+/// (rust4pm, branch `feat/oc-declare`, revision `dd95daad`), which is checked by differential tests.
+pub fn get_violation_fraction(constraint: &OCDeclareConstraint, locel: &IndexLinkedOCEL) -> f64 {
+    let source_evs = get_source_events(constraint, locel);
+    if source_evs.is_empty() {
+        return 0.0;
+    }
+    let num_violating_events = source_evs
+        .par_iter()
+        .filter(|ev| is_violated(constraint, locel, ev))
+        .count();
+    num_violating_events as f64 / source_evs.len() as f64
+}
+
+/// Get all source events of a constraint (i.e., events of the `from` activity satisfying the `from` predicate)
+pub(crate) fn get_source_events(
+    constraint: &OCDeclareConstraint,
+    locel: &IndexLinkedOCEL,
+) -> Vec<EventIndex> {
+    locel
+        .get_evs_of_type(constraint.arc.from.as_str())
+        .filter(|ev| {
+            constraint
+                .from_predicate
+                .as_ref()
+                .is_none_or(|p| p.evaluate(locel, ev))
+        })
+        .copied()
+        .collect()
+}
+
+/// Get a structured violation report of a constraint, listing all violating source events
+///
+/// The `violation_fraction` of the report corresponds to the value returned by [`get_violation_fraction`].
+pub fn get_violation_report(
+    constraint: &OCDeclareConstraint,
+    locel: &IndexLinkedOCEL,
+) -> ArcViolationReport {
+    let source_evs = get_source_events(constraint, locel);
+    let violations: Vec<EventViolation> = source_evs
+        .par_iter()
+        .filter_map(|ev| get_event_violation(constraint, locel, ev))
+        .collect();
+    let num_source_events = source_evs.len();
+    let num_violating_events = violations.len();
+    ArcViolationReport {
+        num_source_events,
+        num_violating_events,
+        violation_fraction: if num_source_events == 0 {
+            0.0
+        } else {
+            num_violating_events as f64 / num_source_events as f64
+        },
+        violations,
+    }
+}
+
+/// Get structured violation reports for multiple constraints
+pub fn get_violation_reports(
+    constraints: &[OCDeclareConstraint],
+    locel: &IndexLinkedOCEL,
+) -> Vec<ArcViolationReport> {
+    constraints
+        .iter()
+        .map(|constraint| get_violation_report(constraint, locel))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use process_mining::object_centric::oc_declare::O2OMode;
+
+    use crate::{
+        constraint::{OCDeclareConstraint, TimeBounds},
+        discover_behavior_constraints,
+        template::parse_predicate,
+        test_utils::{generated_arcs, generated_ocel, test_ocel},
+        OCDeclareArc, OCDeclareArcLabel, OCDeclareArcType, OCDeclareDiscoveryOptions,
+        OCDeclareNode, ObjectTypeAssociation,
+    };
+
+    use super::{
+        get_source_event_conformance, get_violation_fraction, get_violation_report, ViolationKind,
+    };
+
+    fn arc(from: &str, to: &str, arc_type: OCDeclareArcType, each: &str) -> OCDeclareArc {
+        OCDeclareArc {
+            from: OCDeclareNode::new(from),
+            to: OCDeclareNode::new(to),
+            arc_type,
+            label: OCDeclareArcLabel {
+                each: vec![ObjectTypeAssociation::Simple {
+                    object_type: each.to_string(),
+                }],
+                any: vec![],
+                all: vec![],
+            },
+            counts: (Some(1), Some(1)),
+        }
+    }
+
+    #[test]
+    fn violation_report() {
+        let locel = test_ocel();
+        let report = get_violation_report(
+            &arc("place order", "pay order", OCDeclareArcType::EF, "order").into(),
+            &locel,
+        );
+        assert_eq!(report.num_source_events, 3);
+        assert_eq!(report.num_violating_events, 2);
+        let mut violations = report.violations.clone();
+        violations.sort_by(|a, b| a.source_event.cmp(&b.source_event));
+        assert_eq!(violations[0].source_event, "e2");
+        assert_eq!(violations[0].bindings[0].kind, ViolationKind::TooFew);
+        assert_eq!(violations[1].source_event, "e4");
+        let binding = &violations[1].bindings[0];
+        assert_eq!(binding.kind, ViolationKind::TooMany);
+        assert_eq!(binding.observed_count, 2);
+        assert_eq!(binding.each_objects[0].object_id, "o3");
+        let perf = arc("place order", "pay order", OCDeclareArcType::EF, "order")
+            .get_for_all_evs_perf(&locel);
+        assert!((perf - report.violation_fraction).abs() < 1e-9);
+    }
+
+    #[test]
+    fn agrees_with_upstream() {
+        let locel = generated_ocel();
+        let mut arcs = generated_arcs(&["1,∞", "0,0", "1,1", "2,∞", "0,2"]);
+        for o2o_mode in [O2OMode::None, O2OMode::Bidirectional] {
+            let options = OCDeclareDiscoveryOptions {
+                o2o_mode,
+                ..Default::default()
+            };
+            arcs.extend(discover_behavior_constraints(&locel, options));
+        }
+        let mut num_violated = 0;
+        for arc in arcs {
+            let expected = arc.get_for_all_evs_perf(&locel);
+            let constraint: OCDeclareConstraint = arc.into();
+            let report = get_violation_report(&constraint, &locel);
+            let name = constraint.as_template_string();
+            assert!(
+                (expected - report.violation_fraction).abs() < 1e-9,
+                "{name}"
+            );
+            assert!(
+                (expected - get_violation_fraction(&constraint, &locel)).abs() < 1e-9,
+                "{name}"
+            );
+            if expected > 0.0 && expected < 1.0 {
+                num_violated += 1;
+            }
+        }
+        // The log is not trivial for the checked arcs
+        assert!(num_violated > 100);
+    }
+
+    #[test]
+    fn source_event_conformance() {
+        let locel = test_ocel();
+        let constraint = arc("place order", "pay order", OCDeclareArcType::EF, "order").into();
+        let mut rows = get_source_event_conformance(&constraint, &locel);
+        rows.sort_by(|a, b| a.event_id.cmp(&b.event_id));
+        let summary: Vec<_> = rows
+            .iter()
+            .map(|r| (r.event_id.as_str(), r.min_observed_count, r.violated))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("e1", Some(1), false),
+                ("e2", Some(0), true),
+                ("e4", Some(2), true)
+            ]
+        );
+        assert_eq!(rows[1].objects, vec!["o2", "i1", "i2"]);
+        assert_eq!(rows[1].activity, "place order");
+        let report = get_violation_report(&constraint, &locel);
+        assert_eq!(
+            rows.iter().filter(|r| r.violated).count(),
+            report.num_violating_events
+        );
+    }
+
+    #[test]
+    fn directly_follows() {
+        let locel = test_ocel();
+        let report = get_violation_report(
+            &arc("pay order", "ship order", OCDeclareArcType::DF, "order").into(),
+            &locel,
+        );
+        // e3 -> e5 (o1) is directly-follows, both pay order events of o3 are never followed by shipping
+        assert_eq!(report.num_source_events, 3);
+        assert_eq!(report.num_violating_events, 2);
+    }
+
+    #[test]
+    fn time_bounds() {
+        let locel = test_ocel();
+        const DAY: i64 = 24 * 60 * 60 * 1000;
+        let mut place_pay = arc("place order", "pay order", OCDeclareArcType::EF, "order");
+        place_pay.counts = (Some(1), None);
+        let within = |min: Option<i64>, max: Option<i64>| {
+            OCDeclareConstraint::from(place_pay.clone()).with_time_bounds(TimeBounds::new(min, max))
+        };
+        // e1 -> e3 takes 2 days, e4 -> e7/e8 takes 16/17 days, e2 is never paid
+        let violating = |c: &OCDeclareConstraint| {
+            let mut evs: Vec<String> = get_violation_report(c, &locel)
+                .violations
+                .into_iter()
+                .map(|v| v.source_event)
+                .collect();
+            evs.sort();
+            evs
+        };
+        assert_eq!(violating(&within(None, Some(14 * DAY))), vec!["e2", "e4"]);
+        assert_eq!(violating(&within(Some(3 * DAY), None)), vec!["e1", "e2"]);
+        assert_eq!(
+            violating(&within(Some(2 * DAY), Some(16 * DAY))),
+            vec!["e2"]
+        );
+        let c = within(None, Some(14 * DAY));
+        assert!((c.get_for_all_evs_perf(&locel) - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn predicates() {
+        let locel = test_ocel();
+        let mut place_pay = arc("place order", "pay order", OCDeclareArcType::EF, "order");
+        place_pay.counts = (Some(1), None);
+        // Only e2 (2000) and e4 (1500) are expensive, e2 is never paid
+        let expensive = OCDeclareConstraint::from(place_pay.clone())
+            .with_predicates(Some(parse_predicate("price > 1000").unwrap()), None);
+        let report = get_violation_report(&expensive, &locel);
+        assert_eq!(report.num_source_events, 2);
+        assert_eq!(report.num_violating_events, 1);
+        assert_eq!(report.violations[0].source_event, "e2");
+        // Events without the attribute never satisfy a comparison
+        let missing = OCDeclareConstraint::from(place_pay)
+            .with_predicates(None, Some(parse_predicate("price > 0").unwrap()));
+        assert_eq!(missing.get_for_all_evs_perf(&locel), 1.0);
+    }
+}
@@ -0,0 +1,386 @@
+//! Semantic diff between two OC-DECLARE models (e.g., discovered at different points in time)
+//!
+//! Arcs of the old and the new model are matched by their activity pair and arc type
+//! (falling back to comparable arc types, e.g., `EF` and `DF`, for otherwise unmatched arcs).
+//! Matched arcs are compared aspect by aspect, using the dominance relations also used for reduction:
+//! A new arc is _strengthened_ if every aspect stays the same or becomes stricter, and _weakened_ in the opposite case.
+//!
+//! Narrower count bounds are always stricter. In contrast, a dominating arc type or label and narrower time bounds
+//! only restrict the target events of an arc (see [`crate::consistency`]). Fewer target events are stricter
+//! for arcs with only a lower count bound (e.g., `1,∞`), but less strict for arcs with only an upper count bound
+//! (e.g., `0,0`). For arcs with both (e.g., `1,1`), such changes are neither stricter nor less strict.
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{constraint::OCDeclareConstraint, OCDeclareArcLabel, OCDeclareArcType};
+
+/// How an arc changed between two models
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ArcChangeKind {
+    /// Only in the new model
+    Added,
+    /// Only in the old model
+    Removed,
+    /// Identical in both models
+    Unchanged,
+    /// The new arc is stricter than the old one
+    Strengthened,
+    /// The new arc is less strict than the old one
+    Weakened,
+    /// The arc changed, but neither is stricter than the other (e.g., a stricter label but wider count bounds)
+    Modified,
+}
+
+/// Aspect of an arc which can change between two models
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ArcAspect {
+    ArcType,
+    Label,
+    Counts,
+    TimeBounds,
+    Predicates,
+}
+
+/// Diff entry for a single (matched, added or removed) arc
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ArcDiff {
+    pub kind: ArcChangeKind,
+    /// Index of the arc in the old model (`null` for added arcs)
+    pub old_index: Option<usize>,
+    /// Index of the arc in the new model (`null` for removed arcs)
+    pub new_index: Option<usize>,
+    /// Aspects that differ between the old and the new arc (empty for added/removed arcs)
+    pub changed_aspects: Vec<ArcAspect>,
+}
+
+/// Semantic diff between two OC-DECLARE models
+///
+/// Contains one entry for every arc of the old model (in order), followed by the added arcs of the new model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ModelDiff {
+    pub arcs: Vec<ArcDiff>,
+}
+
+impl ModelDiff {
+    /// All entries of the given kind
+    pub fn of_kind(&self, kind: ArcChangeKind) -> impl Iterator<Item = &ArcDiff> {
+        self.arcs.iter().filter(move |a| a.kind == kind)
+    }
+
+    /// Check if both models are equivalent (i.e., all arcs are unchanged)
+    pub fn is_empty(&self) -> bool {
+        self.arcs.iter().all(|a| a.kind == ArcChangeKind::Unchanged)
+    }
+}
+
+/// Compare the target events of two arc types (`Greater` if `new` has fewer target events, `None` if incomparable)
+fn compare_arc_types(old: &OCDeclareArcType, new: &OCDeclareArcType) -> Option<Ordering> {
+    if old == new {
+        Some(Ordering::Equal)
+    } else if old.is_dominated_by_or_eq(new) {
+        Some(Ordering::Greater)
+    } else if new.is_dominated_by_or_eq(old) {
+        Some(Ordering::Less)
+    } else {
+        None
+    }
+}
+
+/// Compare the target events of two labels (`Greater` if `new` has fewer target events, `None` if incomparable)
+fn compare_labels(old: &OCDeclareArcLabel, new: &OCDeclareArcLabel) -> Option<Ordering> {
+    match (old.is_dominated_by(new), new.is_dominated_by(old)) {
+        (true, true) => Some(Ordering::Equal),
+        (true, false) => Some(Ordering::Greater),
+        (false, true) => Some(Ordering::Less),
+        (false, false) => None,
+    }
+}
+
+/// Compare two (inclusive) intervals (`Greater` if `new` is contained in `old`, `None` if neither contains the other)
+fn compare_intervals<T: Ord>(old: (T, T), new: (T, T)) -> Option<Ordering> {
+    let new_in_old = new.0 >= old.0 && new.1 <= old.1;
+    let old_in_new = old.0 >= new.0 && old.1 <= new.1;
+    match (new_in_old, old_in_new) {
+        (true, true) => Some(Ordering::Equal),
+        (true, false) => Some(Ordering::Greater),
+        (false, true) => Some(Ordering::Less),
+        (false, false) => None,
+    }
+}
+
+/// Turn a comparison of target events into a comparison of strictness (`Greater` if `new` is stricter)
+///
+/// Fewer target events are only stricter if both arcs only have a lower count bound,
+/// and less strict if both arcs only have an upper count bound.
+fn fewer_targets_to_stricter(
+    old: &OCDeclareConstraint,
+    new: &OCDeclareConstraint,
+    fewer_targets: Option<Ordering>,
+) -> Option<Ordering> {
+    let bounds =
+        |c: &OCDeclareConstraint| (c.arc.counts.0.unwrap_or(0) > 0, c.arc.counts.1.is_some());
+    match (fewer_targets?, bounds(old), bounds(new)) {
+        (Ordering::Equal, _, _) => Some(Ordering::Equal),
+        (ord, (true, false), (true, false)) => Some(ord),
+        (ord, (false, true), (false, true)) => Some(ord.reverse()),
+        _ => None,
+    }
+}
+
+/// Compare each aspect of two arcs (`Greater` if `new` is stricter, `None` if incomparable)
+fn compare_aspects(
+    old: &OCDeclareConstraint,
+    new: &OCDeclareConstraint,
+) -> Vec<(ArcAspect, Option<Ordering>)> {
+    let counts = |c: &OCDeclareConstraint| {
+        (
+            c.arc.counts.0.unwrap_or(0),
+            c.arc.counts.1.unwrap_or(usize::MAX),
+        )
+    };
+    let targets = |fewer_targets| fewer_targets_to_stricter(old, new, fewer_targets);
+    let time_bounds = |c: &OCDeclareConstraint| {
+        let bounds = c.time_bounds.unwrap_or_default();
+        (
+            bounds.min_delay_ms.unwrap_or(i64::MIN),
+            bounds.max_delay_ms.unwrap_or(i64::MAX),
+        )
+    };
+    let predicates_eq =
+        old.from_predicate == new.from_predicate && old.to_predicate == new.to_predicate;
+    vec![
+        (
+            ArcAspect::ArcType,
+            targets(compare_arc_types(&old.arc.arc_type, &new.arc.arc_type)),
+        ),
+        (
+            ArcAspect::Label,
+            targets(compare_labels(&old.arc.label, &new.arc.label)),
+        ),
+        (
+            ArcAspect::Counts,
+            compare_intervals(counts(old), counts(new)),
+        ),
+        (
+            ArcAspect::TimeBounds,
+            targets(compare_intervals(time_bounds(old), time_bounds(new))),
+        ),
+        (
+            ArcAspect::Predicates,
+            predicates_eq.then_some(Ordering::Equal),
+        ),
+    ]
+}
+
+/// Diff entry for two matched arcs
+fn diff_arcs(
+    old_index: usize,
+    old: &OCDeclareConstraint,
+    new_index: usize,
+    new: &OCDeclareConstraint,
+) -> ArcDiff {
+    let aspects = compare_aspects(old, new);
+    let changed_aspects: Vec<ArcAspect> = aspects
+        .iter()
+        .filter(|(_, ord)| *ord != Some(Ordering::Equal))
+        .map(|(aspect, _)| *aspect)
+        .collect();
+    let stricter = |ord: Ordering| {
+        aspects
+            .iter()
+            .all(|(_, o)| o.is_some_and(|o| o == Ordering::Equal || o == ord))
+    };
+    let kind = if changed_aspects.is_empty() {
+        ArcChangeKind::Unchanged
+    } else if stricter(Ordering::Greater) {
+        ArcChangeKind::Strengthened
+    } else if stricter(Ordering::Less) {
+        ArcChangeKind::Weakened
+    } else {
+        ArcChangeKind::Modified
+    };
+    ArcDiff {
+        kind,
+        old_index: Some(old_index),
+        new_index: Some(new_index),
+        changed_aspects,
+    }
+}
+
+/// Check if two arcs have the same activity pair and arc type
+fn same_key(a: &OCDeclareConstraint, b: &OCDeclareConstraint) -> bool {
+    a.arc.from == b.arc.from && a.arc.to == b.arc.to && a.arc.arc_type == b.arc.arc_type
+}
+
+/// Predicate deciding if an old and a new arc can be matched
+type MatchStage = dyn Fn(&OCDeclareConstraint, &OCDeclareConstraint) -> bool;
+
+/// Compute the semantic diff between an old and a new model
+///
+/// Arcs are matched greedily in stages, preferring identical arcs, then arcs with the same
+/// activity pair and arc type (with an equal, then a comparable, then any label),
+/// and finally arcs with the same activity pair and comparable arc types.
+pub fn diff_models(old: &[OCDeclareConstraint], new: &[OCDeclareConstraint]) -> ModelDiff {
+    let stages: [&MatchStage; 5] = [
+        &|a, b| a == b,
+        &|a, b| same_key(a, b) && a.arc.label == b.arc.label,
+        &|a, b| same_key(a, b) && compare_labels(&a.arc.label, &b.arc.label).is_some(),
+        &|a, b| same_key(a, b),
+        &|a, b| {
+            a.arc.from == b.arc.from
+                && a.arc.to == b.arc.to
+                && compare_arc_types(&a.arc.arc_type, &b.arc.arc_type).is_some()
+        },
+    ];
+    let mut matches: Vec<Option<usize>> = vec![None; old.len()];
+    let mut new_matched = vec![false; new.len()];
+    for stage in stages {
+        for (i, old_arc) in old.iter().enumerate() {
+            if matches[i].is_some() {
+                continue;
+            }
+            if let Some(j) = (0..new.len()).find(|j| !new_matched[*j] && stage(old_arc, &new[*j])) {
+                matches[i] = Some(j);
+                new_matched[j] = true;
+            }
+        }
+    }
+    let mut arcs: Vec<ArcDiff> = matches
+        .iter()
+        .enumerate()
+        .map(|(i, j)| match j {
+            Some(j) => diff_arcs(i, &old[i], *j, &new[*j]),
+            None => ArcDiff {
+                kind: ArcChangeKind::Removed,
+                old_index: Some(i),
+                new_index: None,
+                changed_aspects: Vec::new(),
+            },
+        })
+        .collect();
+    arcs.extend(
+        (0..new.len())
+            .filter(|j| !new_matched[*j])
+            .map(|j| ArcDiff {
+                kind: ArcChangeKind::Added,
+                old_index: None,
+                new_index: Some(j),
+                changed_aspects: Vec::new(),
+            }),
+    );
+    ModelDiff { arcs }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::template::parse_model;
+
+    use super::*;
+
+    #[test]
+    fn strengthened_and_weakened() {
+        let old = parse_model(
+            "EF(place order, pay order, Each(order),1,*)\n\
+             EF(pay order, ship order, Each(order),1,*)\n\
+             AS(place order, ship order, Each(order),1,*)\n\
+             EP(ship order, place order, Each(order),1,1)\n\
+             DF(place order, cancel order, Each(order),0,0)",
+        )
+        .unwrap();
+        let new = parse_model(
+            "EF(place order, pay order, Each(order),1,1)\n\
+             EF(pay order, ship order, Any(order),1,*)\n\
+             EF(place order, ship order, Each(order),1,*)\n\
+             EP(ship order, place order, Each(order),0,2)\n\
+             EF(ship order, archive order, Each(order),1,*)",
+        )
+        .unwrap();
+        let diff = diff_models(&old, &new);
+        let kinds: Vec<(ArcChangeKind, Option<usize>, Option<usize>)> = diff
+            .arcs
+            .iter()
+            .map(|a| (a.kind, a.old_index, a.new_index))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (ArcChangeKind::Strengthened, Some(0), Some(0)),
+                (ArcChangeKind::Weakened, Some(1), Some(1)),
+                (ArcChangeKind::Strengthened, Some(2), Some(2)),
+                (ArcChangeKind::Weakened, Some(3), Some(3)),
+                (ArcChangeKind::Removed, Some(4), None),
+                (ArcChangeKind::Added, None, Some(4)),
+            ]
+        );
+        assert_eq!(diff.arcs[2].changed_aspects, vec![ArcAspect::ArcType]);
+        assert!(diff_models(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn direction_depends_on_counts() {
+        let kind = |old: &str, new: &str| {
+            let diff = diff_models(&parse_model(old).unwrap(), &parse_model(new).unwrap());
+            assert_eq!(diff.arcs.len(), 1);
+            diff.arcs[0].kind
+        };
+        // DF has fewer target events than EF
+        assert_eq!(
+            kind("EF(a, b, Each(o),1,∞)", "DF(a, b, Each(o),1,∞)"),
+            ArcChangeKind::Strengthened
+        );
+        assert_eq!(
+            kind("EF(a, b, Each(o),0,0)", "DF(a, b, Each(o),0,0)"),
+            ArcChangeKind::Weakened
+        );
+        assert_eq!(
+            kind("DF(a, b, Each(o),0,0)", "EF(a, b, Each(o),0,0)"),
+            ArcChangeKind::Strengthened
+        );
+        assert_eq!(
+            kind("EF(a, b, Each(o),1,1)", "DF(a, b, Each(o),1,1)"),
+            ArcChangeKind::Modified
+        );
+        // An additional ALL object type restricts the target events
+        assert_eq!(
+            kind("EF(a, b, Each(o),0,0)", "EF(a, b, Each(o), All(i),0,0)"),
+            ArcChangeKind::Weakened
+        );
+        // Narrower time bounds restrict the target events
+        assert_eq!(
+            kind(
+                "EF(a, b, Each(o),0,2,Delay(*,14d))",
+                "EF(a, b, Each(o),0,2,Delay(*,7d))"
+            ),
+            ArcChangeKind::Weakened
+        );
+        assert_eq!(
+            kind(
+                "EF(a, b, Each(o),0,2,Delay(*,7d))",
+                "EF(a, b, Each(o),0,2,Delay(*,14d))"
+            ),
+            ArcChangeKind::Strengthened
+        );
+        assert_eq!(
+            kind(
+                "EF(a, b, Each(o),1,∞,Delay(*,14d))",
+                "EF(a, b, Each(o),1,∞,Delay(*,7d))"
+            ),
+            ArcChangeKind::Strengthened
+        );
+        // Narrower count bounds are always stricter, but the target events are only comparable for the same kind of bounds
+        assert_eq!(
+            kind("EF(a, b, Each(o),0,2)", "EF(a, b, Each(o),0,1)"),
+            ArcChangeKind::Strengthened
+        );
+        assert_eq!(
+            kind("EF(a, b, Each(o),0,2)", "DF(a, b, Each(o),1,1)"),
+            ArcChangeKind::Modified
+        );
+    }
+}
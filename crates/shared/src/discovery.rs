@@ -0,0 +1,422 @@
+use itertools::Itertools;
+use process_mining::ocel::linked_ocel::{IndexLinkedOCEL, LinkedOCELAccess};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    constraint::{time_distance, OCDeclareConstraint, TimeBounds},
+    diagnostics::{
+        check_count, get_bindings, get_event_violation, get_matching_events, get_source_events,
+        get_violation_fraction,
+    },
+    discover_behavior_constraints,
+    reduction::{ReductionMode, RemovedArc},
+    O2OMode, OCDeclareArc, OCDeclareArcType, OCDeclareDiscoveryOptions, EXIT_EVENT_PREFIX,
+    INIT_EVENT_PREFIX,
+};
+
+/// Serializable discovery options, used by the WASM and Python frontends
+///
+/// Mirrors [`OCDeclareDiscoveryOptions`] and additionally specifies the reduction applied to the discovered arcs.
+/// Missing fields are filled with the defaults of [`OCDeclareDiscoveryOptions`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct DiscoveryOptions {
+    /// Maximum fraction of violating source events for a discovered arc
+    pub noise_threshold: f64,
+    /// Which object-to-object (O2O) relationships to use for object type associations
+    pub o2o_mode: DiscoveryO2OMode,
+    /// Activities to consider (all if `null`)
+    pub acts_to_use: Option<Vec<String>>,
+    /// (min, max) counts used for generating candidate arcs
+    pub counts_for_generation: (Option<usize>, Option<usize>),
+    /// (min, max) counts used for filtering candidate arcs
+    pub counts_for_filter: (Option<usize>, Option<usize>),
+    /// Reduction applied to the discovered arcs
+    pub reduction: ReductionMode,
+    /// Propose time bounds for the discovered `EF`/`EP`/`DF`/`DP` arcs (see [`propose_time_bounds`])
+    pub time_bounds: bool,
+    /// Compute quality metrics for the discovered arcs (see [`get_arc_metrics`])
+    pub metrics: bool,
+}
+
+/// Serializable counterpart of [`O2OMode`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum DiscoveryO2OMode {
+    None,
+    Direct,
+    Reversed,
+    Bidirectional,
+}
+
+impl From<O2OMode> for DiscoveryO2OMode {
+    fn from(value: O2OMode) -> Self {
+        match value {
+            O2OMode::None => DiscoveryO2OMode::None,
+            O2OMode::Direct => DiscoveryO2OMode::Direct,
+            O2OMode::Reversed => DiscoveryO2OMode::Reversed,
+            O2OMode::Bidirectional => DiscoveryO2OMode::Bidirectional,
+        }
+    }
+}
+
+impl From<DiscoveryO2OMode> for O2OMode {
+    fn from(value: DiscoveryO2OMode) -> Self {
+        match value {
+            DiscoveryO2OMode::None => O2OMode::None,
+            DiscoveryO2OMode::Direct => O2OMode::Direct,
+            DiscoveryO2OMode::Reversed => O2OMode::Reversed,
+            DiscoveryO2OMode::Bidirectional => O2OMode::Bidirectional,
+        }
+    }
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        let options = OCDeclareDiscoveryOptions::default();
+        Self {
+            noise_threshold: options.noise_threshold,
+            o2o_mode: options.o2o_mode.into(),
+            acts_to_use: options.acts_to_use,
+            counts_for_generation: options.counts_for_generation,
+            counts_for_filter: options.counts_for_filter,
+            reduction: ReductionMode::None,
+            time_bounds: false,
+            metrics: false,
+        }
+    }
+}
+
+impl From<&DiscoveryOptions> for OCDeclareDiscoveryOptions {
+    fn from(value: &DiscoveryOptions) -> Self {
+        OCDeclareDiscoveryOptions {
+            noise_threshold: value.noise_threshold,
+            o2o_mode: value.o2o_mode.into(),
+            acts_to_use: value.acts_to_use.clone(),
+            counts_for_generation: value.counts_for_generation,
+            counts_for_filter: value.counts_for_filter,
+        }
+    }
+}
+
+/// Quality metrics of an arc w.r.t. a log, e.g., for ranking or filtering discovered arcs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ArcMetrics {
+    /// Number of source events the arc applies to
+    pub num_source_events: usize,
+    /// Number of source events satisfying the arc
+    pub num_satisfying_events: usize,
+    /// Fraction of violating source events (`null` if there are no source events)
+    pub violation_fraction: Option<f64>,
+    /// Fraction of satisfying source events divided by the baseline frequency of the target activity
+    ///
+    /// `lift = (num_satisfying_events / num_source_events) / (num_target_events / num_events)`,
+    /// where `num_events` counts all events of the log and `num_target_events` the events of the target activity.
+    /// Both only count the real events of the log, not the `<init>`/`<exit>` events added by the pre-processing.
+    ///
+    /// Values above 1 indicate that the target activity occurs as required more often than its overall frequency suggests.
+    /// `null` if there are no source events or no (real) events of the target activity (e.g., for `<init>`/`<exit>` targets).
+    pub lift: Option<f64>,
+}
+
+/// Compute the quality metrics of a constraint w.r.t. a log
+pub fn get_arc_metrics(constraint: &OCDeclareConstraint, locel: &IndexLinkedOCEL) -> ArcMetrics {
+    let source_events = get_source_events(constraint, locel);
+    let num_source_events = source_events.len();
+    let num_violating_events = source_events
+        .par_iter()
+        .filter(|ev| get_event_violation(constraint, locel, ev).is_some())
+        .count();
+    let num_satisfying_events = num_source_events - num_violating_events;
+    // The baseline frequency only considers real events (i.e., not the added <init>/<exit> events)
+    let is_real =
+        |et: &str| !et.starts_with(INIT_EVENT_PREFIX) && !et.starts_with(EXIT_EVENT_PREFIX);
+    let num_events: usize = locel
+        .get_ev_types()
+        .filter(|et| is_real(et))
+        .map(|et| locel.get_evs_of_type(et).count())
+        .sum();
+    let target = constraint.arc.to.as_str();
+    let num_target_events = if is_real(target) {
+        locel.get_evs_of_type(target).count()
+    } else {
+        0
+    };
+    let satisfaction_fraction =
+        (num_source_events > 0).then(|| num_satisfying_events as f64 / num_source_events as f64);
+    ArcMetrics {
+        num_source_events,
+        num_satisfying_events,
+        violation_fraction: satisfaction_fraction.map(|f| 1.0 - f),
+        lift: satisfaction_fraction
+            .filter(|_| num_target_events > 0)
+            .map(|f| f / (num_target_events as f64 / num_events as f64)),
+    }
+}
+
+/// A constraint together with its quality metrics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ConstraintWithMetrics {
+    #[serde(flatten)]
+    pub constraint: OCDeclareConstraint,
+    pub metrics: ArcMetrics,
+}
+
+/// Discovered constraints together with the provenance of the arcs removed by the reduction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DiscoveryResult {
+    pub constraints: Vec<OCDeclareConstraint>,
+    /// Quality metrics of the constraints (in the same order), empty unless [`DiscoveryOptions::metrics`] is enabled
+    pub metrics: Vec<ArcMetrics>,
+    /// Arcs removed by the configured reduction (empty if no reduction is applied)
+    pub removed: Vec<RemovedArc>,
+}
+
+/// Discover OC-DECLARE constraints using the given options
+///
+/// The configured reduction is applied to the discovered arcs before time bounds are proposed (if enabled).
+pub fn discover_with_options(
+    locel: &IndexLinkedOCEL,
+    options: &DiscoveryOptions,
+) -> Vec<OCDeclareConstraint> {
+    discover_with_provenance(locel, options).constraints
+}
+
+/// Like [`discover_with_options`], additionally returning why arcs were removed by the reduction
+pub fn discover_with_provenance(
+    locel: &IndexLinkedOCEL,
+    options: &DiscoveryOptions,
+) -> DiscoveryResult {
+    let discovered = discover_behavior_constraints(locel, options.into());
+    let (reduced, removed) = options.reduction.apply_explained(discovered);
+    let constraints: Vec<OCDeclareConstraint> = if options.time_bounds {
+        reduced
+            .into_par_iter()
+            .map(|arc| {
+                let bounds = propose_time_bounds(&arc, locel, options.noise_threshold);
+                let constraint = OCDeclareConstraint::new(arc);
+                match bounds {
+                    Some(bounds) => constraint.with_time_bounds(bounds),
+                    None => constraint,
+                }
+            })
+            .collect()
+    } else {
+        reduced.into_iter().map(OCDeclareConstraint::new).collect()
+    };
+    let metrics = if options.metrics {
+        constraints
+            .par_iter()
+            .map(|constraint| get_arc_metrics(constraint, locel))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    DiscoveryResult {
+        constraints,
+        metrics,
+        removed,
+    }
+}
+
+/// Propose tight time bounds for an `EF`/`EP`/`DF`/`DP` arc with a min count, based on the observed delays
+///
+/// For every binding satisfying the arc, the delay to the nearest matching target event and the delay
+/// up to which the required min count of target events is reached are observed.
+/// The proposed bounds are the `noise_threshold / 2` quantile of the former and the `1 - noise_threshold / 2`
+/// quantile of the latter, i.e., the range covering all but a `noise_threshold` fraction of the satisfying bindings.
+///
+/// As the trimmed bindings add to the source events already violating the arc, the bounded arc is checked again:
+/// If its violation fraction exceeds `noise_threshold`, the bounds are widened (trimming fewer bindings),
+/// up to the full range of observed delays.
+///
+/// Returns `None` for `AS` arcs, arcs without a min count, if no binding satisfies the arc
+/// or if no bounds keep the violation fraction within `noise_threshold`.
+pub fn propose_time_bounds(
+    arc: &OCDeclareArc,
+    locel: &IndexLinkedOCEL,
+    noise_threshold: f64,
+) -> Option<TimeBounds> {
+    let min_count = arc.counts.0.filter(|c| *c > 0)?;
+    if arc.arc_type == OCDeclareArcType::ASS {
+        return None;
+    }
+    let constraint = OCDeclareConstraint::new(arc.clone());
+    let (mut nearest, mut required): (Vec<i64>, Vec<i64>) = locel
+        .get_evs_of_type(arc.from.as_str())
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .flat_map_iter(|ev| {
+            let constraint = &constraint;
+            get_bindings(arc, locel, ev)
+                .into_iter()
+                .filter_map(move |binding| {
+                    let matching = get_matching_events(constraint, locel, ev, &binding);
+                    if check_count(matching.len(), &arc.counts).is_some() {
+                        return None;
+                    }
+                    let mut delays: Vec<i64> = matching
+                        .iter()
+                        .map(|e2| time_distance(&arc.arc_type, locel, ev, e2))
+                        .collect();
+                    delays.sort();
+                    Some((delays[0], delays[min_count - 1]))
+                })
+        })
+        .unzip();
+    if nearest.is_empty() {
+        return None;
+    }
+    nearest.sort();
+    required.sort();
+    let quantile = |values: &[i64], q: f64| {
+        let i = (q * (values.len() - 1) as f64).round() as usize;
+        values[i.min(values.len() - 1)]
+    };
+    let tolerance = (noise_threshold / 2.0).clamp(0.0, 0.5);
+    [tolerance, tolerance / 2.0, tolerance / 4.0, 0.0]
+        .into_iter()
+        .map(|tolerance| {
+            TimeBounds::new(
+                Some(quantile(&nearest, tolerance)),
+                Some(quantile(&required, 1.0 - tolerance)),
+            )
+        })
+        .dedup()
+        .find(|bounds| {
+            let bounded = constraint.clone().with_time_bounds(*bounds);
+            get_violation_fraction(&bounded, locel) <= noise_threshold
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        template::parse_model, test_utils::test_ocel, OCDeclareArcLabel, OCDeclareNode,
+        ObjectTypeAssociation,
+    };
+
+    #[test]
+    fn partial_options_json() {
+        let options: DiscoveryOptions =
+            serde_json::from_str(r#"{"noise_threshold": 0.1, "reduction": "OcArcs"}"#).unwrap();
+        let defaults = DiscoveryOptions::default();
+        assert_eq!(options.noise_threshold, 0.1);
+        assert_eq!(options.reduction, ReductionMode::OcArcs);
+        assert_eq!(options.counts_for_filter, defaults.counts_for_filter);
+        assert_eq!(options.o2o_mode, defaults.o2o_mode);
+        assert!(!options.time_bounds);
+    }
+
+    #[test]
+    fn arc_metrics() {
+        let locel = test_ocel();
+        let constraint = &parse_model("EF(place order, pay order, Each(order),1,1)").unwrap()[0];
+        let metrics = get_arc_metrics(constraint, &locel);
+        // e1 (satisfied), e2 (no payment) and e4 (two payments)
+        assert_eq!(metrics.num_source_events, 3);
+        assert_eq!(metrics.num_satisfying_events, 1);
+        assert!((metrics.violation_fraction.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        // 3 of the 8 real events are payments: lift = (1/3) / (3/8) = 8/9
+        assert!((metrics.lift.unwrap() - 8.0 / 9.0).abs() < 1e-9);
+        // No baseline for the added <exit> events
+        let constraint = &parse_model("EF(place order, <exit> order, Each(order),1,1)").unwrap()[0];
+        let metrics = get_arc_metrics(constraint, &locel);
+        assert_eq!(metrics.num_satisfying_events, 3);
+        assert_eq!(metrics.lift, None);
+    }
+
+    #[test]
+    fn tight_time_bounds() {
+        let locel = test_ocel();
+        let arc = OCDeclareArc {
+            from: OCDeclareNode::new("place order"),
+            to: OCDeclareNode::new("pay order"),
+            arc_type: OCDeclareArcType::EF,
+            label: OCDeclareArcLabel {
+                each: vec![ObjectTypeAssociation::Simple {
+                    object_type: "order".to_string(),
+                }],
+                any: vec![],
+                all: vec![],
+            },
+            counts: (Some(1), None),
+        };
+        const DAY: i64 = 24 * 60 * 60 * 1000;
+        // e1 is paid after 2 days, e4 after 16 days (and again after 17 days), e2 is never paid
+        assert_eq!(
+            propose_time_bounds(&arc, &locel, 0.4),
+            Some(TimeBounds::new(Some(2 * DAY), Some(16 * DAY)))
+        );
+        assert_eq!(propose_time_bounds(&arc, &locel, 0.0), None);
+        let as_arc = OCDeclareArc {
+            arc_type: OCDeclareArcType::ASS,
+            ..arc
+        };
+        assert_eq!(propose_time_bounds(&as_arc, &locel, 0.4), None);
+    }
+
+    #[test]
+    fn time_bounds_within_noise_threshold() {
+        // o0, ..., o8 are paid 1, ..., 9 hours after placing them, o9 is never paid
+        let event = |id: String, ev_type: &str, time: String, order: usize| {
+            serde_json::json!({
+                "id": id,
+                "type": ev_type,
+                "time": time,
+                "attributes": [],
+                "relationships": [{"objectId": format!("o{order}"), "qualifier": ""}]
+            })
+        };
+        let mut events = Vec::new();
+        for i in 0..10 {
+            let day = format!("2024-01-{:02}", i + 1);
+            events.push(event(
+                format!("place{i}"),
+                "place order",
+                format!("{day}T00:00:00Z"),
+                i,
+            ));
+            if i < 9 {
+                let time = format!("{day}T{:02}:00:00Z", i + 1);
+                events.push(event(format!("pay{i}"), "pay order", time, i));
+            }
+        }
+        let objects: Vec<serde_json::Value> = (0..10)
+            .map(|i| {
+                serde_json::json!({"id": format!("o{i}"), "type": "order", "attributes": [], "relationships": []})
+            })
+            .collect();
+        let ocel = serde_json::json!({
+            "objectTypes": [{"name": "order", "attributes": []}],
+            "eventTypes": [{"name": "place order", "attributes": []}, {"name": "pay order", "attributes": []}],
+            "objects": objects,
+            "events": events,
+        });
+        let locel = crate::preprocess_ocel(
+            process_mining::import_ocel_json_from_slice(ocel.to_string().as_bytes()).unwrap(),
+        );
+        let arc = parse_model("EF(place order, pay order, Each(order),1,∞)").unwrap()[0]
+            .arc
+            .clone();
+        const HOUR: i64 = 60 * 60 * 1000;
+        // Trimming 10% of the delays on both sides (2h to 8h) would violate the arc for 3 of 10 orders
+        let trimmed = OCDeclareConstraint::new(arc.clone())
+            .with_time_bounds(TimeBounds::new(Some(2 * HOUR), Some(8 * HOUR)));
+        assert!((trimmed.get_for_all_evs_perf(&locel) - 0.3).abs() < 1e-9);
+        // Instead, the bounds are widened to keep the violation fraction within the noise threshold
+        let bounds = propose_time_bounds(&arc, &locel, 0.2).unwrap();
+        assert_eq!(bounds, TimeBounds::new(Some(HOUR), Some(9 * HOUR)));
+        let bounded = OCDeclareConstraint::new(arc.clone()).with_time_bounds(bounds);
+        assert!(bounded.get_for_all_evs_perf(&locel) <= 0.2);
+        // The arc already exceeds a noise threshold of 5% without time bounds
+        assert_eq!(propose_time_bounds(&arc, &locel, 0.05), None);
+    }
+}
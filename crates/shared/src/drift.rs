@@ -0,0 +1,406 @@
+//! Concept drift detection: Conformance of OC-DECLARE constraints over time windows
+//!
+//! The time span of the log is split into windows of a fixed length, which are either adjacent (`step_ms` unset)
+//! or overlapping (sliding windows, `step_ms` smaller than `window_ms`).
+//! Source events are assigned to windows by their timestamp, and are checked against the whole log,
+//! i.e., target events outside of a window still count.
+//! At most [`MAX_DRIFT_WINDOWS`] windows are supported.
+//! Consecutive (non-empty) windows are compared to flag significant changes of the violation rate
+//! (two-proportion z-test) and constraints starting or stopping to hold (w.r.t. the noise threshold).
+use std::fmt::Display;
+
+use chrono::{DateTime, Duration, FixedOffset};
+use process_mining::ocel::linked_ocel::{IndexLinkedOCEL, LinkedOCELAccess};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    constraint::OCDeclareConstraint,
+    diagnostics::{get_event_violation, get_source_events},
+};
+
+/// Maximum number of windows of a [`DriftReport`]
+pub const MAX_DRIFT_WINDOWS: usize = 10_000;
+
+/// Options for [`detect_drift`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct DriftOptions {
+    /// Length of each window (in milliseconds)
+    #[ts(type = "number")]
+    pub window_ms: i64,
+    /// Offset between the starts of consecutive windows (in milliseconds, defaults to `window_ms`)
+    #[ts(type = "number | null")]
+    pub step_ms: Option<i64>,
+    /// Maximum violation fraction for a constraint to hold in a window
+    pub noise_threshold: f64,
+    /// Minimum absolute z-score of the two-proportion z-test for a significant change of the violation rate
+    pub z_threshold: f64,
+}
+
+impl Default for DriftOptions {
+    fn default() -> Self {
+        Self {
+            window_ms: Duration::days(7).num_milliseconds(),
+            step_ms: None,
+            noise_threshold: 0.2,
+            z_threshold: 1.96,
+        }
+    }
+}
+
+/// Error for invalid [`DriftOptions`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidDriftOptions {
+    /// Name of the invalid option
+    pub field: String,
+    pub message: String,
+}
+
+impl Display for InvalidDriftOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid drift option {}: {}", self.field, self.message)
+    }
+}
+
+impl std::error::Error for InvalidDriftOptions {}
+
+/// Time window (start inclusive, end exclusive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TimeWindow {
+    #[ts(type = "string")]
+    pub start: DateTime<FixedOffset>,
+    #[ts(type = "string")]
+    pub end: DateTime<FixedOffset>,
+}
+
+/// Change of a constraint compared to the previous non-empty window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum DriftChange {
+    /// Significantly higher violation rate
+    ViolationIncrease,
+    /// Significantly lower violation rate
+    ViolationDecrease,
+    /// The constraint holds, but did not hold before
+    Emerged,
+    /// The constraint does not hold anymore
+    Disappeared,
+}
+
+/// Conformance of one constraint in one window (one point of the time series)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct WindowConformance {
+    /// Index of the constraint
+    pub constraint: usize,
+    /// Index of the window
+    pub window: usize,
+    /// Number of source events in the window
+    pub num_source_events: usize,
+    /// Number of violating source events in the window
+    pub num_violating_events: usize,
+    /// Fraction of violating source events (`null` if there are no source events in the window)
+    pub violation_fraction: Option<f64>,
+    /// Whether the constraint holds in the window (i.e., there are source events and the violation fraction is within the noise threshold)
+    pub holds: bool,
+    /// Changes compared to the previous window with source events
+    pub changes: Vec<DriftChange>,
+}
+
+/// Conformance of a set of constraints over time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DriftReport {
+    pub windows: Vec<TimeWindow>,
+    /// One point per constraint and window (ordered by constraint, then window)
+    pub points: Vec<WindowConformance>,
+}
+
+impl DriftReport {
+    /// All points with at least one change
+    pub fn changes(&self) -> impl Iterator<Item = &WindowConformance> {
+        self.points.iter().filter(|p| !p.changes.is_empty())
+    }
+}
+
+/// Number of windows needed to cover the time span between `first` and `last` (`None` on overflow)
+///
+/// The last window is the first one ending after `last`.
+fn num_windows(
+    first: DateTime<FixedOffset>,
+    last: DateTime<FixedOffset>,
+    window_ms: i64,
+    step_ms: i64,
+) -> Option<i64> {
+    let span_ms = (last - first).num_milliseconds();
+    if span_ms < window_ms {
+        Some(1)
+    } else {
+        ((span_ms - window_ms) / step_ms).checked_add(2)
+    }
+}
+
+/// Split the time span between `first` and `last` into windows
+fn get_windows(
+    first: DateTime<FixedOffset>,
+    last: DateTime<FixedOffset>,
+    options: &DriftOptions,
+) -> Result<Vec<TimeWindow>, InvalidDriftOptions> {
+    let step_ms = options.step_ms.unwrap_or(options.window_ms);
+    let too_many = |num: String| InvalidDriftOptions {
+        field: if options.step_ms.is_some() {
+            "step_ms"
+        } else {
+            "window_ms"
+        }
+        .to_string(),
+        message: format!("yields {num} windows, but at most {MAX_DRIFT_WINDOWS} are supported"),
+    };
+    let num = num_windows(first, last, options.window_ms, step_ms)
+        .ok_or_else(|| too_many("too many".to_string()))?;
+    if num > MAX_DRIFT_WINDOWS as i64 {
+        return Err(too_many(num.to_string()));
+    }
+    (0..num)
+        .map(|w| {
+            // Bounded by the number of windows and the time span of the log, up to the window length
+            let start = first + Duration::milliseconds(w * step_ms);
+            let end = start
+                .checked_add_signed(Duration::milliseconds(options.window_ms))
+                .ok_or_else(|| InvalidDriftOptions {
+                    field: "window_ms".to_string(),
+                    message: format!("{} is too large", options.window_ms),
+                })?;
+            Ok(TimeWindow { start, end })
+        })
+        .collect()
+}
+
+/// Two-proportion z-score of the change from `v1` of `n1` to `v2` of `n2` (`0.0` if undefined)
+fn z_score(v1: usize, n1: usize, v2: usize, n2: usize) -> f64 {
+    let (p1, p2) = (v1 as f64 / n1 as f64, v2 as f64 / n2 as f64);
+    let p = (v1 + v2) as f64 / (n1 + n2) as f64;
+    let se = (p * (1.0 - p) * (1.0 / n1 as f64 + 1.0 / n2 as f64)).sqrt();
+    if se == 0.0 {
+        0.0
+    } else {
+        (p2 - p1) / se
+    }
+}
+
+/// Compute the conformance of the constraints per time window and flag changes between consecutive windows
+pub fn detect_drift(
+    constraints: &[OCDeclareConstraint],
+    locel: &IndexLinkedOCEL,
+    options: &DriftOptions,
+) -> Result<DriftReport, InvalidDriftOptions> {
+    let step_ms = options.step_ms.unwrap_or(options.window_ms);
+    for (field, value) in [("window_ms", options.window_ms), ("step_ms", step_ms)] {
+        if value <= 0 {
+            return Err(InvalidDriftOptions {
+                field: field.to_string(),
+                message: format!("needs to be positive, but is {value}"),
+            });
+        }
+    }
+    let times: Vec<DateTime<FixedOffset>> = locel
+        .get_ev_types()
+        .flat_map(|et| locel.get_evs_of_type(et))
+        .map(|ev| locel.get_ev(ev).time)
+        .collect();
+    let (Some(first), Some(last)) = (times.iter().min(), times.iter().max()) else {
+        return Ok(DriftReport {
+            windows: Vec::new(),
+            points: Vec::new(),
+        });
+    };
+    let windows = get_windows(*first, *last, options)?;
+    let points = constraints
+        .par_iter()
+        .enumerate()
+        .flat_map_iter(|(i, constraint)| {
+            let mut checked: Vec<(DateTime<FixedOffset>, bool)> =
+                get_source_events(constraint, locel)
+                    .iter()
+                    .map(|ev| {
+                        (
+                            locel.get_ev(ev).time,
+                            get_event_violation(constraint, locel, ev).is_some(),
+                        )
+                    })
+                    .collect();
+            checked.sort_by_key(|(time, _)| *time);
+            // Number of violating events before each index
+            let violating_before: Vec<usize> = std::iter::once(0)
+                .chain(checked.iter().scan(0, |num, (_, violated)| {
+                    *num += usize::from(*violated);
+                    Some(*num)
+                }))
+                .collect();
+            // Window starts and ends are increasing, so the source events of each window
+            // (between the indices `from` and `to`) are found in a single pass
+            let (mut from, mut to) = (0, 0);
+            let mut previous: Option<(usize, usize, bool)> = None;
+            windows
+                .iter()
+                .enumerate()
+                .map(|(w, window)| {
+                    while from < checked.len() && checked[from].0 < window.start {
+                        from += 1;
+                    }
+                    to = to.max(from);
+                    while to < checked.len() && checked[to].0 < window.end {
+                        to += 1;
+                    }
+                    let num_source_events = to - from;
+                    let num_violating_events = violating_before[to] - violating_before[from];
+                    let violation_fraction = (num_source_events > 0)
+                        .then(|| num_violating_events as f64 / num_source_events as f64);
+                    let holds = violation_fraction.is_some_and(|f| f <= options.noise_threshold);
+                    let mut changes = Vec::new();
+                    if num_source_events > 0 {
+                        if let Some((prev_violating, prev_source, prev_holds)) = previous {
+                            let z = z_score(
+                                prev_violating,
+                                prev_source,
+                                num_violating_events,
+                                num_source_events,
+                            );
+                            if z >= options.z_threshold {
+                                changes.push(DriftChange::ViolationIncrease);
+                            } else if z <= -options.z_threshold {
+                                changes.push(DriftChange::ViolationDecrease);
+                            }
+                            if holds && !prev_holds {
+                                changes.push(DriftChange::Emerged);
+                            } else if !holds && prev_holds {
+                                changes.push(DriftChange::Disappeared);
+                            }
+                        }
+                        previous = Some((num_violating_events, num_source_events, holds));
+                    }
+                    WindowConformance {
+                        constraint: i,
+                        window: w,
+                        num_source_events,
+                        num_violating_events,
+                        violation_fraction,
+                        holds,
+                        changes,
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    Ok(DriftReport { windows, points })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        template::parse_model,
+        test_utils::{generated_ocel, test_ocel},
+    };
+
+    use super::*;
+
+    #[test]
+    fn fixed_windows() {
+        let locel = test_ocel();
+        let constraints = parse_model("EF(place order, pay order, Each(order),1,1)").unwrap();
+        let options = DriftOptions {
+            window_ms: Duration::days(2).num_milliseconds(),
+            noise_threshold: 0.5,
+            ..Default::default()
+        };
+        let report = detect_drift(&constraints, &locel, &options).unwrap();
+        // Events from Jan 1 to Jan 21
+        assert_eq!(report.windows.len(), 11);
+        let counts: Vec<(usize, usize)> = report
+            .points
+            .iter()
+            .map(|p| (p.num_source_events, p.num_violating_events))
+            .take(3)
+            .collect();
+        // e1 (ok), e2 (violated) / e4 (violated)
+        assert_eq!(counts, vec![(2, 1), (1, 1), (0, 0)]);
+        assert_eq!(report.points[1].changes, vec![DriftChange::Disappeared]);
+
+        let sliding = DriftOptions {
+            step_ms: Some(Duration::days(1).num_milliseconds()),
+            ..options
+        };
+        let report = detect_drift(&constraints, &locel, &sliding).unwrap();
+        assert_eq!(report.windows.len(), 20);
+        assert_eq!(report.points[0].num_source_events, 2);
+        assert_eq!(report.points[1].num_source_events, 1);
+    }
+
+    #[test]
+    fn windows_agree_with_filtering() {
+        let locel = generated_ocel();
+        let constraints = parse_model(
+            "EF(place order, pay order, Each(order),1,1)\n\
+             DF(pay order, ship order, Each(order),1,*)",
+        )
+        .unwrap();
+        for step_hours in [None, Some(5), Some(24)] {
+            let options = DriftOptions {
+                window_ms: Duration::hours(24).num_milliseconds(),
+                step_ms: step_hours.map(|h| Duration::hours(h).num_milliseconds()),
+                ..Default::default()
+            };
+            let report = detect_drift(&constraints, &locel, &options).unwrap();
+            for point in &report.points {
+                let window = &report.windows[point.window];
+                let constraint = &constraints[point.constraint];
+                let in_window: Vec<_> = get_source_events(constraint, &locel)
+                    .into_iter()
+                    .filter(|ev| {
+                        let time = locel.get_ev(ev).time;
+                        time >= window.start && time < window.end
+                    })
+                    .collect();
+                let violating = in_window
+                    .iter()
+                    .filter(|ev| get_event_violation(constraint, &locel, ev).is_some())
+                    .count();
+                assert_eq!(point.num_source_events, in_window.len());
+                assert_eq!(point.num_violating_events, violating);
+            }
+            // The last window is the first one ending after the last event
+            let last = locel.get_all_evs().map(|ev| locel.get_ev(&ev).time).max();
+            let ends: Vec<_> = report.windows.iter().rev().take(2).map(|w| w.end).collect();
+            assert!(Some(ends[0]) > last);
+            assert!(ends.get(1).is_none_or(|end| Some(*end) <= last));
+        }
+    }
+
+    #[test]
+    fn too_many_windows() {
+        let locel = test_ocel();
+        let constraints = parse_model("EF(place order, pay order, Each(order),1,1)").unwrap();
+        let options = DriftOptions {
+            window_ms: Duration::minutes(1).num_milliseconds(),
+            ..Default::default()
+        };
+        let err = detect_drift(&constraints, &locel, &options).unwrap_err();
+        assert_eq!(err.field, "window_ms");
+        let sliding = DriftOptions {
+            window_ms: Duration::days(2).num_milliseconds(),
+            step_ms: Some(1),
+            ..Default::default()
+        };
+        let err = detect_drift(&constraints, &locel, &sliding).unwrap_err();
+        assert_eq!(err.field, "step_ms");
+        let huge = DriftOptions {
+            window_ms: i64::MAX,
+            ..Default::default()
+        };
+        assert!(detect_drift(&constraints, &locel, &huge).is_err());
+    }
+}
@@ -0,0 +1,305 @@
+//! Export of OC-DECLARE models as Graphviz DOT and Mermaid graphs (e.g., for reports)
+//!
+//! Activities become nodes, with `<init>`/`<exit>` events of object types drawn as separate object nodes.
+//! Every arc becomes an edge, styled by its [`OCDeclareArcType`] and labeled with its arc type,
+//! the EACH/ALL/ANY object type associations and its count bounds.
+//! Arcs with `[0,0]` counts are drawn as negated arcs (red, with a cross/tee at the target).
+//! Optionally, edges are annotated with the violation percentages of the arcs in a log (see [`get_violation_fractions`]).
+use std::collections::HashMap;
+
+use process_mining::ocel::linked_ocel::IndexLinkedOCEL;
+use rayon::prelude::*;
+
+use crate::{
+    template::print_association, OCDeclareArc, OCDeclareArcType, EXIT_EVENT_PREFIX,
+    INIT_EVENT_PREFIX,
+};
+
+/// Kind of a node in the exported graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeKind {
+    Activity,
+    Init,
+    Exit,
+}
+
+/// A node of the exported graph (an activity or the `<init>`/`<exit>` event of an object type)
+struct Node<'a> {
+    /// Name of the activity or of the object type (for object nodes)
+    name: &'a str,
+    kind: NodeKind,
+}
+
+impl<'a> Node<'a> {
+    fn new(activity: &'a str) -> Self {
+        let object_type = |prefix: &str| {
+            activity
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix(' '))
+        };
+        if let Some(object_type) = object_type(INIT_EVENT_PREFIX) {
+            Self {
+                name: object_type,
+                kind: NodeKind::Init,
+            }
+        } else if let Some(object_type) = object_type(EXIT_EVENT_PREFIX) {
+            Self {
+                name: object_type,
+                kind: NodeKind::Exit,
+            }
+        } else {
+            Self {
+                name: activity,
+                kind: NodeKind::Activity,
+            }
+        }
+    }
+
+    fn label(&self) -> String {
+        match self.kind {
+            NodeKind::Activity => self.name.to_string(),
+            NodeKind::Init => format!("{INIT_EVENT_PREFIX} {}", self.name),
+            NodeKind::Exit => format!("{EXIT_EVENT_PREFIX} {}", self.name),
+        }
+    }
+}
+
+/// Nodes of the arcs (in order of their first occurrence) and the node indices of the source/target of every arc
+fn collect_nodes(arcs: &[OCDeclareArc]) -> (Vec<Node<'_>>, Vec<(usize, usize)>) {
+    let mut nodes = Vec::new();
+    let mut indices: HashMap<&str, usize> = HashMap::new();
+    let mut edges = Vec::with_capacity(arcs.len());
+    for arc in arcs {
+        let [from, to] = [arc.from.as_str(), arc.to.as_str()].map(|activity| {
+            *indices.entry(activity).or_insert_with(|| {
+                nodes.push(Node::new(activity));
+                nodes.len() - 1
+            })
+        });
+        edges.push((from, to));
+    }
+    (nodes, edges)
+}
+
+/// Check if an arc forbids any target events (i.e., has `[0,0]` counts)
+pub fn is_negated(arc: &OCDeclareArc) -> bool {
+    arc.counts == (Some(0), Some(0))
+}
+
+/// Lines of the label of an arc: The arc type, the object type associations, the count bounds and the violation percentage
+fn edge_label_lines(arc: &OCDeclareArc, violation_fraction: Option<f64>) -> Vec<String> {
+    let mut lines = Vec::new();
+    if is_negated(arc) {
+        lines.push(format!("NOT {}", arc.arc_type.get_name()));
+    } else {
+        lines.push(arc.arc_type.get_name().to_string());
+    }
+    for (name, assocs) in [
+        ("EACH", &arc.label.each),
+        ("ALL", &arc.label.all),
+        ("ANY", &arc.label.any),
+    ] {
+        if !assocs.is_empty() {
+            lines.push(format!(
+                "{name}: {}",
+                assocs
+                    .iter()
+                    .map(print_association)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+    if !is_negated(arc) {
+        lines.push(format!(
+            "[{},{}]",
+            arc.counts.0.unwrap_or(0),
+            arc.counts
+                .1
+                .map(|c| c.to_string())
+                .unwrap_or("∞".to_string())
+        ));
+    }
+    if let Some(violation_fraction) = violation_fraction {
+        lines.push(format!("{:.2}% violated", 100.0 * violation_fraction));
+    }
+    lines
+}
+
+/// Get the fraction of violating source events of every arc in the log (see `OCDeclareArc::get_for_all_evs_perf`)
+pub fn get_violation_fractions(arcs: &[OCDeclareArc], locel: &IndexLinkedOCEL) -> Vec<f64> {
+    arcs.par_iter()
+        .map(|arc| arc.get_for_all_evs_perf(locel))
+        .collect()
+}
+
+fn violation_fraction(violation_fractions: Option<&[f64]>, i: usize) -> Option<f64> {
+    violation_fractions.and_then(|fracs| fracs.get(i).copied())
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// DOT attributes for the edge of an arc type: style and arrowhead
+fn dot_edge_style(arc_type: &OCDeclareArcType) -> (&'static str, &'static str) {
+    match arc_type {
+        OCDeclareArcType::ASS => ("dotted", "dot"),
+        OCDeclareArcType::EF => ("solid", "normal"),
+        OCDeclareArcType::EP => ("dashed", "normal"),
+        OCDeclareArcType::DF => ("bold", "normalnormal"),
+        OCDeclareArcType::DP => ("bold,dashed", "normalnormal"),
+    }
+}
+
+/// Export arcs as a Graphviz DOT digraph
+///
+/// If `violation_fractions` are given (in the same order as the arcs), the edges are annotated with them.
+pub fn to_dot(arcs: &[OCDeclareArc], violation_fractions: Option<&[f64]>) -> String {
+    let (nodes, edges) = collect_nodes(arcs);
+    let mut ret = String::from("digraph oc_declare {\n  rankdir=LR;\n  node [fontname=\"Helvetica\"];\n  edge [fontname=\"Helvetica\", fontsize=10];\n");
+    for (i, node) in nodes.iter().enumerate() {
+        let attrs = match node.kind {
+            NodeKind::Activity => "shape=box, style=rounded",
+            NodeKind::Init => "shape=ellipse, style=filled, fillcolor=\"#dcfce7\"",
+            NodeKind::Exit => "shape=ellipse, style=filled, fillcolor=\"#fee2e2\"",
+        };
+        ret.push_str(&format!(
+            "  n{i} [label=\"{}\", {attrs}];\n",
+            escape_dot(&node.label())
+        ));
+    }
+    for (i, (arc, (from, to))) in arcs.iter().zip(edges).enumerate() {
+        let (style, arrowhead) = dot_edge_style(&arc.arc_type);
+        let label = edge_label_lines(arc, violation_fraction(violation_fractions, i))
+            .iter()
+            .map(|line| escape_dot(line))
+            .collect::<Vec<_>>()
+            .join("\\n");
+        let negation = if is_negated(arc) {
+            ", color=\"#dc2626\", fontcolor=\"#dc2626\""
+        } else {
+            ""
+        };
+        let arrowhead = if is_negated(arc) {
+            format!("tee{arrowhead}")
+        } else {
+            arrowhead.to_string()
+        };
+        ret.push_str(&format!(
+            "  n{from} -> n{to} [label=\"{label}\", style=\"{style}\", arrowhead={arrowhead}{negation}];\n"
+        ));
+    }
+    ret.push_str("}\n");
+    ret
+}
+
+fn escape_mermaid(s: &str) -> String {
+    s.replace('&', "#amp;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+/// Mermaid link style (CSS) for the edge of an arc type
+fn mermaid_edge_style(arc_type: &OCDeclareArcType) -> &'static str {
+    match arc_type {
+        OCDeclareArcType::ASS => "stroke-width:1px,stroke-dasharray:2 3",
+        OCDeclareArcType::EF => "stroke-width:2px",
+        OCDeclareArcType::EP => "stroke-width:2px,stroke-dasharray:6 3",
+        OCDeclareArcType::DF => "stroke-width:4px",
+        OCDeclareArcType::DP => "stroke-width:4px,stroke-dasharray:6 3",
+    }
+}
+
+/// Export arcs as a Mermaid flowchart
+///
+/// If `violation_fractions` are given (in the same order as the arcs), the edges are annotated with them.
+pub fn to_mermaid(arcs: &[OCDeclareArc], violation_fractions: Option<&[f64]>) -> String {
+    let (nodes, edges) = collect_nodes(arcs);
+    let mut ret = String::from("flowchart LR\n");
+    for (i, node) in nodes.iter().enumerate() {
+        let label = escape_mermaid(&node.label());
+        match node.kind {
+            NodeKind::Activity => ret.push_str(&format!("  n{i}[\"{label}\"]\n")),
+            NodeKind::Init => ret.push_str(&format!("  n{i}([\"{label}\"]):::init\n")),
+            NodeKind::Exit => ret.push_str(&format!("  n{i}([\"{label}\"]):::exit\n")),
+        }
+    }
+    let mut link_styles = Vec::new();
+    for (i, (arc, (from, to))) in arcs.iter().zip(edges).enumerate() {
+        let label = edge_label_lines(arc, violation_fraction(violation_fractions, i))
+            .iter()
+            .map(|line| escape_mermaid(line))
+            .collect::<Vec<_>>()
+            .join("<br>");
+        let link = if is_negated(arc) {
+            "--x"
+        } else if arc.arc_type == OCDeclareArcType::ASS {
+            "---"
+        } else {
+            "-->"
+        };
+        ret.push_str(&format!("  n{from} {link}|\"{label}\"| n{to}\n"));
+        let style = mermaid_edge_style(&arc.arc_type);
+        if is_negated(arc) {
+            link_styles.push(format!("  linkStyle {i} {style},stroke:#dc2626\n"));
+        } else {
+            link_styles.push(format!("  linkStyle {i} {style}\n"));
+        }
+    }
+    for link_style in link_styles {
+        ret.push_str(&link_style);
+    }
+    ret.push_str("  classDef init fill:#dcfce7\n  classDef exit fill:#fee2e2\n");
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::template::parse_model;
+
+    use super::*;
+
+    fn arcs() -> Vec<OCDeclareArc> {
+        parse_model(
+            "EF(place order, pay order, Each(orders), All(orders>items),1,∞)\n\
+             AS(<init> orders, place order,1,*)\n\
+             DP(\"ship \\\"order\\\"\", pay order, Any(orders),0,0)",
+        )
+        .unwrap()
+        .into_iter()
+        .map(|c| c.arc)
+        .collect()
+    }
+
+    #[test]
+    fn dot() {
+        let dot = to_dot(&arcs(), Some(&[0.125, 0.0, 1.0]));
+        assert!(dot.starts_with("digraph oc_declare {"));
+        assert!(dot.contains("n0 [label=\"place order\", shape=box, style=rounded];"));
+        assert!(dot.contains("n2 [label=\"<init> orders\", shape=ellipse"));
+        assert!(dot.contains("n3 [label=\"ship \\\"order\\\"\", shape=box"));
+        assert!(dot.contains(
+            "n0 -> n1 [label=\"EF\\nEACH: orders\\nALL: orders>items\\n[1,∞]\\n12.50% violated\", style=\"solid\", arrowhead=normal];"
+        ));
+        assert!(dot.contains(
+            "n3 -> n1 [label=\"NOT DP\\nANY: orders\\n100.00% violated\", style=\"bold,dashed\", arrowhead=teenormalnormal, color=\"#dc2626\""
+        ));
+    }
+
+    #[test]
+    fn mermaid() {
+        let mermaid = to_mermaid(&arcs(), None);
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("n2([\"#lt;init#gt; orders\"]):::init"));
+        assert!(
+            mermaid.contains("n0 -->|\"EF<br>EACH: orders<br>ALL: orders#gt;items<br>[1,∞]\"| n1")
+        );
+        assert!(mermaid.contains("n2 ---|\"AS<br>[1,∞]\"| n0"));
+        assert!(mermaid.contains("n3 --x|\"NOT DP<br>ANY: orders\"| n1"));
+        assert!(
+            mermaid.contains("linkStyle 2 stroke-width:4px,stroke-dasharray:6 3,stroke:#dc2626")
+        );
+    }
+}
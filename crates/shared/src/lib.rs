@@ -1,3 +1,21 @@
 pub use process_mining;
 pub use process_mining::object_centric::oc_declare::*;
+pub mod consistency;
+pub mod constraint;
+pub mod declare;
+pub mod diagnostics;
+pub mod diff;
+pub mod discovery;
+pub mod drift;
+pub mod graph;
+pub mod ltlf;
+pub mod monitor;
+pub mod predicate;
 pub mod reduction;
+pub mod snapshot;
+pub mod sql;
+pub mod tables;
+pub mod template;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
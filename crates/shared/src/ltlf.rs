@@ -0,0 +1,485 @@
+//! Translation of OC-DECLARE arcs to LTLf formulas over object-type projections of a log
+//!
+//! The projection of a log onto an object type `T` contains one trace per object of type `T`:
+//! the activities of all events involving the object (including its `<init>`/`<exit>` events), ordered by time
+//! (see [`get_object_traces`]). The projection satisfies the formula of an arc if every trace satisfies it.
+//!
+//! Formulas use the common LTLf syntax of tools like LTLf2DFA or Declare4Py: `true`, `false`, `!`, `&`, `|`, `->`,
+//! `X` (strong next), `WX` (weak next), `U`, `G` and `F`. The weak until `p W q` is written as `((p U q) | G(p))`.
+//! Activities become propositions (see [`activity_proposition`]), and, as in Declare, every position of a trace
+//! is assumed to satisfy exactly one proposition.
+//!
+//! Every occurrence of the source activity in a trace (an activation) requires the number of occurrences of the
+//! target activity to lie within the count bounds, where occurrences are counted
+//! - after the activation for `EF`,
+//! - before the activation for `EP`,
+//! - directly after the activation (i.e., at most one) for `DF`,
+//! - directly before the activation (i.e., at most one) for `DP`,
+//! - anywhere in the trace (including the activation itself) for `AS`.
+//!
+//! In the trace of an object, the targets of a source event are the target events involving the object.
+//! This coincides with the OC-DECLARE semantics of `EACH(T)`, `ALL(T)` and `ANY(T)` if the label only consists of `T`
+//! and every source event involves exactly one object of type `T`.
+//! For `EACH(T)`, the translation is also exact for source events with several objects of type `T`,
+//! as every object is checked on its own. In contrast, a trace may contain more targets than `ALL(T)` (which also
+//! requires the other objects of the source event) and fewer targets than `ANY(T)` (which also counts the
+//! targets of the other objects). Further object types and O2O associations in the label are ignored,
+//! such that the projection contains more targets than the arc.
+//! Source events without any object of type `T` are not part of the projection and thus never violate the formula.
+use std::fmt::Display;
+
+use process_mining::ocel::linked_ocel::{IndexLinkedOCEL, LinkedOCELAccess};
+
+use crate::{OCDeclareArc, OCDeclareArcType, ObjectTypeAssociation};
+
+/// An LTLf formula over activity propositions
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LtlfFormula {
+    True,
+    False,
+    /// Proposition of an activity (see [`activity_proposition`])
+    Atom(String),
+    Not(Box<LtlfFormula>),
+    And(Box<LtlfFormula>, Box<LtlfFormula>),
+    Or(Box<LtlfFormula>, Box<LtlfFormula>),
+    Implies(Box<LtlfFormula>, Box<LtlfFormula>),
+    /// Strong next (`X`): There is a next position and the formula holds there
+    Next(Box<LtlfFormula>),
+    /// Weak next (`WX`): There is no next position or the formula holds there
+    WeakNext(Box<LtlfFormula>),
+    Until(Box<LtlfFormula>, Box<LtlfFormula>),
+    Globally(Box<LtlfFormula>),
+    Eventually(Box<LtlfFormula>),
+}
+
+use LtlfFormula::*;
+
+impl LtlfFormula {
+    pub fn atom(activity: &str) -> Self {
+        Atom(activity_proposition(activity))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(f: Self) -> Self {
+        match f {
+            True => False,
+            False => True,
+            Not(f) => *f,
+            f => Not(Box::new(f)),
+        }
+    }
+
+    pub fn and(l: Self, r: Self) -> Self {
+        match (l, r) {
+            (True, f) | (f, True) => f,
+            (False, _) | (_, False) => False,
+            (l, r) => And(Box::new(l), Box::new(r)),
+        }
+    }
+
+    pub fn or(l: Self, r: Self) -> Self {
+        match (l, r) {
+            (False, f) | (f, False) => f,
+            (True, _) | (_, True) => True,
+            (l, r) => Or(Box::new(l), Box::new(r)),
+        }
+    }
+
+    pub fn implies(l: Self, r: Self) -> Self {
+        match (l, r) {
+            (False, _) | (_, True) => True,
+            (True, f) => f,
+            (f, False) => Self::not(f),
+            (l, r) => Implies(Box::new(l), Box::new(r)),
+        }
+    }
+
+    pub fn next(f: Self) -> Self {
+        match f {
+            False => False,
+            f => Next(Box::new(f)),
+        }
+    }
+
+    pub fn weak_next(f: Self) -> Self {
+        match f {
+            True => True,
+            f => WeakNext(Box::new(f)),
+        }
+    }
+
+    pub fn until(l: Self, r: Self) -> Self {
+        match (l, r) {
+            (_, True) => True,
+            (_, False) => False,
+            (l, r) => Until(Box::new(l), Box::new(r)),
+        }
+    }
+
+    /// Weak until (`l W r`), i.e., `(l U r) | G(l)`
+    pub fn weak_until(l: Self, r: Self) -> Self {
+        Self::or(Self::until(l.clone(), r), Self::globally(l))
+    }
+
+    pub fn globally(f: Self) -> Self {
+        match f {
+            True => True,
+            f => Globally(Box::new(f)),
+        }
+    }
+
+    pub fn eventually(f: Self) -> Self {
+        match f {
+            False => False,
+            f => Eventually(Box::new(f)),
+        }
+    }
+
+    /// Check if the formula holds for a trace (given as the activities of its events)
+    pub fn holds<S: AsRef<str>>(&self, trace: &[S]) -> bool {
+        let trace: Vec<String> = trace
+            .iter()
+            .map(|activity| activity_proposition(activity.as_ref()))
+            .collect();
+        self.holds_at(&trace, 0)
+    }
+
+    fn holds_at(&self, trace: &[String], i: usize) -> bool {
+        match self {
+            True => true,
+            False => false,
+            Atom(p) => trace.get(i).is_some_and(|q| q == p),
+            Not(f) => !f.holds_at(trace, i),
+            And(l, r) => l.holds_at(trace, i) && r.holds_at(trace, i),
+            Or(l, r) => l.holds_at(trace, i) || r.holds_at(trace, i),
+            Implies(l, r) => !l.holds_at(trace, i) || r.holds_at(trace, i),
+            Next(f) => i + 1 < trace.len() && f.holds_at(trace, i + 1),
+            WeakNext(f) => i + 1 >= trace.len() || f.holds_at(trace, i + 1),
+            Until(l, r) => (i..trace.len())
+                .find(|j| r.holds_at(trace, *j) || !l.holds_at(trace, *j))
+                .is_some_and(|j| r.holds_at(trace, j)),
+            Globally(f) => (i..trace.len()).all(|j| f.holds_at(trace, j)),
+            Eventually(f) => (i..trace.len()).any(|j| f.holds_at(trace, j)),
+        }
+    }
+}
+
+impl Display for LtlfFormula {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            True => write!(f, "true"),
+            False => write!(f, "false"),
+            Atom(p) => write!(f, "{p}"),
+            Not(g) => match g.as_ref() {
+                Atom(p) => write!(f, "!{p}"),
+                g => write!(f, "!({g})"),
+            },
+            And(l, r) => write!(f, "({l} & {r})"),
+            Or(l, r) => write!(f, "({l} | {r})"),
+            Implies(l, r) => write!(f, "({l} -> {r})"),
+            Next(g) => write!(f, "X({g})"),
+            WeakNext(g) => write!(f, "WX({g})"),
+            Until(l, r) => write!(f, "({l} U {r})"),
+            Globally(g) => write!(f, "G({g})"),
+            Eventually(g) => write!(f, "F({g})"),
+        }
+    }
+}
+
+/// Proposition name of an activity: The lowercase alphanumeric parts of the name, joined by `_`
+///
+/// For example, `place order` becomes `place_order` and `<init> orders` becomes `init_orders`.
+/// Names not starting with a letter are prefixed by `act_`.
+pub fn activity_proposition(activity: &str) -> String {
+    let name = activity
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if name.starts_with(|c: char| c.is_alphabetic()) {
+        name
+    } else {
+        format!("act_{name}")
+    }
+}
+
+/// At least `n` occurrences of `target` from the current position on
+fn at_least(target: &LtlfFormula, n: usize) -> LtlfFormula {
+    (0..n)
+        .fold(None, |rest: Option<LtlfFormula>, _| {
+            let rest = rest.map_or(True, LtlfFormula::next);
+            Some(LtlfFormula::eventually(LtlfFormula::and(
+                target.clone(),
+                rest,
+            )))
+        })
+        .unwrap_or(True)
+}
+
+/// At most `n` occurrences of `target` from the current position on
+fn at_most(target: &LtlfFormula, n: usize) -> LtlfFormula {
+    (0..n).fold(
+        LtlfFormula::globally(LtlfFormula::not(target.clone())),
+        |rest, _| {
+            LtlfFormula::globally(LtlfFormula::implies(
+                target.clone(),
+                LtlfFormula::weak_next(rest),
+            ))
+        },
+    )
+}
+
+/// `then` holds after the `n`-th occurrence of `target` (from the next position on), if there are `n` occurrences
+fn after_occurrences(target: &LtlfFormula, n: usize, then: LtlfFormula) -> LtlfFormula {
+    (0..n).fold(then, |rest, _| {
+        LtlfFormula::weak_until(
+            LtlfFormula::not(target.clone()),
+            LtlfFormula::and(target.clone(), LtlfFormula::weak_next(rest)),
+        )
+    })
+}
+
+/// Translate an arc to an LTLf formula over the projection onto the given object type
+///
+/// Returns `None` if the object type is not part of the label of the arc (as a simple association).
+/// See the [module documentation](self) for the semantics.
+pub fn arc_to_ltlf(arc: &OCDeclareArc, object_type: &str) -> Option<LtlfFormula> {
+    let label = &arc.label;
+    label
+        .each
+        .iter()
+        .chain(&label.all)
+        .chain(&label.any)
+        .find(|assoc| {
+            matches!(assoc, ObjectTypeAssociation::Simple { object_type: ot } if ot == object_type)
+        })?;
+    let self_loop = arc.from == arc.to;
+    let source = LtlfFormula::atom(arc.from.as_str());
+    let target = LtlfFormula::atom(arc.to.as_str());
+    // Target occurrences which are not the activation itself
+    let other_target = if self_loop { False } else { target.clone() };
+    let (min, max) = (arc.counts.0.unwrap_or(0), arc.counts.1);
+    let formula = match arc.arc_type {
+        OCDeclareArcType::EF => LtlfFormula::globally(LtlfFormula::implies(
+            source,
+            LtlfFormula::and(
+                if min == 0 {
+                    True
+                } else {
+                    LtlfFormula::next(at_least(&target, min))
+                },
+                max.map_or(True, |max| LtlfFormula::weak_next(at_most(&target, max))),
+            ),
+        )),
+        OCDeclareArcType::EP => LtlfFormula::and(
+            // Activations before the `min`-th target occurrence violate the arc
+            (0..min).fold(True, |rest, _| {
+                LtlfFormula::weak_until(
+                    LtlfFormula::not(source.clone()),
+                    LtlfFormula::and(other_target.clone(), LtlfFormula::weak_next(rest)),
+                )
+            }),
+            // Activations after the `max + 1`-th target occurrence violate the arc
+            max.map_or(True, |max| {
+                after_occurrences(
+                    &target,
+                    max + 1,
+                    LtlfFormula::globally(LtlfFormula::not(source.clone())),
+                )
+            }),
+        ),
+        OCDeclareArcType::DF => LtlfFormula::globally(LtlfFormula::implies(
+            source,
+            LtlfFormula::and(
+                match min {
+                    0 => True,
+                    1 => LtlfFormula::next(target.clone()),
+                    _ => False,
+                },
+                if max == Some(0) {
+                    LtlfFormula::weak_next(LtlfFormula::not(target))
+                } else {
+                    True
+                },
+            ),
+        )),
+        OCDeclareArcType::DP => LtlfFormula::and(
+            match min {
+                0 => True,
+                // Activations need to be preceded by the target (and can thus not be at the first position)
+                1 => LtlfFormula::and(
+                    LtlfFormula::not(source.clone()),
+                    LtlfFormula::globally(LtlfFormula::implies(
+                        LtlfFormula::not(target.clone()),
+                        LtlfFormula::weak_next(LtlfFormula::not(source.clone())),
+                    )),
+                ),
+                _ => LtlfFormula::globally(LtlfFormula::not(source.clone())),
+            },
+            if max == Some(0) {
+                LtlfFormula::globally(LtlfFormula::implies(
+                    target,
+                    LtlfFormula::weak_next(LtlfFormula::not(source)),
+                ))
+            } else {
+                True
+            },
+        ),
+        // For self-loops, the activation itself is one of the counted occurrences
+        OCDeclareArcType::ASS => LtlfFormula::implies(
+            LtlfFormula::eventually(source),
+            LtlfFormula::and(
+                at_least(&target, min),
+                max.map_or(True, |max| at_most(&target, max)),
+            ),
+        ),
+    };
+    Some(formula)
+}
+
+/// Translate an arc to LTLf formulas over the projections onto all object types of its label (see [`arc_to_ltlf`])
+pub fn arc_to_ltlf_projections(arc: &OCDeclareArc) -> Vec<(String, LtlfFormula)> {
+    let mut object_types: Vec<&str> = Vec::new();
+    for assoc in arc
+        .label
+        .each
+        .iter()
+        .chain(&arc.label.all)
+        .chain(&arc.label.any)
+    {
+        if let ObjectTypeAssociation::Simple { object_type } = assoc {
+            if !object_types.contains(&object_type.as_str()) {
+                object_types.push(object_type);
+            }
+        }
+    }
+    object_types
+        .into_iter()
+        .filter_map(|ot| arc_to_ltlf(arc, ot).map(|formula| (ot.to_string(), formula)))
+        .collect()
+}
+
+/// Get the projection of a log onto an object type: The activities of the events of every object, ordered by time
+pub fn get_object_traces<'a>(locel: &'a IndexLinkedOCEL, object_type: &str) -> Vec<Vec<&'a str>> {
+    locel
+        .get_obs_of_type(object_type)
+        .map(|ob| {
+            let mut evs: Vec<_> = locel.get_e2o_rev(ob).map(|(_, ev)| *ev).collect();
+            evs.sort_by_key(|ev| (locel.get_ev(ev).time, *ev));
+            evs.dedup();
+            evs.iter()
+                .map(|ev| locel.get_ev(ev).event_type.as_str())
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use process_mining::import_ocel_json_from_slice;
+
+    use crate::{preprocess_ocel, template::parse_constraint};
+
+    use super::*;
+
+    /// Single-object log with the given activities (one event per minute)
+    fn trace_ocel(trace: &[&str]) -> IndexLinkedOCEL {
+        let events: Vec<String> = trace
+            .iter()
+            .enumerate()
+            .map(|(i, act)| {
+                format!(
+                    r#"{{"id": "e{i}", "type": "{act}", "time": "2024-01-01T00:{i:02}:00Z", "attributes": [], "relationships": [{{"objectId": "o1", "qualifier": ""}}]}}"#
+                )
+            })
+            .collect();
+        let json = format!(
+            r#"{{
+                "objectTypes": [{{"name": "order", "attributes": []}}],
+                "eventTypes": [{{"name": "a", "attributes": []}}, {{"name": "b", "attributes": []}}],
+                "objects": [{{"id": "o1", "type": "order", "attributes": [], "relationships": []}}],
+                "events": [{}]
+            }}"#,
+            events.join(",")
+        );
+        preprocess_ocel(import_ocel_json_from_slice(json.as_bytes()).unwrap())
+    }
+
+    /// All traces over `a` and `b` with 1 to `max_len` events
+    fn all_traces(max_len: usize) -> Vec<Vec<&'static str>> {
+        let mut ret: Vec<Vec<&str>> = vec![vec![]];
+        let mut last: Vec<Vec<&str>> = vec![vec![]];
+        for _ in 0..max_len {
+            last = last
+                .iter()
+                .flat_map(|t| {
+                    ["a", "b"].map(|act| {
+                        let mut t = t.clone();
+                        t.push(act);
+                        t
+                    })
+                })
+                .collect();
+            ret.extend(last.iter().cloned());
+        }
+        ret.retain(|t| !t.is_empty());
+        ret
+    }
+
+    #[test]
+    fn formula_syntax() {
+        let arc = parse_constraint("EF(place order, pay order, Each(orders),1,2)")
+            .unwrap()
+            .arc;
+        assert_eq!(
+            arc_to_ltlf(&arc, "orders").unwrap().to_string(),
+            "G((place_order -> (X(F(pay_order)) & WX(G((pay_order -> WX(G((pay_order -> WX(G(!pay_order)))))))))))"
+        );
+        assert_eq!(arc_to_ltlf(&arc, "items"), None);
+        assert_eq!(activity_proposition("<init> orders"), "init_orders");
+        assert_eq!(activity_proposition("1st Check"), "act_1st_check");
+    }
+
+    #[test]
+    fn agrees_with_get_for_all_evs_perf() {
+        let logs: Vec<(Vec<&str>, IndexLinkedOCEL)> = all_traces(5)
+            .into_iter()
+            .map(|t| {
+                let locel = trace_ocel(&t);
+                (t, locel)
+            })
+            .collect();
+        for arc_type in ["AS", "EF", "EP", "DF", "DP"] {
+            for (from, to) in [("a", "b"), ("b", "a"), ("a", "a")] {
+                for label in ["Each(order)", "All(order)", "Any(order)"] {
+                    for (min, max) in [
+                        ("0", "0"),
+                        ("1", "∞"),
+                        ("2", "∞"),
+                        ("0", "1"),
+                        ("1", "1"),
+                        ("1", "2"),
+                    ] {
+                        let arc = parse_constraint(&format!(
+                            "{arc_type}({from}, {to}, {label},{min},{max})"
+                        ))
+                        .unwrap()
+                        .arc;
+                        let formula = arc_to_ltlf(&arc, "order").unwrap();
+                        for (trace, locel) in &logs {
+                            let traces = get_object_traces(locel, "order");
+                            assert_eq!(traces.len(), 1);
+                            assert_eq!(
+                                formula.holds(&traces[0]),
+                                arc.get_for_all_evs_perf(locel) == 0.0,
+                                "{} on {trace:?}: {formula}",
+                                arc.as_template_string()
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
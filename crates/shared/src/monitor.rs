@@ -0,0 +1,1458 @@
+//! Online (streaming) conformance monitoring of OC-DECLARE constraints
+//!
+//! Events and objects are fed to a [`ConformanceMonitor`] one at a time, with events in the order of their timestamps.
+//! For every source event and binding (one per combination of EACH objects, see [`crate::diagnostics`]), the monitor
+//! keeps an _activation_ counting the matching target events observed so far, and reports whenever its state changes.
+//!
+//! Once the stream is finished ([`ConformanceMonitor::finish`]), the final states agree with the
+//! offline check of the constraints on the same events (see [`crate::diagnostics::get_violation_report`]).
+//! Like `preprocess_ocel`, the monitor adds an `<init>` event for each object (directly before its first event)
+//! and an `<exit>` event (with the timestamp of its last event, but only once the stream is finished).
+//! Thus, activations which may still match a past `<exit>` event stay open until the object is involved in another event.
+//! Objects need to be added before the events involving them, as their type determines the bindings.
+//! ALL and ANY associations are ordered by the number of objects of their type added so far,
+//! which agrees with the offline check if all objects are added before the first event.
+//!
+//! As in the offline check, events with the same timestamp are unordered for `EF`/`EP`, i.e., they are neither
+//! before nor after each other. Between equally near events, `DF` picks the first and `DP` the last one in the order
+//! of the offline check: `<init>` events first, then all other events in stream order, and `<exit>` events last.
+//! This agrees with the offline check if the stream has the order of the events in the OCEL.
+//! Also like the offline check, `DF`/`DP` arcs without object requirements use the first/last event of the whole stream.
+//!
+//! Only events which can still be targets of future source events are kept: Events of the target activities of
+//! `EP`/`AS` arcs (or all events, if there is a `DP` arc), and only for the largest maximum delay of these arcs
+//! (forever, if one of them has none). Open activations are indexed by the objects through which target events
+//! are looked up, so that each event only visits the activations it can affect, and are closed once their state is
+//! permanent (e.g., after their maximum delay).
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt::Display,
+};
+
+use chrono::{DateTime, Duration, FixedOffset};
+use itertools::Itertools;
+use process_mining::ocel::ocel_struct::{OCELEvent, OCELEventAttribute, OCELObject};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    constraint::OCDeclareConstraint,
+    diagnostics::{bindings_for, SetFilter},
+    OCDeclareArcType, ObjectTypeAssociation, EXIT_EVENT_PREFIX, INIT_EVENT_PREFIX,
+};
+
+/// One item of an event stream: Either an event or an object (with its O2O relationships)
+///
+/// Items are distinguished by the `time` field, which only events have.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StreamItem {
+    Event(OCELEvent),
+    Object(OCELObject),
+}
+
+/// State of a constraint (or of a single activation) on the prefix of the stream observed so far
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum MonitorState {
+    /// Satisfied, and no continuation of the stream can violate it
+    PermanentlySatisfied,
+    /// Currently satisfied, but future events may violate it
+    TemporarilySatisfied,
+    /// Currently violated, but future events may satisfy it
+    TemporarilyViolated,
+    /// Violated, and no continuation of the stream can satisfy it
+    PermanentlyViolated,
+}
+
+impl MonitorState {
+    /// Check if the state can not change anymore
+    pub fn is_permanent(&self) -> bool {
+        matches!(
+            self,
+            MonitorState::PermanentlySatisfied | MonitorState::PermanentlyViolated
+        )
+    }
+
+    /// Check if the state is (temporarily or permanently) violated
+    pub fn is_violated(&self) -> bool {
+        matches!(
+            self,
+            MonitorState::TemporarilyViolated | MonitorState::PermanentlyViolated
+        )
+    }
+}
+
+/// Notification about a changed state of an activation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MonitorUpdate {
+    /// Index of the constraint in the monitored model
+    pub constraint: usize,
+    /// ID of the source event of the activation
+    pub source_event: String,
+    /// IDs of the EACH objects of the activation (one per EACH association)
+    pub each_objects: Vec<String>,
+    /// Number of matching target events observed so far
+    pub observed_count: usize,
+    /// New state of the activation
+    pub state: MonitorState,
+    /// State of the whole constraint after the update
+    pub constraint_state: MonitorState,
+}
+
+/// Error while feeding an item to a [`ConformanceMonitor`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MonitorError {
+    /// The event has an earlier timestamp than a previously added event
+    OutOfOrder { event: String },
+    /// An event with the same ID was already added
+    ///
+    /// Only detected for events which are still kept (see [`crate::monitor`]), but at least for the events with the
+    /// latest timestamp.
+    DuplicateEvent { event: String },
+    /// The stream was already finished
+    Finished,
+}
+
+impl Display for MonitorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonitorError::OutOfOrder { event } => {
+                write!(f, "Event {event} occurs before a previously added event")
+            }
+            MonitorError::DuplicateEvent { event } => write!(f, "Duplicate event ID {event}"),
+            MonitorError::Finished => write!(f, "The stream was already finished"),
+        }
+    }
+}
+
+impl std::error::Error for MonitorError {}
+
+#[derive(Debug, Clone)]
+struct StreamEvent {
+    id: String,
+    activity: String,
+    time: DateTime<FixedOffset>,
+    attributes: Vec<OCELEventAttribute>,
+    /// Involved objects in the order of the E2O relationships (which determines the order of bindings)
+    e2o: Vec<usize>,
+    objects: HashSet<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct StreamObject {
+    id: String,
+    /// `None` for objects only referenced so far (which are ignored for bindings)
+    object_type: Option<String>,
+    o2o: Vec<usize>,
+    o2o_rev: Vec<usize>,
+    /// Kept events involving the object, in stream order
+    events: VecDeque<usize>,
+    /// Timestamp of the last event involving the object (`None` before its first event)
+    last_time: Option<DateTime<FixedOffset>>,
+}
+
+/// Which events need to be kept, as they can be targets of future source events
+#[derive(Debug, Clone)]
+struct Retention {
+    /// Activities of the kept events (`None` for all activities)
+    activities: Option<HashSet<String>>,
+    /// How long (in ms, before the latest event) events are kept (`None` for forever)
+    max_age_ms: Option<i64>,
+}
+
+impl Retention {
+    /// Only `EP`, `DP` and `AS` arcs match target events before their source events
+    fn new(constraints: &[OCDeclareConstraint]) -> Self {
+        let mut activities = Some(HashSet::new());
+        let mut max_age_ms = Some(0);
+        for constraint in constraints {
+            let arc = &constraint.arc;
+            match arc.arc_type {
+                OCDeclareArcType::EF | OCDeclareArcType::DF => continue,
+                // The nearest event can have any activity
+                OCDeclareArcType::DP => activities = None,
+                OCDeclareArcType::EP | OCDeclareArcType::ASS => {
+                    if let Some(activities) = &mut activities {
+                        activities.insert(arc.to.as_str().to_string());
+                    }
+                }
+            }
+            let max_delay = constraint.time_bounds.and_then(|b| b.max_delay_ms);
+            max_age_ms = max_age_ms.zip(max_delay).map(|(age, max)| age.max(max));
+        }
+        Self {
+            activities,
+            max_age_ms,
+        }
+    }
+
+    fn keeps(&self, activity: &str) -> bool {
+        self.activities
+            .as_ref()
+            .is_none_or(|activities| activities.contains(activity))
+    }
+}
+
+/// The objects and the kept events observed so far
+#[derive(Debug, Clone)]
+struct StreamStore {
+    retention: Retention,
+    /// Kept events by their sequence number (i.e., their position in the stream)
+    events: BTreeMap<usize, StreamEvent>,
+    num_events: usize,
+    latest_time: Option<DateTime<FixedOffset>>,
+    /// First and last event in the order of the offline check (see [`offline_position`])
+    first: Option<(OfflinePosition, StreamEvent)>,
+    last: Option<(OfflinePosition, StreamEvent)>,
+    objects: Vec<StreamObject>,
+    object_ids: HashMap<String, usize>,
+    /// Number of (added) objects per object type
+    num_objects: HashMap<String, usize>,
+    events_of_activity: HashMap<String, VecDeque<usize>>,
+    /// IDs of recent events (for detecting duplicates), in stream order
+    recent_ids: VecDeque<(DateTime<FixedOffset>, String)>,
+    recent_id_set: HashSet<String>,
+}
+
+impl StreamStore {
+    fn new(retention: Retention) -> Self {
+        Self {
+            retention,
+            events: BTreeMap::new(),
+            num_events: 0,
+            latest_time: None,
+            first: None,
+            last: None,
+            objects: Vec::new(),
+            object_ids: HashMap::new(),
+            num_objects: HashMap::new(),
+            events_of_activity: HashMap::new(),
+            recent_ids: VecDeque::new(),
+            recent_id_set: HashSet::new(),
+        }
+    }
+
+    fn object_index(&mut self, id: &str) -> usize {
+        if let Some(o) = self.object_ids.get(id) {
+            return *o;
+        }
+        self.objects.push(StreamObject {
+            id: id.to_string(),
+            object_type: None,
+            o2o: Vec::new(),
+            o2o_rev: Vec::new(),
+            events: VecDeque::new(),
+            last_time: None,
+        });
+        self.object_ids
+            .insert(id.to_string(), self.objects.len() - 1);
+        self.objects.len() - 1
+    }
+
+    fn set_object_type(&mut self, o: usize, object_type: &str) {
+        if let Some(old) = self.objects[o].object_type.replace(object_type.to_string()) {
+            *self.num_objects.entry(old).or_default() -= 1;
+        }
+        *self.num_objects.entry(object_type.to_string()).or_default() += 1;
+    }
+
+    fn has_type(&self, o: usize, object_type: &str) -> bool {
+        self.objects[o].object_type.as_deref() == Some(object_type)
+    }
+
+    /// Same as `objects_for_association` in [`crate::diagnostics`], but on the stream
+    fn objects_for_association(
+        &self,
+        event: &StreamEvent,
+        assoc: &ObjectTypeAssociation,
+    ) -> Vec<usize> {
+        let involved = event.e2o.iter().copied();
+        match assoc {
+            ObjectTypeAssociation::Simple { object_type } => involved
+                .filter(|o| self.has_type(*o, object_type))
+                .collect(),
+            ObjectTypeAssociation::O2O {
+                first,
+                second,
+                reversed,
+            } => involved
+                .filter(|o| self.has_type(*o, first))
+                .flat_map(|o| {
+                    if *reversed {
+                        &self.objects[o].o2o_rev
+                    } else {
+                        &self.objects[o].o2o
+                    }
+                })
+                .filter(|o2| self.has_type(**o2, second))
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// Add an event (kept if it can be a target of future source events)
+    fn push(&mut self, event: &StreamEvent) -> usize {
+        let seq = self.num_events;
+        self.num_events += 1;
+        self.latest_time = self.latest_time.max(Some(event.time));
+        let position = offline_position(event, seq);
+        if self.first.as_ref().is_none_or(|(p, _)| position < *p) {
+            self.first = Some((position, event.clone()));
+        }
+        if self.last.as_ref().is_none_or(|(p, _)| position > *p) {
+            self.last = Some((position, event.clone()));
+        }
+        for o in &event.objects {
+            self.objects[*o].last_time = Some(event.time);
+        }
+        self.recent_ids.push_back((event.time, event.id.clone()));
+        self.recent_id_set.insert(event.id.clone());
+        if self.retention.keeps(&event.activity) {
+            for o in &event.objects {
+                self.objects[*o].events.push_back(seq);
+            }
+            self.events_of_activity
+                .entry(event.activity.clone())
+                .or_default()
+                .push_back(seq);
+            self.events.insert(seq, event.clone());
+        }
+        seq
+    }
+
+    /// Remove the events which are too old to be targets of future source events
+    fn prune(&mut self) {
+        let (Some(latest), Some(max_age)) = (self.latest_time, self.retention.max_age_ms) else {
+            return;
+        };
+        let cutoff = latest - Duration::milliseconds(max_age);
+        while self
+            .recent_ids
+            .front()
+            .is_some_and(|(time, _)| *time < cutoff)
+        {
+            if let Some((_, id)) = self.recent_ids.pop_front() {
+                self.recent_id_set.remove(&id);
+            }
+        }
+        while self
+            .events
+            .first_key_value()
+            .is_some_and(|(_, event)| event.time < cutoff)
+        {
+            let Some((seq, event)) = self.events.pop_first() else {
+                break;
+            };
+            // Events are removed in stream order, i.e., always from the front
+            for o in &event.objects {
+                let events = &mut self.objects[*o].events;
+                if events.front() == Some(&seq) {
+                    events.pop_front();
+                }
+            }
+            if let Some(events) = self.events_of_activity.get_mut(&event.activity) {
+                if events.front() == Some(&seq) {
+                    events.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Kept events which are target candidates of an activation, once per candidate lookup
+    /// (i.e., in the order and multiplicity of `get_matching_events` in [`crate::diagnostics`])
+    ///
+    /// Each candidate is returned together with the index of the object of the first filter it was looked up through.
+    fn candidates(
+        &self,
+        filters: &[SetFilter<usize>],
+        activity: Option<&str>,
+    ) -> Vec<(usize, usize)> {
+        let events_of = |o: &usize| self.objects[*o].events.iter().copied();
+        match filters.first() {
+            None => match activity {
+                Some(activity) => self
+                    .events_of_activity
+                    .get(activity)
+                    .into_iter()
+                    .flatten()
+                    .map(|seq| (*seq, 0))
+                    .collect(),
+                None => self.events.keys().map(|seq| (*seq, 0)).collect(),
+            },
+            Some(SetFilter::All(items)) => items
+                .first()
+                .into_iter()
+                .flat_map(events_of)
+                .map(|seq| (seq, 0))
+                .collect(),
+            Some(SetFilter::Any(items)) => items
+                .iter()
+                .enumerate()
+                .flat_map(|(i, o)| events_of(o).map(move |seq| (seq, i)))
+                .collect(),
+        }
+    }
+}
+
+/// Nearest event after/before the source event of a `DF`/`DP` activation (satisfying its object requirements)
+#[derive(Debug, Clone, Copy)]
+struct Nearest {
+    time: DateTime<FixedOffset>,
+    /// Index of the object of the first filter through which the event was looked up,
+    /// and the [`tie_rank`] of the event (ties are broken by both)
+    rank: (usize, u8),
+    is_target: bool,
+}
+
+/// Incremental state of one source event binding
+#[derive(Debug, Clone)]
+struct Activation {
+    source_event: String,
+    source_time: DateTime<FixedOffset>,
+    each_objects: Vec<String>,
+    /// Object requirements on target events (see `Binding::filters` in [`crate::diagnostics`])
+    filters: Vec<SetFilter<usize>>,
+    /// Nearest event after (`DF`) or before (`DP`) the source event so far
+    nearest: Option<Nearest>,
+    /// See [`pending_exits`]
+    pending_exits: Vec<usize>,
+    count: usize,
+    state: MonitorState,
+}
+
+impl Activation {
+    fn to_update(&self, constraint: usize) -> MonitorUpdate {
+        MonitorUpdate {
+            constraint,
+            source_event: self.source_event.clone(),
+            each_objects: self.each_objects.clone(),
+            observed_count: self.count,
+            state: self.state,
+            // Set once all updates for the constraint are known
+            constraint_state: self.state,
+        }
+    }
+
+    /// Count once no further target events can match
+    ///
+    /// Like in `process_mining`, the nearest event of `DF`/`DP` activations without object requirements
+    /// is the first/last event of the whole stream (instead of the nearest event after/before the source event).
+    fn final_count(&self, constraint: &OCDeclareConstraint, store: &StreamStore) -> usize {
+        let nearest_of_stream = |nearest: &Option<(_, StreamEvent)>| {
+            nearest.as_ref().map_or(0, |(_, event)| {
+                usize::from(is_target(constraint, self.source_time, event))
+            })
+        };
+        match constraint.arc.arc_type {
+            OCDeclareArcType::DF if self.filters.is_empty() => nearest_of_stream(&store.first),
+            OCDeclareArcType::DP if self.filters.is_empty() => nearest_of_stream(&store.last),
+            OCDeclareArcType::DF | OCDeclareArcType::DP => {
+                self.nearest.map_or(0, |n| usize::from(n.is_target))
+            }
+            _ => self.count,
+        }
+    }
+
+    /// Objects through which target candidates are looked up (`None` if every event is a candidate)
+    fn watched_objects(&self) -> Option<Vec<usize>> {
+        match self.filters.first() {
+            None => None,
+            Some(SetFilter::All(items)) => Some(items.first().copied().into_iter().collect()),
+            Some(SetFilter::Any(items)) => {
+                let mut items = items.clone();
+                items.sort();
+                items.dedup();
+                Some(items)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ConstraintMonitor {
+    constraint: OCDeclareConstraint,
+    /// Activations whose state is not yet permanent, by their ID
+    open: HashMap<usize, Activation>,
+    /// IDs of the open activations per object through which their target candidates are looked up
+    watched: HashMap<usize, Vec<usize>>,
+    /// IDs of the open activations for which every event is a target candidate
+    unwatched: HashSet<usize>,
+    /// Times after which open activations can not match any further events (earliest first)
+    deadlines: BinaryHeap<Reverse<(DateTime<FixedOffset>, usize)>>,
+    next_id: usize,
+    num_activations: usize,
+    num_temporarily_violated: usize,
+    num_permanently_violated: usize,
+}
+
+impl ConstraintMonitor {
+    fn new(constraint: OCDeclareConstraint) -> Self {
+        Self {
+            constraint,
+            open: HashMap::new(),
+            watched: HashMap::new(),
+            unwatched: HashSet::new(),
+            deadlines: BinaryHeap::new(),
+            next_id: 0,
+            num_activations: 0,
+            num_temporarily_violated: 0,
+            num_permanently_violated: 0,
+        }
+    }
+
+    fn state(&self, finished: bool) -> MonitorState {
+        if self.num_permanently_violated > 0 {
+            MonitorState::PermanentlyViolated
+        } else if self.num_temporarily_violated > 0 {
+            MonitorState::TemporarilyViolated
+        } else if finished {
+            MonitorState::PermanentlySatisfied
+        } else {
+            MonitorState::TemporarilySatisfied
+        }
+    }
+
+    /// Add a new activation, which is kept open unless its state is already permanent
+    fn activate(
+        &mut self,
+        index: usize,
+        mut activation: Activation,
+        store: &StreamStore,
+        updates: &mut Vec<MonitorUpdate>,
+    ) {
+        self.num_activations += 1;
+        let arc = &self.constraint.arc;
+        let is_final = match arc.arc_type {
+            // Past `<exit>` events are only added once the stream is finished
+            OCDeclareArcType::EP if activation.filters.is_empty() => {
+                !arc.to.as_str().starts_with(EXIT_EVENT_PREFIX)
+            }
+            OCDeclareArcType::EP | OCDeclareArcType::DP if !activation.filters.is_empty() => {
+                activation.pending_exits.is_empty()
+            }
+            OCDeclareArcType::DP => false,
+            OCDeclareArcType::DF if activation.filters.is_empty() => store
+                .first
+                .as_ref()
+                .is_some_and(|(_, first)| first.time < activation.source_time),
+            _ => has_no_candidates(&activation.filters),
+        };
+        if is_final {
+            activation.count = activation.final_count(&self.constraint, store);
+        }
+        activation.state =
+            activation_state(activation.count, &self.constraint.arc.counts, is_final);
+        updates.push(activation.to_update(index));
+        match activation.state {
+            MonitorState::PermanentlyViolated => {
+                self.num_permanently_violated += 1;
+                return;
+            }
+            MonitorState::PermanentlySatisfied => return,
+            MonitorState::TemporarilyViolated => self.num_temporarily_violated += 1,
+            MonitorState::TemporarilySatisfied => {}
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        let arc = &self.constraint.arc;
+        let is_direct = matches!(arc.arc_type, OCDeclareArcType::DF | OCDeclareArcType::DP);
+        match activation.watched_objects() {
+            Some(objects) => {
+                for o in objects {
+                    self.watched.entry(o).or_default().push(id);
+                }
+            }
+            // Without object requirements, `DF`/`DP` activations only depend on the first/last event of the stream
+            None if is_direct => {}
+            None => {
+                self.unwatched.insert(id);
+            }
+        }
+        if arc.arc_type == OCDeclareArcType::DF && activation.filters.is_empty() {
+            // Further events with the same timestamp may still come first
+            self.deadlines.push(Reverse((activation.source_time, id)));
+        }
+        // `<exit>` events are only added once the stream is finished (but with the timestamp of the last event),
+        // and `DP` activations can only change through them
+        let waits_for_finish =
+            arc.to.as_str().starts_with(EXIT_EVENT_PREFIX) || arc.arc_type == OCDeclareArcType::DP;
+        if let Some(max) = self.constraint.time_bounds.and_then(|b| b.max_delay_ms) {
+            if !waits_for_finish {
+                self.deadlines.push(Reverse((
+                    activation.source_time + Duration::milliseconds(max),
+                    id,
+                )));
+            }
+        }
+        self.open.insert(id, activation);
+    }
+
+    /// Update the state of an open activation after its count changed, or once no further events can match (`is_final`)
+    fn refresh(
+        &mut self,
+        index: usize,
+        id: usize,
+        is_final: bool,
+        store: &StreamStore,
+        updates: &mut Vec<MonitorUpdate>,
+    ) {
+        let Some(activation) = self.open.get_mut(&id) else {
+            return;
+        };
+        if is_final {
+            activation.count = activation.final_count(&self.constraint, store);
+        }
+        let state = activation_state(activation.count, &self.constraint.arc.counts, is_final);
+        if state == activation.state {
+            return;
+        }
+        if activation.state == MonitorState::TemporarilyViolated {
+            self.num_temporarily_violated -= 1;
+        }
+        activation.state = state;
+        updates.push(activation.to_update(index));
+        match state {
+            MonitorState::TemporarilyViolated => self.num_temporarily_violated += 1,
+            MonitorState::PermanentlyViolated => self.num_permanently_violated += 1,
+            MonitorState::PermanentlySatisfied | MonitorState::TemporarilySatisfied => {}
+        }
+        if state.is_permanent() {
+            self.close(id);
+        }
+    }
+
+    fn close(&mut self, id: usize) {
+        let Some(activation) = self.open.remove(&id) else {
+            return;
+        };
+        match activation.watched_objects() {
+            Some(objects) => {
+                for o in objects {
+                    if let Some(ids) = self.watched.get_mut(&o) {
+                        ids.retain(|i| *i != id);
+                        if ids.is_empty() {
+                            self.watched.remove(&o);
+                        }
+                    }
+                }
+            }
+            None => {
+                self.unwatched.remove(&id);
+            }
+        }
+    }
+
+    /// Make the open activations permanent which can not match events at `time` (or later) anymore
+    fn close_expired(
+        &mut self,
+        index: usize,
+        time: DateTime<FixedOffset>,
+        store: &StreamStore,
+        updates: &mut Vec<MonitorUpdate>,
+    ) {
+        while let Some(Reverse((deadline, id))) = self.deadlines.peek().copied() {
+            if deadline >= time {
+                break;
+            }
+            self.deadlines.pop();
+            self.refresh(index, id, true, store, updates);
+        }
+    }
+
+    /// Match a new event against the open activations it can affect
+    fn match_event(
+        &mut self,
+        index: usize,
+        event: &StreamEvent,
+        store: &StreamStore,
+        updates: &mut Vec<MonitorUpdate>,
+    ) {
+        let mut ids: Vec<usize> = self
+            .unwatched
+            .iter()
+            .copied()
+            .chain(
+                event
+                    .objects
+                    .iter()
+                    .filter_map(|o| self.watched.get(o))
+                    .flatten()
+                    .copied(),
+            )
+            .collect();
+        ids.sort();
+        ids.dedup();
+        for id in ids {
+            let constraint = &self.constraint;
+            let Some(activation) = self.open.get_mut(&id) else {
+                continue;
+            };
+            let mut exit_resolved = false;
+            if !activation.pending_exits.is_empty() {
+                activation
+                    .pending_exits
+                    .retain(|o| !event.objects.contains(o));
+                exit_resolved = activation.pending_exits.is_empty();
+            }
+            let Some((weight, first_key, last_key)) =
+                candidate_weight(&activation.filters, &event.objects)
+            else {
+                if exit_resolved {
+                    self.refresh(index, id, true, store, updates);
+                }
+                continue;
+            };
+            let is_target = is_target(constraint, activation.source_time, event);
+            match constraint.arc.arc_type {
+                OCDeclareArcType::EF | OCDeclareArcType::EP | OCDeclareArcType::ASS => {
+                    let in_relation = match constraint.arc.arc_type {
+                        OCDeclareArcType::EF => event.time > activation.source_time,
+                        OCDeclareArcType::EP => event.time < activation.source_time,
+                        _ => true,
+                    };
+                    if in_relation && is_target {
+                        activation.count += weight;
+                    }
+                    self.refresh(index, id, exit_resolved, store, updates);
+                }
+                OCDeclareArcType::DF => {
+                    let rank = (first_key, tie_rank(&event.activity));
+                    if event.time <= activation.source_time
+                        || activation
+                            .nearest
+                            .is_some_and(|n| (n.time, n.rank) <= (event.time, rank))
+                    {
+                        continue;
+                    }
+                    activation.nearest = Some(Nearest {
+                        time: event.time,
+                        rank,
+                        is_target,
+                    });
+                    if rank == (0, 0) {
+                        // Later events with the same timestamp can not be nearer
+                        self.refresh(index, id, true, store, updates);
+                    } else {
+                        self.deadlines.push(Reverse((event.time, id)));
+                    }
+                }
+                OCDeclareArcType::DP => {
+                    // Only past `<exit>` events can be nearer than the nearest event at activation
+                    let rank = (last_key, tie_rank(&event.activity));
+                    if event.time < activation.source_time
+                        && activation
+                            .nearest
+                            .is_none_or(|n| (n.time, n.rank) < (event.time, rank))
+                    {
+                        activation.nearest = Some(Nearest {
+                            time: event.time,
+                            rank,
+                            is_target,
+                        });
+                    }
+                    if exit_resolved {
+                        self.refresh(index, id, true, store, updates);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Incremental conformance checker for a set of OC-DECLARE constraints on a stream of events
+#[derive(Debug, Clone)]
+pub struct ConformanceMonitor {
+    store: StreamStore,
+    monitors: Vec<ConstraintMonitor>,
+    finished: bool,
+}
+
+impl ConformanceMonitor {
+    pub fn new(constraints: Vec<OCDeclareConstraint>) -> Self {
+        Self {
+            store: StreamStore::new(Retention::new(&constraints)),
+            monitors: constraints
+                .into_iter()
+                .map(ConstraintMonitor::new)
+                .collect(),
+            finished: false,
+        }
+    }
+
+    /// The monitored constraints
+    pub fn constraints(&self) -> impl Iterator<Item = &OCDeclareConstraint> {
+        self.monitors.iter().map(|m| &m.constraint)
+    }
+
+    /// Add an event or an object of the stream
+    pub fn add(&mut self, item: &StreamItem) -> Result<Vec<MonitorUpdate>, MonitorError> {
+        match item {
+            StreamItem::Event(ev) => self.add_event(ev),
+            StreamItem::Object(ob) => self.add_object(ob).map(|_| Vec::new()),
+        }
+    }
+
+    /// Add (or update) an object together with its O2O relationships
+    ///
+    /// Relationships to objects not yet added are kept, and take effect once the related object is added.
+    pub fn add_object(&mut self, object: &OCELObject) -> Result<(), MonitorError> {
+        if self.finished {
+            return Err(MonitorError::Finished);
+        }
+        let o = self.store.object_index(&object.id);
+        self.store.set_object_type(o, &object.object_type);
+        for rel in &object.relationships {
+            let o2 = self.store.object_index(&rel.object_id);
+            if !self.store.objects[o].o2o.contains(&o2) {
+                self.store.objects[o].o2o.push(o2);
+                self.store.objects[o2].o2o_rev.push(o);
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the next event of the stream
+    ///
+    /// Returns the state changes of all activations caused by the event,
+    /// including the initial states of the activations of the event itself.
+    pub fn add_event(&mut self, event: &OCELEvent) -> Result<Vec<MonitorUpdate>, MonitorError> {
+        if self.finished {
+            return Err(MonitorError::Finished);
+        }
+        if self.store.recent_id_set.contains(&event.id) {
+            return Err(MonitorError::DuplicateEvent {
+                event: event.id.clone(),
+            });
+        }
+        if self
+            .store
+            .latest_time
+            .is_some_and(|latest| latest > event.time)
+        {
+            return Err(MonitorError::OutOfOrder {
+                event: event.id.clone(),
+            });
+        }
+        let e2o: Vec<usize> = event
+            .relationships
+            .iter()
+            .map(|rel| self.store.object_index(&rel.object_id))
+            .collect();
+        let objects: HashSet<usize> = e2o.iter().copied().collect();
+        let mut updates = Vec::new();
+        let mut new_objects: Vec<usize> = objects
+            .iter()
+            .filter(|o| self.store.objects[**o].last_time.is_none())
+            .copied()
+            .collect();
+        new_objects.sort();
+        for o in new_objects {
+            updates.extend(self.add_lifecycle_event(o, INIT_EVENT_PREFIX, event.time));
+        }
+        updates.extend(self.process_event(StreamEvent {
+            id: event.id.clone(),
+            activity: event.event_type.clone(),
+            time: event.time,
+            attributes: event.attributes.clone(),
+            e2o,
+            objects,
+        }));
+        self.store.prune();
+        Ok(updates)
+    }
+
+    /// Finish the stream: Add the `<exit>` events of all objects and make all states permanent
+    ///
+    /// Calling this again has no effect.
+    pub fn finish(&mut self) -> Vec<MonitorUpdate> {
+        if self.finished {
+            return Vec::new();
+        }
+        let mut updates = Vec::new();
+        for o in 0..self.store.objects.len() {
+            if let Some(time) = self.store.objects[o].last_time {
+                updates.extend(self.add_lifecycle_event(o, EXIT_EVENT_PREFIX, time));
+            }
+        }
+        self.finished = true;
+        for (i, monitor) in self.monitors.iter_mut().enumerate() {
+            let start = updates.len();
+            let mut ids: Vec<usize> = monitor.open.keys().copied().collect();
+            ids.sort();
+            for id in ids {
+                monitor.refresh(i, id, true, &self.store, &mut updates);
+            }
+            let constraint_state = monitor.state(true);
+            for update in &mut updates[start..] {
+                update.constraint_state = constraint_state;
+            }
+        }
+        updates
+    }
+
+    /// Current state of each monitored constraint
+    ///
+    /// A constraint is violated if one of its activations is, and only permanently satisfied once the stream is finished
+    /// (as new source events may arrive before).
+    pub fn constraint_states(&self) -> Vec<MonitorState> {
+        self.monitors
+            .iter()
+            .map(|m| m.state(self.finished))
+            .collect()
+    }
+
+    /// Number of activations and of permanently violated activations of each monitored constraint
+    pub fn activation_counts(&self) -> Vec<(usize, usize)> {
+        self.monitors
+            .iter()
+            .map(|m| (m.num_activations, m.num_permanently_violated))
+            .collect()
+    }
+
+    fn add_lifecycle_event(
+        &mut self,
+        o: usize,
+        prefix: &str,
+        time: DateTime<FixedOffset>,
+    ) -> Vec<MonitorUpdate> {
+        let ob = &self.store.objects[o];
+        let Some(object_type) = &ob.object_type else {
+            return Vec::new();
+        };
+        self.process_event(StreamEvent {
+            id: format!("{prefix}_{}", ob.id),
+            activity: format!("{prefix} {object_type}"),
+            time,
+            attributes: Vec::new(),
+            e2o: vec![o],
+            objects: HashSet::from([o]),
+        })
+    }
+
+    fn process_event(&mut self, event: StreamEvent) -> Vec<MonitorUpdate> {
+        let seq = self.store.push(&event);
+        let store = &self.store;
+        let mut updates = Vec::new();
+        for (i, monitor) in self.monitors.iter_mut().enumerate() {
+            let start = updates.len();
+            monitor.close_expired(i, event.time, store, &mut updates);
+            monitor.match_event(i, &event, store, &mut updates);
+            let constraint = &monitor.constraint;
+            if event.activity == constraint.arc.from.as_str()
+                && constraint
+                    .from_predicate
+                    .as_ref()
+                    .is_none_or(|p| p.evaluate_attributes(&event.attributes))
+            {
+                for activation in get_activations(store, constraint, &event, seq) {
+                    monitor.activate(i, activation, store, &mut updates);
+                }
+            }
+            let constraint_state = monitor.state(false);
+            for update in &mut updates[start..] {
+                update.constraint_state = constraint_state;
+            }
+        }
+        updates
+    }
+}
+
+/// State of an activation with `count` matching target events
+///
+/// The count of an activation can only increase over time.
+/// `is_final` indicates that no further target events can match.
+fn activation_state(
+    count: usize,
+    counts: &(Option<usize>, Option<usize>),
+    is_final: bool,
+) -> MonitorState {
+    if counts.1.is_some_and(|max| count > max) {
+        return MonitorState::PermanentlyViolated;
+    }
+    let enough = counts.0.is_none_or(|min| count >= min);
+    match (enough, is_final) {
+        (true, true) => MonitorState::PermanentlySatisfied,
+        (true, false) if counts.1.is_none() => MonitorState::PermanentlySatisfied,
+        (true, false) => MonitorState::TemporarilySatisfied,
+        (false, true) => MonitorState::PermanentlyViolated,
+        (false, false) => MonitorState::TemporarilyViolated,
+    }
+}
+
+/// How often an event is a target candidate of an activation, if it satisfies all object requirements
+/// (as in `get_matching_events` in [`crate::diagnostics`]: once per involved object of a first ANY filter),
+/// together with the indices of the first and last involved object of a first ANY filter (`0` otherwise)
+fn candidate_weight(
+    filters: &[SetFilter<usize>],
+    objects: &HashSet<usize>,
+) -> Option<(usize, usize, usize)> {
+    if has_no_candidates(filters) || !filters.iter().all(|f| f.check(objects)) {
+        return None;
+    }
+    match filters.first() {
+        Some(SetFilter::Any(items)) => {
+            let involved: Vec<usize> = items.iter().positions(|o| objects.contains(o)).collect();
+            Some((involved.len(), *involved.first()?, *involved.last()?))
+        }
+        _ => Some((1, 0, 0)),
+    }
+}
+
+/// Objects of the first filter of a new `EP`/`DP` activation whose `<exit>` event may still be
+/// a matching target or the nearest event before the source event
+///
+/// `<exit>` events are only added once the stream is finished, but with the timestamp of the last event of their
+/// object (i.e., possibly before the source event). Objects are removed once they are involved in a further event.
+fn pending_exits(
+    store: &StreamStore,
+    constraint: &OCDeclareConstraint,
+    activation: &Activation,
+) -> Vec<usize> {
+    let arc = &constraint.arc;
+    let is_exit_target = arc.to.as_str().starts_with(EXIT_EVENT_PREFIX);
+    if !(arc.arc_type == OCDeclareArcType::DP
+        || (arc.arc_type == OCDeclareArcType::EP && is_exit_target))
+    {
+        return Vec::new();
+    }
+    activation
+        .watched_objects()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|o| {
+            let ob = &store.objects[*o];
+            let Some(last_time) = ob.last_time.filter(|t| *t < activation.source_time) else {
+                return false;
+            };
+            let Some((_, _, key)) = candidate_weight(&activation.filters, &HashSet::from([*o]))
+            else {
+                return false;
+            };
+            match arc.arc_type {
+                OCDeclareArcType::DP => activation
+                    .nearest
+                    .is_none_or(|n| (n.time, n.rank) < (last_time, (key, 2))),
+                _ => ob.object_type.as_ref().is_some_and(|object_type| {
+                    arc.to.as_str() == format!("{EXIT_EVENT_PREFIX} {object_type}")
+                }),
+            }
+        })
+        .collect()
+}
+
+type OfflinePosition = (DateTime<FixedOffset>, u8, usize);
+
+/// Position of the event `seq` in the order of the offline check (i.e., in the preprocessed OCEL, see `preprocess_ocel`)
+///
+/// Events are ordered by their timestamp. Among events with the same timestamp, `<init>` events come first and
+/// `<exit>` events last (both in the order of their objects), and all other events are in stream order.
+fn offline_position(event: &StreamEvent, seq: usize) -> OfflinePosition {
+    let rank = tie_rank(&event.activity);
+    match event.e2o.first() {
+        Some(o) if rank != 1 => (event.time, rank, *o),
+        _ => (event.time, rank, seq),
+    }
+}
+
+/// Position of an event among the events with the same timestamp in the offline check (see `preprocess_ocel`):
+/// `<init>` events come first and `<exit>` events last
+///
+/// As the monitor only adds `<init>` events once the first event of an object arrives,
+/// this is used to break ties between equally near events of `DF`/`DP` activations.
+fn tie_rank(activity: &str) -> u8 {
+    if activity.starts_with(INIT_EVENT_PREFIX) {
+        0
+    } else if activity.starts_with(EXIT_EVENT_PREFIX) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Check if no event can satisfy the object requirements of the first filter
+fn has_no_candidates(filters: &[SetFilter<usize>]) -> bool {
+    matches!(
+        filters.first(),
+        Some(SetFilter::All(items) | SetFilter::Any(items)) if items.is_empty()
+    )
+}
+
+/// Check if the event has the `to` activity and satisfies the `to` predicate and the time bounds of the constraint
+fn is_target(
+    constraint: &OCDeclareConstraint,
+    source_time: DateTime<FixedOffset>,
+    event: &StreamEvent,
+) -> bool {
+    if event.activity != constraint.arc.to.as_str()
+        || !constraint
+            .to_predicate
+            .as_ref()
+            .is_none_or(|p| p.evaluate_attributes(&event.attributes))
+    {
+        return false;
+    }
+    let diff = (event.time - source_time).num_milliseconds();
+    let distance = match constraint.arc.arc_type {
+        OCDeclareArcType::EF | OCDeclareArcType::DF => diff,
+        OCDeclareArcType::EP | OCDeclareArcType::DP => -diff,
+        OCDeclareArcType::ASS => diff.abs(),
+    };
+    constraint
+        .time_bounds
+        .is_none_or(|bounds| bounds.contains(distance))
+}
+
+/// Same as `get_bindings` in [`crate::diagnostics`], but on the stream,
+/// with the matching target events before the source event (i.e., the event `seq`) already counted
+fn get_activations(
+    store: &StreamStore,
+    constraint: &OCDeclareConstraint,
+    event: &StreamEvent,
+    seq: usize,
+) -> Vec<Activation> {
+    let arc = &constraint.arc;
+    let num_objects = |object_type: &str| store.num_objects.get(object_type).copied().unwrap_or(0);
+    bindings_for(&arc.label, |assoc| {
+        store.objects_for_association(event, assoc)
+    })
+    .into_iter()
+    .map(|binding| {
+        let mut activation = Activation {
+            source_event: event.id.clone(),
+            source_time: event.time,
+            each_objects: binding
+                .each
+                .iter()
+                .map(|(o, _)| store.objects[*o].id.clone())
+                .collect(),
+            filters: binding.filters(&arc.label, num_objects),
+            nearest: None,
+            pending_exits: Vec::new(),
+            count: 0,
+            state: MonitorState::TemporarilySatisfied,
+        };
+        if constraint.arc.arc_type == OCDeclareArcType::DP {
+            activation.nearest = nearest_before(store, constraint, &activation, seq);
+        } else {
+            activation.count = count_past_matches(store, constraint, &activation, seq);
+        }
+        activation.pending_exits = pending_exits(store, constraint, &activation);
+        activation
+    })
+    .collect()
+}
+
+/// Kept target candidates of a new activation up to its source event `seq`, which satisfy all object requirements
+///
+/// Each candidate is returned together with its sequence number and the index of the object of the first filter
+/// it was looked up through.
+fn past_candidates<'a>(
+    store: &'a StreamStore,
+    constraint: &OCDeclareConstraint,
+    activation: &'a Activation,
+    seq: usize,
+) -> impl Iterator<Item = (&'a StreamEvent, usize, usize)> {
+    let activity =
+        (constraint.arc.arc_type != OCDeclareArcType::DP).then_some(constraint.arc.to.as_str());
+    store
+        .candidates(&activation.filters, activity)
+        .into_iter()
+        .filter(move |(e2, _)| *e2 <= seq)
+        .map(|(e2, key)| (&store.events[&e2], e2, key))
+        .filter(|(event, _, _)| activation.filters.iter().all(|f| f.check(&event.objects)))
+}
+
+/// Count the matching (kept) target events of a new `EP`/`AS` activation up to its source event `seq`
+fn count_past_matches(
+    store: &StreamStore,
+    constraint: &OCDeclareConstraint,
+    activation: &Activation,
+    seq: usize,
+) -> usize {
+    let arc_type = constraint.arc.arc_type;
+    if !matches!(arc_type, OCDeclareArcType::EP | OCDeclareArcType::ASS) {
+        return 0;
+    }
+    let source_time = activation.source_time;
+    past_candidates(store, constraint, activation, seq)
+        .filter(|(event, _, _)| {
+            (arc_type == OCDeclareArcType::ASS || event.time < source_time)
+                && is_target(constraint, source_time, event)
+        })
+        .count()
+}
+
+/// The last of the nearest (kept) events before the source event `seq` of a new `DP` activation
+///
+/// Without object requirements, the nearest event is only known once the stream is finished
+/// (see `Activation::final_count`).
+fn nearest_before(
+    store: &StreamStore,
+    constraint: &OCDeclareConstraint,
+    activation: &Activation,
+    seq: usize,
+) -> Option<Nearest> {
+    if activation.filters.is_empty() {
+        return None;
+    }
+    let source_time = activation.source_time;
+    past_candidates(store, constraint, activation, seq)
+        .filter(|(event, _, _)| event.time < source_time)
+        .max_by_key(|(event, e2, key)| (event.time, *key, tie_rank(&event.activity), *e2))
+        .map(|(event, _, key)| Nearest {
+            time: event.time,
+            rank: (key, tie_rank(&event.activity)),
+            is_target: is_target(constraint, source_time, event),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use process_mining::import_ocel_json_from_slice;
+
+    use process_mining::ocel::ocel_struct::OCEL;
+
+    use crate::{
+        constraint::TimeBounds,
+        diagnostics::get_violation_report,
+        preprocess_ocel,
+        template::parse_model,
+        test_utils::{generated_arcs, generated_raw_ocel, test_ocel, TEST_OCEL_JSON},
+    };
+
+    use super::*;
+
+    /// Feed all objects and then all events (sorted by time) of the OCEL to a new monitor
+    fn monitor_ocel(
+        constraints: &[OCDeclareConstraint],
+        ocel: &OCEL,
+    ) -> (ConformanceMonitor, Vec<MonitorUpdate>) {
+        let mut monitor = ConformanceMonitor::new(constraints.to_vec());
+        let mut updates = Vec::new();
+        for ob in &ocel.objects {
+            monitor.add(&StreamItem::Object(ob.clone())).unwrap();
+        }
+        let mut events = ocel.events.clone();
+        events.sort_by_key(|e| e.time);
+        for ev in &events {
+            updates.extend(monitor.add(&StreamItem::Event(ev.clone())).unwrap());
+        }
+        updates.extend(monitor.finish());
+        (monitor, updates)
+    }
+
+    /// Permanently violated bindings (source event and EACH objects) per constraint
+    fn violated_bindings(
+        num_constraints: usize,
+        updates: &[MonitorUpdate],
+    ) -> Vec<HashSet<(String, Vec<String>)>> {
+        let mut ret = vec![HashSet::new(); num_constraints];
+        for u in updates {
+            if u.state == MonitorState::PermanentlyViolated {
+                ret[u.constraint].insert((u.source_event.clone(), u.each_objects.clone()));
+            }
+        }
+        ret
+    }
+
+    #[test]
+    fn agrees_with_offline_check() {
+        let constraints = parse_model(
+            "EF(place order, pay order, Each(order),1,1)\n\
+             DF(pay order, ship order, Each(order),1,*)\n\
+             EP(ship order, place order, Each(order),1,*)\n\
+             DP(ship order, pay order, Any(order),0,0)\n\
+             AS(<init> order, place order, Each(order),1,1)\n\
+             EF(place order, <exit> order, Each(order),1,1)\n\
+             EP(pay order, <exit> item, Any(order>item),1,*)\n\
+             DP(ship order, <exit> item, Each(order>item),1,1)\n\
+             EF(place order, ship order, Each(order>item),1,1)",
+        )
+        .unwrap();
+        let ocel = import_ocel_json_from_slice(TEST_OCEL_JSON.as_bytes()).unwrap();
+        let (_, updates) = monitor_ocel(&constraints, &ocel);
+
+        let locel = test_ocel();
+        let mut num_violations = 0;
+        for (i, constraint) in constraints.iter().enumerate() {
+            let expected: HashSet<String> = get_violation_report(constraint, &locel)
+                .violations
+                .into_iter()
+                .map(|v| v.source_event)
+                .collect();
+            let actual: HashSet<String> = updates
+                .iter()
+                .filter(|u| u.constraint == i && u.state == MonitorState::PermanentlyViolated)
+                .map(|u| u.source_event.clone())
+                .collect();
+            assert_eq!(actual, expected, "{}", constraint.as_template_string());
+            num_violations += expected.len();
+        }
+        assert!(num_violations > 0);
+        // The O2O constraint (last line) is activated by the orders with items
+        let o2o_activations: HashSet<&str> = updates
+            .iter()
+            .filter(|u| u.constraint == constraints.len() - 1)
+            .map(|u| u.source_event.as_str())
+            .collect();
+        assert_eq!(o2o_activations, HashSet::from(["e1", "e2"]));
+    }
+
+    #[test]
+    fn temporary_violations() {
+        let constraints = parse_model("EF(place order, pay order, Each(order),1,1)").unwrap();
+        let ocel = import_ocel_json_from_slice(TEST_OCEL_JSON.as_bytes()).unwrap();
+        let mut monitor = ConformanceMonitor::new(constraints);
+        for ob in &ocel.objects {
+            monitor.add_object(ob).unwrap();
+        }
+        let place = ocel
+            .events
+            .iter()
+            .find(|e| e.event_type == "place order")
+            .unwrap();
+        let updates = monitor.add_event(place).unwrap();
+        assert_eq!(updates.len(), 1);
+        assert!(updates
+            .iter()
+            .all(|u| u.state == MonitorState::TemporarilyViolated));
+        assert_eq!(
+            monitor.constraint_states(),
+            vec![MonitorState::TemporarilyViolated]
+        );
+        monitor.finish();
+        assert_eq!(
+            monitor.constraint_states(),
+            vec![MonitorState::PermanentlyViolated]
+        );
+        assert_eq!(monitor.add_event(place), Err(MonitorError::Finished));
+    }
+
+    #[test]
+    fn agrees_with_offline_check_on_generated_log() {
+        let arcs = generated_arcs(&["1,∞", "0,0", "1,1", "0,2"]);
+        let mut constraints: Vec<OCDeclareConstraint> = Vec::new();
+        for bounds in [
+            None,
+            Some(TimeBounds::new(None, Some(4 * 3_600_000))),
+            Some(TimeBounds::new(Some(2 * 3_600_000), Some(8 * 3_600_000))),
+        ] {
+            constraints.extend(arcs.iter().map(|arc| {
+                let constraint = OCDeclareConstraint::new(arc.clone());
+                match bounds {
+                    Some(bounds) => constraint.with_time_bounds(bounds),
+                    None => constraint,
+                }
+            }));
+        }
+        let ocel = generated_raw_ocel();
+        let (_, updates) = monitor_ocel(&constraints, &ocel);
+        let actual = violated_bindings(constraints.len(), &updates);
+        let locel = preprocess_ocel(ocel);
+        let mut num_violated = 0;
+        for (i, constraint) in constraints.iter().enumerate() {
+            let report = get_violation_report(constraint, &locel);
+            let expected: HashSet<(String, Vec<String>)> = report
+                .violations
+                .iter()
+                .flat_map(|v| {
+                    v.bindings.iter().map(|b| {
+                        let each = b.each_objects.iter().map(|o| o.object_id.clone());
+                        (v.source_event.clone(), each.collect())
+                    })
+                })
+                .collect();
+            let name = constraint.as_template_string();
+            assert_eq!(actual[i], expected, "{name}");
+            if !expected.is_empty() {
+                num_violated += 1;
+            }
+        }
+        assert!(num_violated > 100);
+    }
+
+    #[test]
+    fn equal_timestamps() {
+        let constraints = parse_model(
+            "EF(place order, pay order, Each(order),1,*)\n\
+             EP(pay order, place order, Each(order),1,*)\n\
+             AS(place order, pay order, Each(order),1,*)",
+        )
+        .unwrap();
+        let ocel: OCEL = serde_json::from_value(serde_json::json!({
+            "objectTypes": [{"name": "order", "attributes": []}],
+            "eventTypes": [],
+            "objects": [{"id": "o1", "type": "order", "attributes": [], "relationships": []}],
+            "events": [
+                {"id": "e1", "type": "place order", "time": "2024-01-01T00:00:00Z", "attributes": [],
+                 "relationships": [{"objectId": "o1", "qualifier": ""}]},
+                {"id": "e2", "type": "pay order", "time": "2024-01-01T00:00:00Z", "attributes": [],
+                 "relationships": [{"objectId": "o1", "qualifier": ""}]},
+            ],
+        }))
+        .unwrap();
+        let (monitor, _) = monitor_ocel(&constraints, &ocel);
+        // Events with the same timestamp are neither before nor after each other
+        assert_eq!(
+            monitor.constraint_states(),
+            vec![
+                MonitorState::PermanentlyViolated,
+                MonitorState::PermanentlyViolated,
+                MonitorState::PermanentlySatisfied
+            ]
+        );
+    }
+
+    #[test]
+    fn prunes_events() {
+        let ocel = generated_raw_ocel();
+        // Only `EF`/`DF` arcs: No event can be the target of a future source event
+        let constraints = parse_model(
+            "EF(place order, pay order, Each(order),1,*)\n\
+             DF(pay order, ship order, Each(order),1,*)",
+        )
+        .unwrap();
+        let (monitor, _) = monitor_ocel(&constraints, &ocel);
+        assert!(monitor.store.events.is_empty());
+        assert!(monitor
+            .monitors
+            .iter()
+            .all(|m| m.open.is_empty() && m.watched.is_empty() && m.unwatched.is_empty()));
+
+        // Only `pay order` events within the last 4 hours are kept
+        let mut constraints = parse_model("EP(ship order, pay order, Each(order),1,*)").unwrap();
+        constraints[0] = constraints[0]
+            .clone()
+            .with_time_bounds(TimeBounds::new(None, Some(4 * 3_600_000)));
+        let mut monitor = ConformanceMonitor::new(constraints);
+        for ob in &ocel.objects {
+            monitor.add_object(ob).unwrap();
+        }
+        let mut events = ocel.events.clone();
+        events.sort_by_key(|e| e.time);
+        for ev in &events {
+            monitor.add_event(ev).unwrap();
+            let cutoff = ev.time - Duration::hours(4);
+            assert!(monitor
+                .store
+                .events
+                .values()
+                .all(|e| e.activity == "pay order" && e.time >= cutoff));
+        }
+        assert!(!monitor.store.events.is_empty());
+        let num_kept: usize = monitor.store.objects.iter().map(|o| o.events.len()).sum();
+        assert!(num_kept < events.len());
+        // Recent events are still detected as duplicates
+        assert_eq!(
+            monitor.add_event(events.last().unwrap()),
+            Err(MonitorError::DuplicateEvent {
+                event: events.last().unwrap().id.clone()
+            })
+        );
+    }
+}
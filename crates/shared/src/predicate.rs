@@ -0,0 +1,152 @@
+use std::cmp::Ordering;
+
+use chrono::{DateTime, FixedOffset};
+use process_mining::ocel::{
+    linked_ocel::{index_linked_ocel::EventIndex, IndexLinkedOCEL, LinkedOCELAccess},
+    ocel_struct::{OCELAttributeValue, OCELEventAttribute},
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Predicate on the attributes of an event, restricting which events of an activity a node refers to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "type")]
+#[ts(export)]
+pub enum EventPredicate {
+    /// Compare the value of an event attribute to a constant
+    ///
+    /// If the event does not have the attribute or the value has a different type, the comparison is false.
+    Compare {
+        attribute: String,
+        op: ComparisonOp,
+        value: PredicateValue,
+    },
+    /// All predicates need to hold (true if empty)
+    And { predicates: Vec<EventPredicate> },
+    /// At least one predicate needs to hold (false if empty)
+    Or { predicates: Vec<EventPredicate> },
+    /// The predicate must not hold
+    Not { predicate: Box<EventPredicate> },
+}
+
+/// Comparison operator of an [`EventPredicate::Compare`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ComparisonOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Constant value of an [`EventPredicate::Compare`]
+///
+/// Numbers are compared to integer and float attributes, strings lexicographically and timestamps chronologically.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", content = "value")]
+#[ts(export)]
+pub enum PredicateValue {
+    Number(f64),
+    String(String),
+    Boolean(bool),
+    Time(#[ts(type = "string")] DateTime<FixedOffset>),
+}
+
+impl ComparisonOp {
+    /// Symbol of the operator in the textual model format
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ComparisonOp::Eq => "=",
+            ComparisonOp::Ne => "!=",
+            ComparisonOp::Lt => "<",
+            ComparisonOp::Le => "<=",
+            ComparisonOp::Gt => ">",
+            ComparisonOp::Ge => ">=",
+        }
+    }
+
+    fn holds(&self, ordering: Ordering) -> bool {
+        match self {
+            ComparisonOp::Eq => ordering.is_eq(),
+            ComparisonOp::Ne => ordering.is_ne(),
+            ComparisonOp::Lt => ordering.is_lt(),
+            ComparisonOp::Le => ordering.is_le(),
+            ComparisonOp::Gt => ordering.is_gt(),
+            ComparisonOp::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+impl PredicateValue {
+    /// Compare an attribute value to this value (`None` if the types are not comparable)
+    fn compare_attribute(&self, attribute_value: &OCELAttributeValue) -> Option<Ordering> {
+        match (attribute_value, self) {
+            (OCELAttributeValue::Integer(i), PredicateValue::Number(n)) => {
+                (*i as f64).partial_cmp(n)
+            }
+            (OCELAttributeValue::Float(f), PredicateValue::Number(n)) => f.partial_cmp(n),
+            (OCELAttributeValue::String(s), PredicateValue::String(v)) => Some(s.cmp(v)),
+            (OCELAttributeValue::Boolean(b), PredicateValue::Boolean(v)) => Some(b.cmp(v)),
+            (OCELAttributeValue::Time(t), PredicateValue::Time(v)) => Some(t.cmp(v)),
+            _ => None,
+        }
+    }
+}
+
+impl EventPredicate {
+    /// Check if the predicate holds for the attributes of an event
+    pub fn evaluate(&self, locel: &IndexLinkedOCEL, ev: &EventIndex) -> bool {
+        self.evaluate_attributes(&locel.get_ev(ev).attributes)
+    }
+
+    /// Check if the predicate holds for the given event attributes
+    pub fn evaluate_attributes(&self, attributes: &[OCELEventAttribute]) -> bool {
+        match self {
+            EventPredicate::Compare {
+                attribute,
+                op,
+                value,
+            } => attributes
+                .iter()
+                .find(|attr| &attr.name == attribute)
+                .and_then(|attr| value.compare_attribute(&attr.value))
+                .is_some_and(|ordering| op.holds(ordering)),
+            EventPredicate::And { predicates } => {
+                predicates.iter().all(|p| p.evaluate_attributes(attributes))
+            }
+            EventPredicate::Or { predicates } => {
+                predicates.iter().any(|p| p.evaluate_attributes(attributes))
+            }
+            EventPredicate::Not { predicate } => !predicate.evaluate_attributes(attributes),
+        }
+    }
+
+    /// Get the equivalent predicate without single-element conjunctions/disjunctions
+    /// (which are replaced by their element) and with the empty disjunction written as `Not` of the empty conjunction
+    ///
+    /// Predicates parsed from the textual model format are normalized (see [`crate::template::print_predicate`]).
+    pub fn normalized(self) -> Self {
+        match self {
+            EventPredicate::Compare { .. } => self,
+            EventPredicate::And { mut predicates } | EventPredicate::Or { mut predicates }
+                if predicates.len() == 1 =>
+            {
+                predicates.remove(0).normalized()
+            }
+            EventPredicate::Or { predicates } if predicates.is_empty() => EventPredicate::Not {
+                predicate: Box::new(EventPredicate::And { predicates: vec![] }),
+            },
+            EventPredicate::And { predicates } => EventPredicate::And {
+                predicates: predicates.into_iter().map(Self::normalized).collect(),
+            },
+            EventPredicate::Or { predicates } => EventPredicate::Or {
+                predicates: predicates.into_iter().map(Self::normalized).collect(),
+            },
+            EventPredicate::Not { predicate } => EventPredicate::Not {
+                predicate: Box::new(predicate.normalized()),
+            },
+        }
+    }
+}
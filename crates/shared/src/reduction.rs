@@ -1,98 +1,608 @@
-use crate::{OCDeclareArc, OCDeclareArcType};
-
-pub fn perform_transitive_reduction(
-    candidates: &Vec<OCDeclareArc>,
-) -> Vec<OCDeclareArc> {
-    let mut ret: Vec<OCDeclareArc> = candidates.clone();
-    for e1 in candidates {
-        for e2 in candidates {
-            if  e1.to == e2.from {
-                // So we have a1 -l1> b1 -l2> b2
-                // Remove all a1 -l3> b2, where l3 <= l1  and l3 <= l2
-                ret.retain(|e3| {
-                    let remove = e3.arc_type.is_dominated_by_or_eq(&e1.arc_type) && e3.arc_type.is_dominated_by_or_eq(&e2.arc_type) && e3.from == e1.from
-                        && e3.to == e2.to
-                        && (e3.label.is_dominated_by(&e1.label) && e3.label.is_dominated_by(&e2.label));
-                    !remove
-                })
-            }
+use std::collections::{HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    constraint::OCDeclareConstraint, OCDeclareArc, OCDeclareArcLabel, OCDeclareArcType,
+    OCDeclareNode, ObjectTypeAssociation,
+};
+
+/// Reduction applied to a set of arcs (e.g., after discovery)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ReductionMode {
+    /// Keep all arcs
+    #[default]
+    None,
+    /// Reduce using [`reduce_oc_arcs`]
+    OcArcs,
+    /// Reduce using [`perform_transitive_reduction`]
+    Transitive,
+    /// Reduce using [`reduce_by_entailment`]
+    Entailment,
+}
+
+impl ReductionMode {
+    /// Apply the reduction to the given arcs
+    pub fn apply(&self, arcs: Vec<OCDeclareArc>) -> Vec<OCDeclareArc> {
+        self.apply_explained(arcs).0
+    }
+
+    /// Apply the reduction to the given arcs, additionally returning the provenance of every removed arc
+    pub fn apply_explained(&self, arcs: Vec<OCDeclareArc>) -> (Vec<OCDeclareArc>, Vec<RemovedArc>) {
+        match self {
+            ReductionMode::None => (arcs, Vec::new()),
+            ReductionMode::OcArcs => reduce_oc_arcs_explained(&arcs),
+            ReductionMode::Transitive => perform_transitive_reduction_explained(&arcs),
+            ReductionMode::Entailment => reduce_by_entailment_explained(&arcs),
         }
     }
 
-    ret
-}
+    /// Apply the reduction to the plain arcs among the given constraints
+    ///
+    /// Constraints with additional restrictions (e.g., time bounds) are not implied by other arcs and are kept as is.
+    pub fn apply_to_constraints(
+        &self,
+        constraints: Vec<OCDeclareConstraint>,
+    ) -> Vec<OCDeclareConstraint> {
+        self.apply_to_constraints_explained(constraints).0
+    }
 
+    /// Apply the reduction to the plain arcs among the given constraints, additionally returning the provenance of every removed arc
+    pub fn apply_to_constraints_explained(
+        &self,
+        constraints: Vec<OCDeclareConstraint>,
+    ) -> (Vec<OCDeclareConstraint>, Vec<RemovedArc>) {
+        let (plain, mut restricted): (Vec<_>, Vec<_>) =
+            constraints.into_iter().partition(|c| c.is_plain_arc());
+        let (reduced, removed) = self.apply_explained(plain.into_iter().map(|c| c.arc).collect());
+        let mut ret: Vec<OCDeclareConstraint> =
+            reduced.into_iter().map(OCDeclareConstraint::new).collect();
+        ret.append(&mut restricted);
+        (ret, removed)
+    }
+}
 
-pub fn reduce_oc_arcs(arcs: &Vec<OCDeclareArc>) -> Vec<OCDeclareArc> {
-    let mut ret = arcs.clone();
+/// Rule by which a reduction removed an arc `a -> c` because of the arcs `a -> b` and `b -> c`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum ReductionRule {
+    /// The arc type and label are dominated by both arcs (see [`perform_transitive_reduction`])
+    DominatedByPair,
+    /// The arc type and label are dominated by both arcs, and its ANY associations are not
+    /// ANY associations of the relevant arc (see [`reduce_oc_arcs`])
+    StrictlyDominatedByPair,
+    /// Two `EF`/`DF` arcs imply an `EF` arc
+    FollowsChain,
+    /// Two `EP`/`DP` arcs imply an `EP` arc
+    PrecedesChain,
+    /// Two arcs of any type imply an `AS` arc
+    AssociationChain,
+}
 
-    for a in arcs {
+/// Provenance of an arc removed by a reduction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct RemovedArc {
+    /// The removed arc (`a -> c`)
+    pub arc: OCDeclareArc,
+    /// The implying arc starting at the source activity of the removed arc (`a -> b`)
+    pub a: OCDeclareArc,
+    /// The implying arc ending at the target activity of the removed arc (`b -> c`)
+    pub b: OCDeclareArc,
+    pub rule: ReductionRule,
+    /// Human-readable explanation (e.g., "implied by EF(a, b, ...) and EF(b, c, ...)" or "dominated by EF(a, b, ...) and EF(b, c, ...)")
+    pub explanation: String,
+}
 
-        //         ret.retain(|b| {
+/// Justification why an arc is implied by a set of arcs (see [`entails`])
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Entailment {
+    /// Indices of the implying arcs, forming a chain from the source to the target activity of the implied arc
+    ///
+    /// Contains a single arc if the implied arc is dominated by it, and is empty if the implied arc holds trivially.
+    pub chain: Vec<usize>,
+}
 
-        //     let remove = a.from == b.to && a.to == b.from && a.arc_type != b.arc_type  && b.label.is_dominated_by(&a.label) && !a.label.is_dominated_by(&b.label);
+impl Entailment {
+    /// Human-readable explanation, given the arcs the indices of the chain refer to
+    pub fn explain(&self, arcs: &[OCDeclareArc]) -> String {
+        let chain: Vec<String> = self
+            .chain
+            .iter()
+            .map(|i| arcs[*i].as_template_string())
+            .collect();
+        match chain.as_slice() {
+            [] => "trivially satisfied (no count bounds)".to_string(),
+            [arc] => format!("implied by {arc}"),
+            [init @ .., last] => format!("implied by {} and {last}", init.join(", ")),
+        }
+    }
+}
 
-        //     !remove
+/// Check if every target event of `strong` is also a target event of `weak` (for the same source event),
+/// such that the counts of `weak` are at least the counts of `strong`
+fn is_dominated(weak: &OCDeclareArc, strong: &OCDeclareArc) -> bool {
+    weak.from == strong.from
+        && weak.to == strong.to
+        && weak.arc_type.is_dominated_by_or_eq(&strong.arc_type)
+        && weak.label.is_dominated_by(&strong.label)
+}
 
-        // });
+/// Check if `strong` implies `weak` on its own
+fn implies_directly(strong: &OCDeclareArc, weak: &OCDeclareArc) -> bool {
+    if !is_dominated(weak, strong) {
+        return false;
+    }
+    let min_implied = weak.counts.0.unwrap_or(0) <= strong.counts.0.unwrap_or(0);
+    // Upper bounds are only implied by arcs with exactly the same targets
+    let max_implied = match (weak.counts.1, strong.counts.1) {
+        (None, _) => true,
+        (Some(weak_max), Some(strong_max)) => strong_max <= weak_max && is_dominated(strong, weak),
+        (Some(_), None) => false,
+    };
+    min_implied && max_implied
+}
 
-        //         ret.retain(|b| {
+/// Check if the counts of `arc` can be implied by a chain (which only implies the existence of a target event)
+fn is_implied_by_chains(arc: &OCDeclareArc) -> bool {
+    arc.counts.0.unwrap_or(0) <= 1 && arc.counts.1.is_none()
+}
 
-        //     let remove = a.from == b.to && a.to == b.from && a.arc_type != b.arc_type && a.arc_type == OCDeclareArcType::EF  && b.label.is_dominated_by(&a.label);
+/// Check if `step` can be part of a chain implying `arc` (ignoring the label)
+fn is_chain_step(step: &OCDeclareArc, arc: &OCDeclareArc) -> bool {
+    let type_implied = match arc.arc_type {
+        OCDeclareArcType::ASS => true,
+        OCDeclareArcType::EF => {
+            matches!(step.arc_type, OCDeclareArcType::EF | OCDeclareArcType::DF)
+        }
+        OCDeclareArcType::EP => {
+            matches!(step.arc_type, OCDeclareArcType::EP | OCDeclareArcType::DP)
+        }
+        // Chains of directly-follows relations do not imply a directly-follows relation
+        OCDeclareArcType::DF | OCDeclareArcType::DP => false,
+    };
+    type_implied && step.from != step.to && step.counts.0.is_some_and(|min| min >= 1)
+}
 
-        //     !remove
+/// How an object type association of a label binds the objects of the source event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AssociationKind {
+    Each,
+    All,
+    Any,
+}
 
-        // });
+/// Check if the target events of `step` are guaranteed to involve the objects of an association of the source event of the chain
+///
+/// The first step of a chain can also guarantee ANY associations through its ANY associations.
+/// Later steps need to carry the objects (of the target object type of the association) through EACH or ALL.
+fn carries(
+    step: &OCDeclareArcLabel,
+    assoc: &ObjectTypeAssociation,
+    kind: AssociationKind,
+    first: bool,
+) -> bool {
+    let carried = match assoc {
+        ObjectTypeAssociation::O2O { second, .. } if !first => {
+            ObjectTypeAssociation::new_simple(second)
+        }
+        _ => assoc.clone(),
+    };
+    let in_all = step.all.contains(&carried);
+    let in_each = step.each.contains(&carried);
+    match kind {
+        AssociationKind::All => in_all,
+        AssociationKind::Each => in_all || in_each,
+        AssociationKind::Any => in_all || in_each || (first && step.any.contains(&carried)),
+    }
+}
 
+/// Check if `step` carries all associations of the label of the implied arc
+fn carries_label(step: &OCDeclareArcLabel, label: &OCDeclareArcLabel, first: bool) -> bool {
+    [
+        (&label.each, AssociationKind::Each),
+        (&label.all, AssociationKind::All),
+        (&label.any, AssociationKind::Any),
+    ]
+    .into_iter()
+    .all(|(assocs, kind)| assocs.iter().all(|a| carries(step, a, kind, first)))
+}
 
-        for b in arcs {
-            if a.from != a.to && b.from == a.to && a.from != b.to {
+/// Find the shortest chain of at least two usable arcs implying `arc`
+///
+/// Every step of the chain requires at least one target event (of the right direction),
+/// which is again a source event of the next step and involves the objects bound by the label of `arc`.
+fn find_chain(
+    arcs: &[OCDeclareArc],
+    arc: &OCDeclareArc,
+    usable: &dyn Fn(usize) -> bool,
+    max_len: Option<usize>,
+) -> Option<Vec<usize>> {
+    let steps: Vec<usize> = (0..arcs.len())
+        .filter(|i| usable(*i) && is_chain_step(&arcs[*i], arc))
+        .collect();
+    // Activity reached after the first step, with the chain leading to it
+    let mut queue: VecDeque<(&OCDeclareNode, Vec<usize>)> = steps
+        .iter()
+        .filter(|i| arcs[**i].from == arc.from && carries_label(&arcs[**i].label, &arc.label, true))
+        .map(|i| (&arcs[*i].to, vec![*i]))
+        .collect();
+    let mut visited: HashSet<&OCDeclareNode> = queue.iter().map(|(act, _)| *act).collect();
+    visited.insert(&arc.from);
+    visited.remove(&arc.to);
+    while let Some((act, chain)) = queue.pop_front() {
+        if act == &arc.to || max_len.is_some_and(|max| chain.len() >= max) {
+            continue;
+        }
+        for i in &steps {
+            let step = &arcs[*i];
+            if &step.from != act || !carries_label(&step.label, &arc.label, false) {
+                continue;
+            }
+            if step.to == arc.to {
+                let mut chain = chain.clone();
+                chain.push(*i);
+                return Some(chain);
+            }
+            if visited.insert(&step.to) {
+                let mut chain = chain.clone();
+                chain.push(*i);
+                queue.push_back((&step.to, chain));
+            }
+        }
+    }
+    None
+}
 
-                ret.retain(|c| {
-                    let remove = c.from == a.from
-                        && c.to == b.to
-                        && c.arc_type.is_dominated_by_or_eq(&a.arc_type) && c.arc_type.is_dominated_by_or_eq(&b.arc_type)
-                        && (c.label.is_dominated_by(&a.label) && c.label.is_dominated_by(&b.label));
+/// Check if `arc` is implied by the usable arcs (using chains of at most `max_len` arcs)
+fn find_entailment(
+    arcs: &[OCDeclareArc],
+    arc: &OCDeclareArc,
+    usable: &dyn Fn(usize) -> bool,
+    max_len: Option<usize>,
+) -> Option<Entailment> {
+    if arc.counts.0.unwrap_or(0) == 0 && arc.counts.1.is_none() {
+        return Some(Entailment { chain: Vec::new() });
+    }
+    if let Some(i) = (0..arcs.len()).find(|i| usable(*i) && implies_directly(&arcs[*i], arc)) {
+        return Some(Entailment { chain: vec![i] });
+    }
+    if !is_implied_by_chains(arc) {
+        return None;
+    }
+    find_chain(arcs, arc, usable, max_len).map(|chain| Entailment { chain })
+}
 
-                    let is_strictly_dominated = c.label.any.iter().all(|any_label| {
-                        let x = if c.arc_type == OCDeclareArcType::EF {
-                            !b.label.any.iter().any(|l| l == any_label)
-                        } else {
-                            !a.label.any.iter().any(|l| l == any_label)
-                        };
+/// Check if the arcs of a model imply `arc`, i.e., every log satisfying all arcs of the model also satisfies `arc`
+///
+/// Returns the shortest justification found: `arc` holds trivially, is dominated by a single arc of the model
+/// (a dominating arc type and label, and a stricter lower count bound), or is implied by a chain of arcs
+/// (e.g., `EF(a, b, Each(o),1,∞)` and `EF(b, c, Each(o),1,∞)` imply `EF(a, c, Each(o),1,∞)`).
+/// The check is sound, but not complete: `None` does not guarantee that the model does not imply `arc`.
+pub fn entails(model: &[OCDeclareArc], arc: &OCDeclareArc) -> Option<Entailment> {
+    find_entailment(model, arc, &|_| true, None)
+}
 
-                        x
-                    });
+/// Remove arcs implied by a chain of two other (remaining) arcs, returning the indices of the kept arcs
+/// and the justification for each removed arc
+///
+/// Arcs are considered in order, such that the remaining arcs still imply all removed arcs.
+fn reduce_by_chains(arcs: &[OCDeclareArc]) -> (Vec<usize>, Vec<(usize, Entailment)>) {
+    let mut kept = vec![true; arcs.len()];
+    let mut removed = Vec::new();
+    for (i, arc) in arcs.iter().enumerate() {
+        if !is_implied_by_chains(arc) {
+            continue;
+        }
+        let usable = |j: usize| j != i && kept[j];
+        if let Some(chain) = find_chain(arcs, arc, &usable, Some(2)) {
+            kept[i] = false;
+            removed.push((i, Entailment { chain }));
+        }
+    }
+    ((0..arcs.len()).filter(|i| kept[*i]).collect(), removed)
+}
 
+/// Remove arcs implied by a chain of two other (remaining) arcs (`a -> b -> c` implying `a -> c`, see [`entails`])
+///
+/// Unlike [`reduce_oc_arcs`] and [`perform_transitive_reduction`], every removed arc is guaranteed to be implied by the remaining arcs.
+/// As a chain only implies the existence of a target event, arcs with an upper count bound
+/// or a lower count bound above one are never removed.
+pub fn reduce_by_entailment(arcs: &[OCDeclareArc]) -> Vec<OCDeclareArc> {
+    reduce_by_entailment_explained(arcs).0
+}
 
-                    !remove || !is_strictly_dominated
-                })
+/// Like [`reduce_by_entailment`], additionally returning the provenance of every removed arc
+pub fn reduce_by_entailment_explained(
+    arcs: &[OCDeclareArc],
+) -> (Vec<OCDeclareArc>, Vec<RemovedArc>) {
+    let (kept, removed) = reduce_by_chains(arcs);
+    let removed = removed
+        .into_iter()
+        .map(|(i, entailment)| {
+            let arc = &arcs[i];
+            RemovedArc {
+                arc: arc.clone(),
+                a: arcs[entailment.chain[0]].clone(),
+                b: arcs[entailment.chain[1]].clone(),
+                rule: match arc.arc_type {
+                    OCDeclareArcType::EF => ReductionRule::FollowsChain,
+                    OCDeclareArcType::EP => ReductionRule::PrecedesChain,
+                    _ => ReductionRule::AssociationChain,
+                },
+                explanation: entailment.explain(arcs),
             }
+        })
+        .collect();
+    (kept.into_iter().map(|i| arcs[i].clone()).collect(), removed)
+}
+
+/// Remove arcs based on pairs of arcs `a -> b`, `b -> c`, keeping the arcs not matched by `removes`
+///
+/// For every removed arc, the provenance references the first pair (in the order of the arcs) removing it.
+/// As in the original heuristics, the pairs are taken from all arcs (including removed ones).
+fn reduce_by_pairs(
+    arcs: &[OCDeclareArc],
+    rule: ReductionRule,
+    removes: impl Fn(&OCDeclareArc, &OCDeclareArc, &OCDeclareArc) -> bool,
+) -> (Vec<OCDeclareArc>, Vec<RemovedArc>) {
+    let mut kept = Vec::new();
+    let mut removed = Vec::new();
+    for c in arcs {
+        let pair = arcs
+            .iter()
+            .flat_map(|a| arcs.iter().map(move |b| (a, b)))
+            .find(|(a, b)| removes(a, b, c));
+        match pair {
+            Some((a, b)) => removed.push(RemovedArc {
+                arc: c.clone(),
+                a: a.clone(),
+                b: b.clone(),
+                rule,
+                explanation: format!(
+                    "dominated by {} and {}",
+                    a.as_template_string(),
+                    b.as_template_string()
+                ),
+            }),
+            None => kept.push(c.clone()),
         }
     }
+    (kept, removed)
+}
+
+/// Remove all arcs `a -> c` whose arc type and label are dominated by both arcs `a -> b` and `b -> c` of another pair of arcs
+pub fn perform_transitive_reduction(candidates: &[OCDeclareArc]) -> Vec<OCDeclareArc> {
+    perform_transitive_reduction_explained(candidates).0
+}
+
+/// Like [`perform_transitive_reduction`], additionally returning the provenance of every removed arc
+pub fn perform_transitive_reduction_explained(
+    candidates: &[OCDeclareArc],
+) -> (Vec<OCDeclareArc>, Vec<RemovedArc>) {
+    reduce_by_pairs(candidates, ReductionRule::DominatedByPair, |e1, e2, e3| {
+        // So we have a1 -l1> b1 -l2> b2
+        // Remove all a1 -l3> b2, where l3 <= l1  and l3 <= l2
+        e1.to == e2.from
+            && e3.arc_type.is_dominated_by_or_eq(&e1.arc_type)
+            && e3.arc_type.is_dominated_by_or_eq(&e2.arc_type)
+            && e3.from == e1.from
+            && e3.to == e2.to
+            && (e3.label.is_dominated_by(&e1.label) && e3.label.is_dominated_by(&e2.label))
+    })
+}
 
-    ret
+/// Remove all arcs `a -> c` (with `a != c`) whose arc type and label are dominated by both arcs `a -> b` and `b -> c`
+/// of another pair of arcs (with `a != b`), unless one of its ANY associations is an ANY association
+/// of `b -> c` (for `EF`) or `a -> b` (otherwise)
+pub fn reduce_oc_arcs(arcs: &[OCDeclareArc]) -> Vec<OCDeclareArc> {
+    reduce_oc_arcs_explained(arcs).0
 }
 
+/// Like [`reduce_oc_arcs`], additionally returning the provenance of every removed arc
+pub fn reduce_oc_arcs_explained(arcs: &[OCDeclareArc]) -> (Vec<OCDeclareArc>, Vec<RemovedArc>) {
+    reduce_by_pairs(arcs, ReductionRule::StrictlyDominatedByPair, |a, b, c| {
+        if a.from == a.to || b.from != a.to || a.from == b.to {
+            return false;
+        }
+        let remove = c.from == a.from
+            && c.to == b.to
+            && c.arc_type.is_dominated_by_or_eq(&a.arc_type)
+            && c.arc_type.is_dominated_by_or_eq(&b.arc_type)
+            && (c.label.is_dominated_by(&a.label) && c.label.is_dominated_by(&b.label));
+        let is_strictly_dominated = c.label.any.iter().all(|any_label| {
+            if c.arc_type == OCDeclareArcType::EF {
+                !b.label.any.iter().any(|l| l == any_label)
+            } else {
+                !a.label.any.iter().any(|l| l == any_label)
+            }
+        });
+        remove && is_strictly_dominated
+    })
+}
 
 #[cfg(test)]
-mod tests
-{
-    use std::fs::File;
+mod tests {
+    use process_mining::object_centric::oc_declare::{O2OMode, OCDeclareDiscoveryOptions};
 
-    use process_mining::{import_ocel_json_from_path, object_centric::oc_declare::OCDeclareDiscoveryOptions};
+    use crate::{
+        discover_behavior_constraints,
+        reduction::{
+            entails, perform_transitive_reduction, reduce_by_entailment,
+            reduce_by_entailment_explained, reduce_oc_arcs, reduce_oc_arcs_explained,
+            ReductionRule,
+        },
+        template::parse_model,
+        test_utils::test_ocel,
+        OCDeclareArc, OCDeclareArcType,
+    };
 
-    use crate::{discover_behavior_constraints, preprocess_ocel, reduction::reduce_oc_arcs};
+    fn parse_arcs(input: &str) -> Vec<OCDeclareArc> {
+        parse_model(input)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.arc)
+            .collect()
+    }
 
+    /// Original implementation of [`perform_transitive_reduction`] (as published)
+    fn published_transitive_reduction(candidates: &[OCDeclareArc]) -> Vec<OCDeclareArc> {
+        let mut ret = candidates.to_vec();
+        for e1 in candidates {
+            for e2 in candidates {
+                if e1.to == e2.from {
+                    ret.retain(|e3| {
+                        let remove = e3.arc_type.is_dominated_by_or_eq(&e1.arc_type)
+                            && e3.arc_type.is_dominated_by_or_eq(&e2.arc_type)
+                            && e3.from == e1.from
+                            && e3.to == e2.to
+                            && (e3.label.is_dominated_by(&e1.label)
+                                && e3.label.is_dominated_by(&e2.label));
+                        !remove
+                    })
+                }
+            }
+        }
+        ret
+    }
+
+    /// Original implementation of [`reduce_oc_arcs`] (as published)
+    fn published_reduce_oc_arcs(arcs: &[OCDeclareArc]) -> Vec<OCDeclareArc> {
+        let mut ret = arcs.to_vec();
+        for a in arcs {
+            for b in arcs {
+                if a.from != a.to && b.from == a.to && a.from != b.to {
+                    ret.retain(|c| {
+                        let remove = c.from == a.from
+                            && c.to == b.to
+                            && c.arc_type.is_dominated_by_or_eq(&a.arc_type)
+                            && c.arc_type.is_dominated_by_or_eq(&b.arc_type)
+                            && (c.label.is_dominated_by(&a.label)
+                                && c.label.is_dominated_by(&b.label));
+                        let is_strictly_dominated = c.label.any.iter().all(|any_label| {
+                            if c.arc_type == OCDeclareArcType::EF {
+                                !b.label.any.iter().any(|l| l == any_label)
+                            } else {
+                                !a.label.any.iter().any(|l| l == any_label)
+                            }
+                        });
+                        !remove || !is_strictly_dominated
+                    })
+                }
+            }
+        }
+        ret
+    }
 
-    
     #[test]
-    fn transitive_reduction(){
-        let ocel = import_ocel_json_from_path("/home/aarkue/dow/ocel/bpic2017-o2o-workflow-qualifier-index-no-ev-attrs-sm.json").unwrap();
-        let locel = preprocess_ocel(ocel);
-        let res = discover_behavior_constraints(&locel, OCDeclareDiscoveryOptions::default());
-        let filtered_res = reduce_oc_arcs(&res);
-        serde_json::to_writer(File::create("bpic2017-reduced-oc-DECLARE.json").unwrap(), &filtered_res).unwrap();
-        println!("Reduced {} to {}",res.len(),filtered_res.len());
-    }
-}
\ No newline at end of file
+    fn transitive_reduction() {
+        let locel = test_ocel();
+        for o2o_mode in [O2OMode::None, O2OMode::Direct, O2OMode::Bidirectional] {
+            let options = OCDeclareDiscoveryOptions {
+                o2o_mode,
+                ..Default::default()
+            };
+            let res = discover_behavior_constraints(&locel, options);
+            assert_eq!(reduce_oc_arcs(&res), published_reduce_oc_arcs(&res));
+            assert_eq!(
+                perform_transitive_reduction(&res),
+                published_transitive_reduction(&res)
+            );
+            let (reduced, removed) = reduce_by_entailment_explained(&res);
+            assert_eq!(reduced.len() + removed.len(), res.len());
+            // The remaining arcs still imply every arc removed based on entailment
+            for r in &removed {
+                assert!(entails(&reduced, &r.arc).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn published_reductions() {
+        let arcs = parse_arcs(
+            "EF(place order, pay order, Each(order),1,*)\n\
+             EF(pay order, ship order, Each(order),1,*)\n\
+             EF(place order, ship order, Each(order),1,1)\n\
+             EF(place order, ship order, Any(order),1,*)",
+        );
+        // The published reductions also remove arcs with an upper count bound
+        assert_eq!(perform_transitive_reduction(&arcs), arcs[..2].to_vec());
+        let (reduced, removed) = reduce_oc_arcs_explained(&arcs);
+        assert_eq!(reduced, arcs[..2].to_vec());
+        assert_eq!(removed.len(), 2);
+        assert_eq!((&removed[0].a, &removed[0].b), (&arcs[0], &arcs[1]));
+        assert_eq!(removed[0].rule, ReductionRule::StrictlyDominatedByPair);
+        assert!(removed[0]
+            .explanation
+            .starts_with("dominated by EF(place order, pay order"));
+
+        // ANY associations shared with the relevant arc are not strictly dominated
+        let arcs = parse_arcs(
+            "EF(place order, pay order, Each(order),1,*)\n\
+             EF(pay order, ship order, Any(order),1,*)\n\
+             EF(place order, ship order, Any(order),1,*)",
+        );
+        assert_eq!(reduce_oc_arcs(&arcs), arcs);
+        assert_eq!(perform_transitive_reduction(&arcs), arcs[..2].to_vec());
+    }
+
+    #[test]
+    fn entailment() {
+        let model = parse_arcs(
+            "EF(place order, pay order, Each(order),1,*)\n\
+             EF(pay order, ship order, Each(order),1,*)\n\
+             DF(place order, pay order, Each(order),2,*)\n\
+             EF(ship order, archive order, Any(order),1,*)",
+        );
+        let chain = |arc: &str| entails(&model, &parse_arcs(arc)[0]).map(|e| e.chain);
+        assert_eq!(
+            chain("EF(place order, ship order, Each(order),1,*)"),
+            Some(vec![0, 1])
+        );
+        assert_eq!(
+            chain("EF(place order, pay order, Any(order),1,*)"),
+            Some(vec![0])
+        );
+        assert_eq!(
+            chain("EF(place order, pay order, Each(order),2,*)"),
+            Some(vec![2])
+        );
+        assert_eq!(
+            chain("EF(place order, archive order, Each(order),0,*)"),
+            Some(vec![])
+        );
+        // Upper bounds are not implied by chains
+        assert_eq!(chain("EF(place order, ship order, Each(order),1,1)"), None);
+        // The ANY association of the last step does not carry the orders of the first event
+        assert_eq!(
+            chain("EF(place order, archive order, Any(order),1,*)"),
+            None
+        );
+        // DF relations are not transitive
+        assert_eq!(chain("DF(place order, ship order, Each(order),1,*)"), None);
+
+        let arcs = parse_arcs(
+            "EF(place order, pay order, Each(order),1,*)\n\
+             EF(pay order, ship order, Each(order),1,*)\n\
+             EF(place order, ship order, Each(order),1,*)",
+        );
+        let (reduced, removed) = reduce_by_entailment_explained(&arcs);
+        assert_eq!(reduced, arcs[..2].to_vec());
+        assert_eq!(removed.len(), 1);
+        assert_eq!((&removed[0].a, &removed[0].b), (&arcs[0], &arcs[1]));
+        assert_eq!(removed[0].rule, ReductionRule::FollowsChain);
+        let explanation = entails(&arcs[..2], &arcs[2]).unwrap().explain(&arcs);
+        assert!(explanation.starts_with("implied by EF(place order, pay order"));
+
+        // Arcs with an upper count bound are not implied by chains and are always kept
+        let arcs = parse_arcs(
+            "EF(place order, pay order, Each(order),1,*)\n\
+             EF(pay order, ship order, Each(order),1,*)\n\
+             EF(place order, ship order, Each(order),1,1)",
+        );
+        assert_eq!(reduce_by_entailment(&arcs), arcs);
+    }
+}
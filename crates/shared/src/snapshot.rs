@@ -0,0 +1,141 @@
+//! Binary snapshots of pre-processed OCEL
+//!
+//! A snapshot stores the already pre-processed OCEL (i.e., including the added init/exit events),
+//! so that loading it only requires re-building the index structures instead of re-running [`crate::preprocess_ocel`].
+//!
+//! Format: The magic bytes [`SNAPSHOT_MAGIC`], followed by a single version byte and the MessagePack-encoded OCEL.
+use std::fmt::Display;
+
+use process_mining::ocel::{
+    linked_ocel::{IndexLinkedOCEL, LinkedOCELAccess},
+    ocel_struct::{OCELType, OCEL},
+};
+
+/// Magic bytes at the start of every snapshot
+pub const SNAPSHOT_MAGIC: &[u8; 7] = b"OCDSNAP";
+/// Current version of the snapshot format
+pub const SNAPSHOT_VERSION: u8 = 1;
+
+/// Error reading a snapshot
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The data does not start with [`SNAPSHOT_MAGIC`]
+    NotASnapshot,
+    /// The snapshot was written in an unsupported version of the format
+    UnsupportedVersion(u8),
+    /// The payload could not be decoded
+    Decode(rmp_serde::decode::Error),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::NotASnapshot => write!(f, "Data is not an OC-DECLARE OCEL snapshot"),
+            SnapshotError::UnsupportedVersion(v) => write!(
+                f,
+                "Unsupported snapshot version {v} (expected {SNAPSHOT_VERSION})"
+            ),
+            SnapshotError::Decode(e) => write!(f, "Invalid snapshot data: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<rmp_serde::decode::Error> for SnapshotError {
+    fn from(value: rmp_serde::decode::Error) -> Self {
+        SnapshotError::Decode(value)
+    }
+}
+
+/// Reconstruct the (pre-processed) OCEL underlying the linked OCEL
+///
+/// Events and objects are kept in their index order.
+/// Attribute declarations of event and object types are not retained.
+fn linked_to_ocel(locel: &IndexLinkedOCEL) -> OCEL {
+    let to_type = |name: &str| OCELType {
+        name: name.to_string(),
+        attributes: Vec::new(),
+    };
+    let mut evs: Vec<_> = locel
+        .get_ev_types()
+        .flat_map(|et| locel.get_evs_of_type(et))
+        .collect();
+    evs.sort();
+    let mut obs: Vec<_> = locel
+        .get_ob_types()
+        .flat_map(|ot| locel.get_obs_of_type(ot))
+        .collect();
+    obs.sort();
+    OCEL {
+        event_types: locel.get_ev_types().map(to_type).collect(),
+        object_types: locel.get_ob_types().map(to_type).collect(),
+        events: evs.into_iter().map(|e| locel.get_ev(e).clone()).collect(),
+        objects: obs.into_iter().map(|o| locel.get_ob(o).clone()).collect(),
+    }
+}
+
+/// Write a binary snapshot of a pre-processed OCEL
+pub fn write_snapshot(locel: &IndexLinkedOCEL) -> Vec<u8> {
+    let mut ret = SNAPSHOT_MAGIC.to_vec();
+    ret.push(SNAPSHOT_VERSION);
+    rmp_serde::encode::write_named(&mut ret, &linked_to_ocel(locel))
+        .expect("Serializing OCEL to a Vec should not fail");
+    ret
+}
+
+/// Read a binary snapshot written by [`write_snapshot`]
+pub fn read_snapshot(data: &[u8]) -> Result<IndexLinkedOCEL, SnapshotError> {
+    let payload = data
+        .strip_prefix(SNAPSHOT_MAGIC.as_slice())
+        .ok_or(SnapshotError::NotASnapshot)?;
+    match payload.split_first() {
+        Some((&SNAPSHOT_VERSION, payload)) => {
+            let ocel: OCEL = rmp_serde::from_slice(payload)?;
+            Ok(IndexLinkedOCEL::from_ocel(ocel))
+        }
+        Some((&version, _)) => Err(SnapshotError::UnsupportedVersion(version)),
+        None => Err(SnapshotError::NotASnapshot),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        diagnostics::get_violation_report, discovery::discover_with_provenance,
+        test_utils::test_ocel,
+    };
+
+    use super::*;
+
+    #[test]
+    fn snapshot_round_trip() {
+        let locel = test_ocel();
+        let data = write_snapshot(&locel);
+        let reloaded = read_snapshot(&data).unwrap();
+        assert_eq!(
+            reloaded.get_ev_types().collect::<Vec<_>>(),
+            locel.get_ev_types().collect::<Vec<_>>()
+        );
+        let constraints = discover_with_provenance(&locel, &Default::default()).constraints;
+        assert!(!constraints.is_empty());
+        for c in constraints {
+            let (expected, actual) = (
+                get_violation_report(&c, &locel),
+                get_violation_report(&c, &reloaded),
+            );
+            assert_eq!(expected.num_source_events, actual.num_source_events);
+            assert_eq!(expected.num_violating_events, actual.num_violating_events);
+        }
+        assert!(matches!(
+            read_snapshot(b"not a snapshot"),
+            Err(SnapshotError::NotASnapshot)
+        ));
+        let mut data = data;
+        data[SNAPSHOT_MAGIC.len()] = SNAPSHOT_VERSION + 1;
+        assert!(matches!(
+            read_snapshot(&data),
+            Err(SnapshotError::UnsupportedVersion(_))
+        ));
+    }
+}
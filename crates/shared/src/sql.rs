@@ -0,0 +1,463 @@
+//! Translation of OC-DECLARE arcs to SQL queries on the OCEL 2.0 SQLite schema
+//!
+//! The generated query returns the IDs (column `ocel_id`) of all source events violating the arc,
+//! following the semantics of `OCDeclareArc::get_for_all_evs_perf` (see [`crate::diagnostics`]).
+//! It only uses the standard tables of the schema (`event`/`object`, the per-type `event_<type>` tables for the
+//! timestamps, `event_object` and `object_object`), such that arcs can be checked directly inside an OCEL store.
+//! Intermediate results are `MATERIALIZED` common table expressions, which requires SQLite 3.35 or newer.
+//!
+//! As the names of the per-type event tables are not fixed by the schema, the query is generated for a mapping from
+//! event types to their table name suffix (the `ocel_type_map` column, see [`EVENT_TYPE_MAP_QUERY`]).
+//!
+//! The `<init>`/`<exit>` events added by `preprocess_ocel` are not stored in the database. Instead, they are derived
+//! from the first/last event of every object (with IDs `<init>_<object id>`/`<exit>_<object id>`).
+//! Target events are compared to the source event by their timestamp only (i.e., events with the same timestamp are
+//! neither before nor after each other). If several events with the same timestamp are nearest for `DF`/`DP`,
+//! `<init>` events come first, then the events ordered by ID, and `<exit>` events last. `process_mining` instead
+//! uses the first of them it encounters, such that the results can differ for such ties.
+use std::collections::HashMap;
+
+use crate::{
+    OCDeclareArc, OCDeclareArcType, ObjectTypeAssociation, EXIT_EVENT_PREFIX, INIT_EVENT_PREFIX,
+};
+
+/// Query for the mapping from event types to the suffix of their `event_<type>` table
+pub const EVENT_TYPE_MAP_QUERY: &str = "SELECT ocel_type, ocel_type_map FROM event_map_type";
+
+/// Quote a string literal
+fn sql_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+/// Quote an identifier
+fn sql_identifier(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+/// Objects bound through one object type association: Rows `(src, kind, ob)` for all source events
+fn bound_objects_query(assoc: &ObjectTypeAssociation, kind: &str) -> String {
+    match assoc {
+        ObjectTypeAssociation::Simple { object_type } => format!(
+            "SELECT x.ev, {}, x.ob FROM e2o x JOIN src s ON s.id = x.ev JOIN object o ON o.ocel_id = x.ob WHERE o.ocel_type = {}",
+            sql_string(kind),
+            sql_string(object_type)
+        ),
+        ObjectTypeAssociation::O2O {
+            first,
+            second,
+            reversed,
+        } => {
+            let (from_col, to_col) = if *reversed {
+                ("ocel_target_id", "ocel_source_id")
+            } else {
+                ("ocel_source_id", "ocel_target_id")
+            };
+            format!(
+                "SELECT x.ev, {}, oo.{to_col} FROM e2o x JOIN src s ON s.id = x.ev JOIN object o1 ON o1.ocel_id = x.ob \
+                 JOIN object_object oo ON oo.{from_col} = x.ob JOIN object o2 ON o2.ocel_id = oo.{to_col} \
+                 WHERE o1.ocel_type = {} AND o2.ocel_type = {}",
+                sql_string(kind),
+                sql_string(first),
+                sql_string(second)
+            )
+        }
+    }
+}
+
+/// Kind of the objects bound through the `i`-th association of a label part (e.g., `each0`)
+fn bound_kind(part: &str, i: usize) -> String {
+    format!("{part}{i}")
+}
+
+/// Kind of the association of a label part with the most objects of its (target) object type
+/// (ties are broken by the order in the label), which is used to look up target candidates
+fn first_association_query(part: &str, assocs: &[ObjectTypeAssociation]) -> String {
+    let associations: Vec<String> = assocs
+        .iter()
+        .enumerate()
+        .map(|(i, assoc)| {
+            let object_type = match assoc {
+                ObjectTypeAssociation::Simple { object_type } => object_type,
+                ObjectTypeAssociation::O2O { second, .. } => second,
+            };
+            format!(
+                "SELECT {} AS kind, {i} AS i, {} AS ot",
+                sql_string(&bound_kind(part, i)),
+                sql_string(object_type)
+            )
+        })
+        .collect();
+    format!(
+        "SELECT kind FROM ({}) v ORDER BY (SELECT COUNT(*) FROM object o WHERE o.ocel_type = v.ot) DESC, v.i LIMIT 1",
+        associations.join(" UNION ALL ")
+    )
+}
+
+/// Translate an arc to a SQL query returning the IDs (`ocel_id`) of all violating source events
+///
+/// `event_tables` maps event types to the suffix of their `event_<type>` table (see [`EVENT_TYPE_MAP_QUERY`]).
+/// Event types without a table are treated as having no events.
+pub fn arc_to_sql(arc: &OCDeclareArc, event_tables: &HashMap<String, String>) -> String {
+    let label = &arc.label;
+    let is_object_event =
+        |act: &str| act.starts_with(INIT_EVENT_PREFIX) || act.starts_with(EXIT_EVENT_PREFIX);
+    let is_direct = matches!(arc.arc_type, OCDeclareArcType::DF | OCDeclareArcType::DP);
+    // For DF/DP, the nearest event of any activity is considered (including <init>/<exit> events)
+    let uses_object_events =
+        is_direct || is_object_event(arc.from.as_str()) || is_object_event(arc.to.as_str());
+    // Timestamps of all events are required to derive <init>/<exit> events
+    let mut event_types: Vec<&String> = if uses_object_events {
+        event_tables.keys().collect()
+    } else {
+        [arc.from.as_str(), arc.to.as_str()]
+            .into_iter()
+            .filter_map(|act| event_tables.get_key_value(act).map(|(k, _)| k))
+            .collect()
+    };
+    event_types.sort();
+    event_types.dedup();
+    let real_events = if event_types.is_empty() {
+        "SELECT NULL, NULL, NULL WHERE 0".to_string()
+    } else {
+        event_types
+            .iter()
+            .map(|t| {
+                format!(
+                    "SELECT ocel_id, {}, julianday(ocel_time) FROM {}",
+                    sql_string(t),
+                    sql_identifier(&format!("event_{}", event_tables[*t]))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" UNION ALL ")
+    };
+    let mut events = vec!["SELECT id, type, time, 1 FROM rev".to_string()];
+    let mut e2o = vec!["SELECT ocel_event_id, ocel_object_id FROM event_object".to_string()];
+    if uses_object_events {
+        for (prefix, agg, ord) in [(INIT_EVENT_PREFIX, "MIN", 0), (EXIT_EVENT_PREFIX, "MAX", 2)] {
+            let id_prefix = sql_string(&format!("{prefix}_"));
+            events.push(format!(
+                "SELECT {id_prefix} || eo.ocel_object_id, {} || o.ocel_type, {agg}(r.time), {ord} \
+                 FROM event_object eo JOIN rev r ON r.id = eo.ocel_event_id JOIN object o ON o.ocel_id = eo.ocel_object_id \
+                 GROUP BY eo.ocel_object_id, o.ocel_type",
+                sql_string(&format!("{prefix} "))
+            ));
+            e2o.push(format!(
+                "SELECT {id_prefix} || eo.ocel_object_id, eo.ocel_object_id FROM event_object eo JOIN rev r ON r.id = eo.ocel_event_id"
+            ));
+        }
+    }
+    let parts = [
+        ("each", &label.each),
+        ("all", &label.all),
+        ("any", &label.any),
+    ];
+    // Objects bound through an O2O association are kept once per related object (as in `process_mining`)
+    let bound: Vec<String> = parts
+        .iter()
+        .flat_map(|(part, assocs)| {
+            assocs
+                .iter()
+                .enumerate()
+                .map(|(i, assoc)| bound_objects_query(assoc, &bound_kind(part, i)))
+        })
+        .collect();
+    let bound = if bound.is_empty() {
+        "SELECT NULL, NULL, NULL WHERE 0".to_string()
+    } else {
+        bound.join(" UNION ALL ")
+    };
+    // One binding per combination of objects of the EACH associations
+    let binding_columns: String = (0..label.each.len()).map(|i| format!(", o{i}")).collect();
+    let bindings = format!(
+        "SELECT s.id{} FROM src s{}",
+        (0..label.each.len())
+            .map(|i| format!(", b{i}.ob"))
+            .collect::<String>(),
+        (0..label.each.len())
+            .map(|i| format!(
+                " JOIN bound b{i} ON b{i}.src = s.id AND b{i}.kind = {}",
+                sql_string(&bound_kind("each", i))
+            ))
+            .collect::<String>()
+    );
+    let mut first = Vec::new();
+    if !label.all.is_empty() {
+        first.push(format!(
+            "first_all(kind) AS ({})",
+            first_association_query("all", &label.all)
+        ));
+    }
+    if !label.any.is_empty() {
+        first.push(format!(
+            "first_any(kind) AS ({})",
+            first_association_query("any", &label.any)
+        ));
+    }
+    // Conditions on a target event `t` of the binding `b` of the source event `s`
+    let mut conditions: Vec<String> = Vec::new();
+    if !is_direct {
+        conditions.push(format!("t.type = {}", sql_string(arc.to.as_str())));
+    }
+    conditions.extend(
+        (0..label.each.len())
+            .map(|i| format!("EXISTS (SELECT 1 FROM e2o w WHERE w.ev = t.id AND w.ob = b.o{i})")),
+    );
+    conditions.extend((0..label.all.len()).map(|i| {
+        format!(
+            "NOT EXISTS (SELECT 1 FROM bound a WHERE a.src = s.id AND a.kind = {} \
+             AND NOT EXISTS (SELECT 1 FROM e2o w WHERE w.ev = t.id AND w.ob = a.ob))",
+            sql_string(&bound_kind("all", i))
+        )
+    }));
+    conditions.extend((0..label.any.len()).map(|i| {
+        format!(
+            "EXISTS (SELECT 1 FROM bound a JOIN e2o w ON w.ob = a.ob WHERE a.src = s.id AND a.kind = {} AND w.ev = t.id)",
+            sql_string(&bound_kind("any", i))
+        )
+    }));
+    if !label.all.is_empty() {
+        // Target candidates are looked up through the objects of the first ALL association
+        conditions.push(
+            "EXISTS (SELECT 1 FROM bound a JOIN first_all f ON f.kind = a.kind WHERE a.src = s.id)"
+                .to_string(),
+        );
+    }
+    let label_is_empty = label.each.is_empty() && label.all.is_empty() && label.any.is_empty();
+    match arc.arc_type {
+        OCDeclareArcType::ASS => {}
+        OCDeclareArcType::EF => conditions.push("t.time > s.time".to_string()),
+        OCDeclareArcType::EP => conditions.push("t.time < s.time".to_string()),
+        // Without object requirements, the first/last event of the log is considered (as in `process_mining`)
+        OCDeclareArcType::DF | OCDeclareArcType::DP if label_is_empty => {}
+        OCDeclareArcType::DF => conditions.push("t.time > s.time".to_string()),
+        OCDeclareArcType::DP => conditions.push("t.time < s.time".to_string()),
+    }
+    let conditions = if conditions.is_empty() {
+        "1".to_string()
+    } else {
+        conditions.join(" AND ")
+    };
+    let count = if is_direct {
+        let order = if arc.arc_type == OCDeclareArcType::DF {
+            "t.time, t.ord, t.id"
+        } else {
+            "t.time DESC, t.ord DESC, t.id DESC"
+        };
+        format!(
+            "COALESCE((SELECT t.type = {} FROM ev t WHERE {conditions} ORDER BY {order} LIMIT 1), 0)",
+            sql_string(arc.to.as_str())
+        )
+    } else if label.each.is_empty() && label.all.is_empty() && !label.any.is_empty() {
+        // Target events are counted once per object of the first ANY association they involve
+        format!(
+            "(SELECT COUNT(*) FROM bound c JOIN first_any f ON f.kind = c.kind JOIN e2o x ON x.ob = c.ob \
+             JOIN ev t ON t.id = x.ev WHERE c.src = s.id AND {conditions})"
+        )
+    } else {
+        format!("(SELECT COUNT(*) FROM ev t WHERE {conditions})")
+    };
+    let mut violated: Vec<String> = Vec::new();
+    if let Some(min) = arc.counts.0.filter(|min| *min > 0) {
+        violated.push(format!("n < {min}"));
+    }
+    if let Some(max) = arc.counts.1 {
+        violated.push(format!("n > {max}"));
+    }
+    let violated = if violated.is_empty() {
+        "0".to_string()
+    } else {
+        violated.join(" OR ")
+    };
+    format!(
+        "WITH\n\
+         rev(id, type, time) AS ({real_events}),\n\
+         ev(id, type, time, ord) AS MATERIALIZED ({}),\n\
+         e2o(ev, ob) AS MATERIALIZED ({}),\n\
+         src(id, time, ord) AS (SELECT id, time, ord FROM ev WHERE type = {}),\n\
+         bound(src, kind, ob) AS MATERIALIZED ({bound}),\n\
+         {}\
+         binding(src{binding_columns}) AS ({bindings}),\n\
+         counts(src, n) AS (SELECT s.id, {count} FROM binding b JOIN src s ON s.id = b.src)\n\
+         SELECT DISTINCT src AS ocel_id FROM counts WHERE {violated} ORDER BY src",
+        events.join(" UNION ALL "),
+        e2o.join(" UNION "),
+        sql_string(arc.from.as_str()),
+        first.iter().map(|f| format!("{f},\n")).collect::<String>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use process_mining::{
+        import_ocel_json_from_slice,
+        ocel::{
+            linked_ocel::{IndexLinkedOCEL, LinkedOCELAccess},
+            ocel_struct::OCEL,
+        },
+    };
+    use rusqlite::Connection;
+
+    use crate::{
+        constraint::OCDeclareConstraint,
+        diagnostics::get_violation_report,
+        preprocess_ocel,
+        template::parse_model,
+        test_utils::{generated_arcs, generated_raw_ocel, TEST_OCEL_JSON},
+    };
+
+    use super::*;
+
+    /// Write the events, objects and relationships of an OCEL to an in-memory database with the OCEL 2.0 SQLite schema
+    fn ocel_to_sqlite(ocel: &OCEL) -> (Connection, HashMap<String, String>) {
+        let con = Connection::open_in_memory().unwrap();
+        con.execute_batch(
+            "CREATE TABLE event (ocel_id TEXT PRIMARY KEY, ocel_type TEXT);
+             CREATE TABLE object (ocel_id TEXT PRIMARY KEY, ocel_type TEXT);
+             CREATE TABLE event_object (ocel_event_id TEXT, ocel_object_id TEXT, ocel_qualifier TEXT);
+             CREATE TABLE object_object (ocel_source_id TEXT, ocel_target_id TEXT, ocel_qualifier TEXT);
+             CREATE TABLE event_map_type (ocel_type TEXT, ocel_type_map TEXT);",
+        )
+        .unwrap();
+        for (i, event_type) in ocel.event_types.iter().enumerate() {
+            let map = format!("Type{i}");
+            con.execute(
+                "INSERT INTO event_map_type VALUES (?1, ?2)",
+                (&event_type.name, &map),
+            )
+            .unwrap();
+            con.execute_batch(&format!(
+                "CREATE TABLE event_{map} (ocel_id TEXT PRIMARY KEY, ocel_time TIMESTAMP)"
+            ))
+            .unwrap();
+        }
+        let event_tables: HashMap<String, String> = con
+            .prepare(EVENT_TYPE_MAP_QUERY)
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        for ev in &ocel.events {
+            con.execute(
+                "INSERT INTO event VALUES (?1, ?2)",
+                (&ev.id, &ev.event_type),
+            )
+            .unwrap();
+            con.execute(
+                &format!(
+                    "INSERT INTO event_{} VALUES (?1, ?2)",
+                    event_tables[&ev.event_type]
+                ),
+                (&ev.id, ev.time.to_rfc3339()),
+            )
+            .unwrap();
+            for rel in &ev.relationships {
+                con.execute(
+                    "INSERT INTO event_object VALUES (?1, ?2, ?3)",
+                    (&ev.id, &rel.object_id, &rel.qualifier),
+                )
+                .unwrap();
+            }
+        }
+        for ob in &ocel.objects {
+            con.execute(
+                "INSERT INTO object VALUES (?1, ?2)",
+                (&ob.id, &ob.object_type),
+            )
+            .unwrap();
+            for rel in &ob.relationships {
+                con.execute(
+                    "INSERT INTO object_object VALUES (?1, ?2, ?3)",
+                    (&ob.id, &rel.object_id, &rel.qualifier),
+                )
+                .unwrap();
+            }
+        }
+        (con, event_tables)
+    }
+
+    fn violating_events(
+        con: &Connection,
+        arc: &OCDeclareArc,
+        event_tables: &HashMap<String, String>,
+    ) -> Vec<String> {
+        con.prepare(&arc_to_sql(arc, event_tables))
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn agrees_with_get_for_all_evs_perf() {
+        let ocel = import_ocel_json_from_slice(TEST_OCEL_JSON.as_bytes()).unwrap();
+        let (con, event_tables) = ocel_to_sqlite(&ocel);
+        let locel: IndexLinkedOCEL = preprocess_ocel(ocel);
+        let constraints = parse_model(
+            "EF(place order, pay order, Each(order),1,∞)\n\
+             EF(place order, pay order, Each(order),0,1)\n\
+             EP(pay order, place order, Each(order),1,1)\n\
+             DF(place order, pay order, Each(order),1,∞)\n\
+             DF(place order, ship order, Each(order),0,0)\n\
+             DP(ship order, pay order, All(order),1,∞)\n\
+             AS(ship order, place order, Each(item),1,∞)\n\
+             EF(place order, ship order, Any(item),1,∞)\n\
+             EF(place order, ship order, All(item),1,∞)\n\
+             EF(place order, ship order, Each(order->item),1,∞)\n\
+             EP(ship order, place order, Each(item<-order),2,∞)\n\
+             EF(place order, ship order,1,∞)\n\
+             DF(place order, pay order,1,∞)\n\
+             AS(<init> order, pay order, Each(order),1,1)\n\
+             DF(<init> order, place order, Each(order),1,∞)\n\
+             EP(<exit> item, ship order, Each(item),2,∞)\n\
+             DP(<exit> order, ship order, Each(order),1,∞)\n\
+             AS(unknown activity, pay order, Each(order),1,∞)",
+        )
+        .unwrap();
+        for OCDeclareConstraint { arc, .. } in &constraints {
+            let violating = violating_events(&con, arc, &event_tables);
+            let report = get_violation_report(&OCDeclareConstraint::new(arc.clone()), &locel);
+            let fraction = if report.num_source_events == 0 {
+                0.0
+            } else {
+                violating.len() as f64 / report.num_source_events as f64
+            };
+            let expected = arc.get_for_all_evs_perf(&locel);
+            assert!(
+                (fraction - expected).abs() < 1e-9,
+                "{}: {fraction} (SQL) != {expected}",
+                arc.as_template_string()
+            );
+            let mut expected_events: Vec<String> = report
+                .violations
+                .into_iter()
+                .map(|v| v.source_event)
+                .collect();
+            expected_events.sort();
+            assert_eq!(violating, expected_events, "{}", arc.as_template_string());
+        }
+    }
+    #[test]
+    fn agrees_with_upstream_on_generated_log() {
+        let ocel = generated_raw_ocel();
+        let (con, event_tables) = ocel_to_sqlite(&ocel);
+        let locel: IndexLinkedOCEL = preprocess_ocel(ocel);
+        // The generated log contains events with the same timestamp, for which the nearest event of DF/DP is not unique
+        let arcs = generated_arcs(&["1,∞", "0,0", "1,1"])
+            .into_iter()
+            .filter(|arc| !matches!(arc.arc_type, OCDeclareArcType::DF | OCDeclareArcType::DP));
+        for arc in arcs {
+            let num_source_events = locel.get_evs_of_type(arc.from.as_str()).count();
+            let violating = violating_events(&con, &arc, &event_tables);
+            let fraction = violating.len() as f64 / num_source_events as f64;
+            let expected = arc.get_for_all_evs_perf(&locel);
+            assert!(
+                (fraction - expected).abs() < 1e-9,
+                "{}: {fraction} (SQL) != {expected}",
+                arc.as_template_string()
+            );
+        }
+    }
+}
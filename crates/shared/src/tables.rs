@@ -0,0 +1,288 @@
+//! Construction of OCEL from tabular data (e.g., pandas DataFrames of pm4py OCEL objects in the Python bindings)
+//!
+//! The tables follow the layout used by pm4py: One row per event, object, E2O/O2O relationship and object attribute change.
+//! Attribute values are given per cell (missing values omitted), attribute types are inferred from the values.
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
+use process_mining::ocel::ocel_struct::{
+    OCELAttributeValue, OCELEvent, OCELEventAttribute, OCELObject, OCELObjectAttribute,
+    OCELRelationship, OCELType, OCELTypeAttribute, OCEL,
+};
+
+/// A row of the event table
+#[derive(Debug, Clone)]
+pub struct EventRow {
+    pub id: String,
+    pub activity: String,
+    pub time: DateTime<FixedOffset>,
+    /// Non-missing attribute values
+    pub attributes: Vec<(String, OCELAttributeValue)>,
+}
+
+/// A row of the object table (with the initial attribute values)
+#[derive(Debug, Clone)]
+pub struct ObjectRow {
+    pub id: String,
+    pub object_type: String,
+    /// Non-missing initial attribute values
+    pub attributes: Vec<(String, OCELAttributeValue)>,
+}
+
+/// A row of the E2O or O2O relationship table
+#[derive(Debug, Clone)]
+pub struct RelationRow {
+    /// ID of the event (E2O) or source object (O2O)
+    pub source_id: String,
+    /// ID of the (target) object
+    pub object_id: String,
+    pub qualifier: String,
+}
+
+/// A row of the object attribute change table
+#[derive(Debug, Clone)]
+pub struct ObjectChangeRow {
+    pub object_id: String,
+    pub time: DateTime<FixedOffset>,
+    /// Name of the changed attribute
+    pub field: String,
+    pub value: OCELAttributeValue,
+}
+
+/// Parse a timestamp in ISO 8601/RFC 3339 format (optionally with a space instead of `T`)
+///
+/// Timestamps without a timezone are interpreted as UTC.
+pub fn parse_timestamp(s: &str) -> Option<DateTime<FixedOffset>> {
+    let s = s.trim().replacen(' ', "T", 1);
+    DateTime::parse_from_rfc3339(&s)
+        .or_else(|_| DateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S%.f%#z"))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S%.f")
+                .or_else(|_| NaiveDateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M"))
+                .ok()
+                .or_else(|| {
+                    NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                        .ok()
+                        .and_then(|d| d.and_hms_opt(0, 0, 0))
+                })
+                .map(|t| t.and_utc().fixed_offset())
+        })
+}
+
+fn attribute_type(value: &OCELAttributeValue) -> &'static str {
+    match value {
+        OCELAttributeValue::Time(_) => "time",
+        OCELAttributeValue::Integer(_) => "integer",
+        OCELAttributeValue::Float(_) => "float",
+        OCELAttributeValue::Boolean(_) => "boolean",
+        OCELAttributeValue::String(_) | OCELAttributeValue::Null => "string",
+    }
+}
+
+/// Event or object types (in order of their first occurrence) with the attributes observed for them
+#[derive(Default)]
+struct TypeCollector {
+    types: Vec<OCELType>,
+    indices: HashMap<String, usize>,
+}
+
+impl TypeCollector {
+    fn add<'a>(
+        &mut self,
+        name: &str,
+        attributes: impl IntoIterator<Item = (&'a String, &'a OCELAttributeValue)>,
+    ) {
+        let index = *self.indices.entry(name.to_string()).or_insert_with(|| {
+            self.types.push(OCELType {
+                name: name.to_string(),
+                attributes: Vec::new(),
+            });
+            self.types.len() - 1
+        });
+        let ocel_type = &mut self.types[index];
+        for (name, value) in attributes {
+            match ocel_type.attributes.iter_mut().find(|a| &a.name == name) {
+                // Columns mixing integers and floats are float columns
+                Some(attr) if attr.value_type == "integer" && attribute_type(value) == "float" => {
+                    attr.value_type = "float".to_string();
+                }
+                Some(_) => {}
+                None => ocel_type.attributes.push(OCELTypeAttribute {
+                    name: name.clone(),
+                    value_type: attribute_type(value).to_string(),
+                }),
+            }
+        }
+    }
+}
+
+/// Build an OCEL from its event, object, relationship and object change tables
+///
+/// Initial object attribute values are assigned the UNIX epoch as their timestamp (as in the OCEL 2.0 formats).
+pub fn ocel_from_tables(
+    events: Vec<EventRow>,
+    objects: Vec<ObjectRow>,
+    e2o: Vec<RelationRow>,
+    o2o: Vec<RelationRow>,
+    object_changes: Vec<ObjectChangeRow>,
+) -> OCEL {
+    let group_relations = |relations: Vec<RelationRow>| {
+        let mut ret: HashMap<String, Vec<OCELRelationship>> = HashMap::new();
+        for rel in relations {
+            ret.entry(rel.source_id)
+                .or_default()
+                .push(OCELRelationship {
+                    object_id: rel.object_id,
+                    qualifier: rel.qualifier,
+                });
+        }
+        ret
+    };
+    let (mut e2o, mut o2o) = (group_relations(e2o), group_relations(o2o));
+    let mut changes: HashMap<String, Vec<ObjectChangeRow>> = HashMap::new();
+    for change in object_changes {
+        changes
+            .entry(change.object_id.clone())
+            .or_default()
+            .push(change);
+    }
+
+    let mut event_types = TypeCollector::default();
+    let events = events
+        .into_iter()
+        .map(|ev| {
+            event_types.add(&ev.activity, ev.attributes.iter().map(|(n, v)| (n, v)));
+            OCELEvent {
+                relationships: e2o.remove(&ev.id).unwrap_or_default(),
+                id: ev.id,
+                event_type: ev.activity,
+                time: ev.time,
+                attributes: ev
+                    .attributes
+                    .into_iter()
+                    .map(|(name, value)| OCELEventAttribute { name, value })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    let mut object_types = TypeCollector::default();
+    let objects = objects
+        .into_iter()
+        .map(|ob| {
+            let changes = changes.remove(&ob.id).unwrap_or_default();
+            object_types.add(
+                &ob.object_type,
+                ob.attributes
+                    .iter()
+                    .map(|(n, v)| (n, v))
+                    .chain(changes.iter().map(|c| (&c.field, &c.value))),
+            );
+            let initial_time = DateTime::UNIX_EPOCH.fixed_offset();
+            OCELObject {
+                relationships: o2o.remove(&ob.id).unwrap_or_default(),
+                id: ob.id,
+                object_type: ob.object_type,
+                attributes: ob
+                    .attributes
+                    .into_iter()
+                    .map(|(name, value)| OCELObjectAttribute {
+                        name,
+                        value,
+                        time: initial_time,
+                    })
+                    .chain(changes.into_iter().map(|c| OCELObjectAttribute {
+                        name: c.field,
+                        value: c.value,
+                        time: c.time,
+                    }))
+                    .collect(),
+            }
+        })
+        .collect();
+
+    OCEL {
+        event_types: event_types.types,
+        object_types: object_types.types,
+        events,
+        objects,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use process_mining::ocel::linked_ocel::LinkedOCELAccess;
+
+    use crate::preprocess_ocel;
+
+    use super::*;
+
+    #[test]
+    fn from_tables() {
+        let time = |s: &str| parse_timestamp(s).unwrap();
+        let relation = |source: &str, object: &str| RelationRow {
+            source_id: source.to_string(),
+            object_id: object.to_string(),
+            qualifier: String::new(),
+        };
+        let ocel = ocel_from_tables(
+            vec![
+                EventRow {
+                    id: "e1".to_string(),
+                    activity: "place order".to_string(),
+                    time: time("2024-01-01 08:00:00"),
+                    attributes: vec![("price".to_string(), OCELAttributeValue::Integer(500))],
+                },
+                EventRow {
+                    id: "e2".to_string(),
+                    activity: "place order".to_string(),
+                    time: time("2024-01-02T08:00:00.5+01:00"),
+                    attributes: vec![("price".to_string(), OCELAttributeValue::Float(12.5))],
+                },
+            ],
+            vec![
+                ObjectRow {
+                    id: "o1".to_string(),
+                    object_type: "order".to_string(),
+                    attributes: vec![(
+                        "status".to_string(),
+                        OCELAttributeValue::String("new".to_string()),
+                    )],
+                },
+                ObjectRow {
+                    id: "i1".to_string(),
+                    object_type: "item".to_string(),
+                    attributes: Vec::new(),
+                },
+            ],
+            vec![
+                relation("e1", "o1"),
+                relation("e1", "i1"),
+                relation("e2", "o1"),
+            ],
+            vec![relation("o1", "i1")],
+            vec![ObjectChangeRow {
+                object_id: "o1".to_string(),
+                time: time("2024-01-03"),
+                field: "status".to_string(),
+                value: OCELAttributeValue::String("paid".to_string()),
+            }],
+        );
+        assert_eq!(ocel.event_types.len(), 1);
+        assert_eq!(ocel.event_types[0].attributes[0].value_type, "float");
+        assert_eq!(
+            ocel.events[1].time.to_rfc3339(),
+            "2024-01-02T08:00:00.500+01:00"
+        );
+        assert_eq!(ocel.events[0].relationships.len(), 2);
+        assert_eq!(ocel.objects[0].relationships[0].object_id, "i1");
+        assert_eq!(ocel.objects[0].attributes.len(), 2);
+        assert_eq!(
+            ocel.objects[0].attributes[1].time.to_rfc3339(),
+            "2024-01-03T00:00:00+00:00"
+        );
+        let locel = preprocess_ocel(ocel);
+        assert_eq!(locel.get_evs_of_type("place order").count(), 2);
+    }
+}
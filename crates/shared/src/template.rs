@@ -0,0 +1,1170 @@
+//! Textual model format for OC-DECLARE, based on the syntax of `OCDeclareArc::as_template_string`
+//!
+//! A model file contains one arc per line. Empty lines and lines starting with `#` are ignored.
+//!
+//! ```text
+//! # Orders need to be paid exactly once
+//! EF(place order, pay order, Each(orders),1,1)
+//! DP(ship order, "pack, label", Any(items), All(orders>customers),1,∞)
+//! AS(<init> orders, place order,1,*)
+//! EF(confirm order, pay order, Each(orders),1,∞,Delay(*,14d))
+//! EF(pay order[price > 1000], approve payment, Each(orders),1,∞)
+//! ```
+//!
+//! - Arc types: `AS` (or `ASS`), `EF`, `EP`, `DF`, `DP`
+//! - Labels: Any number of `Each(..)`, `All(..)` and `Any(..)` parts, each containing a comma-separated list
+//!   of object type associations. `a>b` denotes the O2O association from `a` to `b`, `a<b` the reversed one
+//!   (as in `ObjectTypeAssociation::as_template_string`; `a->b` and `a<-b` are accepted as well).
+//! - Counts: The two items after the label are the min and max count. `*` (or `∞`/`inf`) denotes an unbounded count.
+//! - Time bounds (optional): A final `Delay(min,max)` item bounds the time distance between source and target events
+//!   (see [`TimeBounds`]). Durations are sequences of amounts with units `w`, `d`, `h`, `m`, `s` and `ms`
+//!   (e.g., `1d12h`), optionally prefixed by `-` (e.g., `-2h`). `*` (or `∞`/`inf`) denotes an unbounded distance.
+//! - Predicates (optional): An activity can be followed by an event attribute predicate in brackets,
+//!   e.g., `pay order[price > 1000 and not (express = true)]` (see [`EventPredicate`]).
+//!   Comparisons (`=`, `!=`, `<`, `<=`, `>`, `>=`) compare an attribute to a number, a `"string"`, `true`/`false`
+//!   or an RFC 3339 timestamp prefixed by `@` (e.g., `@2024-01-01T00:00:00Z`).
+//!   They can be combined using `and`/`&&`, `or`/`||`, `not`/`!` and parentheses.
+//! - Names: Activity and object type names are either bare (everything up to the next `,`, `(`, `)` or `[`,
+//!   trimmed) or double-quoted with `\"` and `\\` as escape sequences.
+
+use std::fmt::Display;
+
+use chrono::DateTime;
+
+use crate::{
+    constraint::{OCDeclareConstraint, TimeBounds},
+    predicate::{ComparisonOp, EventPredicate, PredicateValue},
+    OCDeclareArc, OCDeclareArcLabel, OCDeclareArcType, OCDeclareNode, ObjectTypeAssociation,
+};
+
+/// Duration units of time bounds, from largest to smallest
+const DURATION_UNITS: [(&str, i64); 6] = [
+    ("w", 7 * 24 * 60 * 60 * 1000),
+    ("d", 24 * 60 * 60 * 1000),
+    ("h", 60 * 60 * 1000),
+    ("m", 60 * 1000),
+    ("s", 1000),
+    ("ms", 1),
+];
+
+/// Characters which end a bare attribute name in a predicate
+const PREDICATE_SPECIAL_CHARS: [char; 12] =
+    ['=', '!', '<', '>', '(', ')', '[', ']', '"', ',', '&', '|'];
+
+/// Error while parsing the textual OC-DECLARE model format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the error (1-based)
+    pub line: usize,
+    /// Column of the error (1-based, in characters)
+    pub column: usize,
+    /// Description of the error
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// One comma-separated item inside the parentheses of an arc
+enum ArcItem {
+    Label(LabelKind, Vec<ObjectTypeAssociation>),
+    Count(Option<usize>),
+    Delay(TimeBounds),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum LabelKind {
+    Each,
+    All,
+    Any,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str, line: usize) -> Self {
+        Self {
+            input,
+            pos: 0,
+            line,
+        }
+    }
+
+    fn error_at<S: Into<String>>(&self, pos: usize, message: S) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.input[..pos].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    fn error<S: Into<String>>(&self, message: S) -> ParseError {
+        self.error_at(self.pos, message)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.pos += c.len_utf8();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        self.skip_ws();
+        match self.peek() {
+            Some(found) if found == c => {
+                self.pos += c.len_utf8();
+                Ok(())
+            }
+            Some(found) => Err(self.error(format!("Expected '{c}', found '{found}'"))),
+            None => Err(self.error(format!("Expected '{c}', found end of line"))),
+        }
+    }
+
+    /// Parse a double-quoted string (starting at the current position) with `\"` and `\\` as escape sequences
+    fn quoted(&mut self, what: &str) -> Result<String, ParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut ret = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error_at(start, format!("Unterminated quoted {what}"))),
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(ret);
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(c @ ('"' | '\\')) => {
+                            ret.push(c);
+                            self.pos += 1;
+                        }
+                        _ => {
+                            return Err(
+                                self.error("Invalid escape sequence (expected \\\" or \\\\)")
+                            )
+                        }
+                    }
+                }
+                Some(c) => {
+                    ret.push(c);
+                    self.pos += c.len_utf8();
+                }
+            }
+        }
+    }
+
+    /// Parse a bare or quoted name, stopping at any of the `stop` sequences (for bare names)
+    fn name(&mut self, what: &str, stop: &[&str]) -> Result<String, ParseError> {
+        self.skip_ws();
+        let start = self.pos;
+        if self.peek() == Some('"') {
+            return self.quoted(what);
+        }
+        while let Some(c) = self.peek() {
+            if matches!(c, ',' | '(' | ')' | '[' | '"')
+                || stop.iter().any(|s| self.rest().starts_with(s))
+            {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+        let ret = self.input[start..self.pos].trim();
+        if ret.is_empty() {
+            return Err(self.error_at(start, format!("Expected {what}")));
+        }
+        Ok(ret.to_string())
+    }
+
+    fn constraint(&mut self) -> Result<OCDeclareConstraint, ParseError> {
+        self.skip_ws();
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        let type_str = &self.input[start..self.pos];
+        let arc_type = OCDeclareArcType::parse_str(type_str).ok_or_else(|| {
+            self.error_at(
+                start,
+                format!("Unknown arc type '{type_str}' (expected one of AS, EF, EP, DF, DP)"),
+            )
+        })?;
+        self.expect('(')?;
+        let from = self.name("source activity", &[])?;
+        let from_predicate = self.node_predicate()?;
+        self.expect(',')?;
+        let to = self.name("target activity", &[])?;
+        let to_predicate = self.node_predicate()?;
+        let mut items: Vec<(usize, ArcItem)> = Vec::new();
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some(',') => {
+                    self.pos += 1;
+                    self.skip_ws();
+                    // Tolerate empty items (e.g., from an empty label)
+                    if self.peek() == Some(',') {
+                        continue;
+                    }
+                    let item_start = self.pos;
+                    items.push((item_start, self.arc_item()?));
+                }
+                Some(')') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(c) => return Err(self.error(format!("Expected ',' or ')', found '{c}'"))),
+                None => return Err(self.error("Expected ')', found end of line")),
+            }
+        }
+        self.skip_ws();
+        if let Some(c) = self.peek() {
+            return Err(self.error(format!("Unexpected '{c}' after end of arc")));
+        }
+
+        let time_bounds = match items.last() {
+            Some((_, ArcItem::Delay(bounds))) => {
+                let bounds = *bounds;
+                items.pop();
+                Some(bounds)
+            }
+            _ => None,
+        };
+        if let Some((item_pos, _)) = items
+            .iter()
+            .find(|(_, item)| matches!(item, ArcItem::Delay(_)))
+        {
+            return Err(self.error_at(*item_pos, "Delay(..) needs to be the last item of the arc"));
+        }
+
+        let mut label = OCDeclareArcLabel {
+            each: Vec::new(),
+            any: Vec::new(),
+            all: Vec::new(),
+        };
+        let num_labels = items.len().saturating_sub(2);
+        let mut counts = Vec::new();
+        for (i, (item_pos, item)) in items.into_iter().enumerate() {
+            match (item, i < num_labels) {
+                (ArcItem::Label(kind, assocs), true) => match kind {
+                    LabelKind::Each => label.each.extend(assocs),
+                    LabelKind::All => label.all.extend(assocs),
+                    LabelKind::Any => label.any.extend(assocs),
+                },
+                (ArcItem::Count(c), false) => counts.push(c),
+                (ArcItem::Label(_, _), false) => {
+                    return Err(
+                        self.error_at(item_pos, "Expected min and max count after the label")
+                    )
+                }
+                (ArcItem::Count(_), true) => {
+                    return Err(self.error_at(item_pos, "Expected Each(..), All(..) or Any(..)"))
+                }
+                (ArcItem::Delay(_), _) => unreachable!("Delay(..) items were checked above"),
+            }
+        }
+        if counts.len() != 2 {
+            return Err(self.error("Expected min and max count at the end of the arc"));
+        }
+        let arc = OCDeclareArc {
+            from: OCDeclareNode::new(from),
+            to: OCDeclareNode::new(to),
+            arc_type,
+            label,
+            counts: (counts[0], counts[1]),
+        };
+        Ok(OCDeclareConstraint {
+            arc,
+            time_bounds,
+            from_predicate,
+            to_predicate,
+        })
+    }
+
+    /// Parse the optional `[predicate]` after the activity of a node
+    fn node_predicate(&mut self) -> Result<Option<EventPredicate>, ParseError> {
+        self.skip_ws();
+        if self.peek() != Some('[') {
+            return Ok(None);
+        }
+        self.pos += 1;
+        let predicate = self.predicate_or()?;
+        self.expect(']')?;
+        Ok(Some(predicate))
+    }
+
+    /// Check if the rest starts with the (case-insensitive) keyword or symbol, and consume it if so
+    fn keyword(&mut self, keyword: &str, symbol: &str) -> bool {
+        self.skip_ws();
+        let rest = self.rest();
+        if rest.starts_with(symbol) {
+            self.pos += symbol.len();
+            return true;
+        }
+        let is_keyword = rest
+            .get(..keyword.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(keyword))
+            && !rest[keyword.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_');
+        if is_keyword {
+            self.pos += keyword.len();
+        }
+        is_keyword
+    }
+
+    fn predicate_or(&mut self) -> Result<EventPredicate, ParseError> {
+        let mut predicates = vec![self.predicate_and()?];
+        while self.keyword("or", "||") {
+            predicates.push(self.predicate_and()?);
+        }
+        Ok(if predicates.len() == 1 {
+            predicates.remove(0)
+        } else {
+            EventPredicate::Or { predicates }
+        })
+    }
+
+    fn predicate_and(&mut self) -> Result<EventPredicate, ParseError> {
+        let mut predicates = vec![self.predicate_unary()?];
+        while self.keyword("and", "&&") {
+            predicates.push(self.predicate_unary()?);
+        }
+        Ok(if predicates.len() == 1 {
+            predicates.remove(0)
+        } else {
+            EventPredicate::And { predicates }
+        })
+    }
+
+    fn predicate_unary(&mut self) -> Result<EventPredicate, ParseError> {
+        // `!=` is not a negation
+        if !self.rest().trim_start().starts_with("!=") && self.keyword("not", "!") {
+            let predicate = self.predicate_unary()?;
+            return Ok(EventPredicate::Not {
+                predicate: Box::new(predicate),
+            });
+        }
+        self.skip_ws();
+        if self.peek() == Some('(') {
+            self.pos += 1;
+            self.skip_ws();
+            // `()` is the empty conjunction, i.e., always true
+            if self.peek() == Some(')') {
+                self.pos += 1;
+                return Ok(EventPredicate::And { predicates: vec![] });
+            }
+            let predicate = self.predicate_or()?;
+            self.expect(')')?;
+            return Ok(predicate);
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<EventPredicate, ParseError> {
+        self.skip_ws();
+        let attribute = if self.peek() == Some('"') {
+            self.quoted("attribute")?
+        } else {
+            let start = self.pos;
+            while self
+                .peek()
+                .is_some_and(|c| !c.is_whitespace() && !PREDICATE_SPECIAL_CHARS.contains(&c))
+            {
+                self.pos += self.peek().map_or(0, char::len_utf8);
+            }
+            if self.pos == start {
+                return Err(self.error("Expected attribute name"));
+            }
+            self.input[start..self.pos].to_string()
+        };
+        self.skip_ws();
+        let op = [
+            ("<=", ComparisonOp::Le),
+            (">=", ComparisonOp::Ge),
+            ("!=", ComparisonOp::Ne),
+            ("==", ComparisonOp::Eq),
+            ("=", ComparisonOp::Eq),
+            ("<", ComparisonOp::Lt),
+            (">", ComparisonOp::Gt),
+        ]
+        .into_iter()
+        .find(|(symbol, _)| self.rest().starts_with(symbol));
+        let op = match op {
+            Some((symbol, op)) => {
+                self.pos += symbol.len();
+                op
+            }
+            None => return Err(self.error("Expected comparison operator (=, !=, <, <=, > or >=)")),
+        };
+        let value = self.predicate_value()?;
+        Ok(EventPredicate::Compare {
+            attribute,
+            op,
+            value,
+        })
+    }
+
+    fn predicate_value(&mut self) -> Result<PredicateValue, ParseError> {
+        self.skip_ws();
+        let start = self.pos;
+        if self.peek() == Some('"') {
+            return Ok(PredicateValue::String(self.quoted("string")?));
+        }
+        let is_time = self.peek() == Some('@');
+        if is_time {
+            self.pos += 1;
+        }
+        let token_start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| !c.is_whitespace() && !matches!(c, ')' | ']' | '(' | '[' | ','))
+        {
+            self.pos += self.peek().map_or(0, char::len_utf8);
+        }
+        let token = &self.input[token_start..self.pos];
+        if is_time {
+            return DateTime::parse_from_rfc3339(token)
+                .map(PredicateValue::Time)
+                .map_err(|e| self.error_at(start, format!("Invalid timestamp (RFC 3339): {e}")));
+        }
+        match token {
+            "true" => Ok(PredicateValue::Boolean(true)),
+            "false" => Ok(PredicateValue::Boolean(false)),
+            "" => Err(self.error_at(
+                start,
+                "Expected a value (number, \"string\", true, false or @timestamp)",
+            )),
+            _ => token
+                .parse::<f64>()
+                .map(PredicateValue::Number)
+                .map_err(|_| {
+                    self.error_at(
+                        start,
+                        format!("Invalid value '{token}' (expected number, \"string\", true, false or @timestamp)"),
+                    )
+                }),
+        }
+    }
+
+    fn arc_item(&mut self) -> Result<ArcItem, ParseError> {
+        let start = self.pos;
+        if let Some(c) = self.count()? {
+            return Ok(ArcItem::Count(c));
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        let kind = match self.input[start..self.pos].to_ascii_lowercase().as_str() {
+            "each" => LabelKind::Each,
+            "all" => LabelKind::All,
+            "any" => LabelKind::Any,
+            "delay" => return self.delay(),
+            _ => {
+                return Err(self.error_at(
+                    start,
+                    "Expected Each(..), All(..), Any(..), Delay(..) or a count",
+                ))
+            }
+        };
+        self.expect('(')?;
+        let mut assocs = vec![self.association()?];
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some(',') => {
+                    self.pos += 1;
+                    assocs.push(self.association()?);
+                }
+                Some(')') => {
+                    self.pos += 1;
+                    return Ok(ArcItem::Label(kind, assocs));
+                }
+                Some(c) => return Err(self.error(format!("Expected ',' or ')', found '{c}'"))),
+                None => return Err(self.error("Expected ')', found end of line")),
+            }
+        }
+    }
+
+    /// Try to parse a count (returns `None` if the item is not a count)
+    fn count(&mut self) -> Result<Option<Option<usize>>, ParseError> {
+        let rest = self.rest();
+        for unbounded in ["*", "∞", "inf"] {
+            if rest.starts_with(unbounded)
+                && rest[unbounded.len()..].trim_start().starts_with([',', ')'])
+            {
+                self.pos += unbounded.len();
+                return Ok(Some(None));
+            }
+        }
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return Ok(None);
+        }
+        let start = self.pos;
+        self.pos += digits;
+        let count = self.input[start..self.pos]
+            .parse::<usize>()
+            .map_err(|e| self.error_at(start, format!("Invalid count: {e}")))?;
+        Ok(Some(Some(count)))
+    }
+
+    /// Parse the parenthesized min and max duration of a `Delay(..)` item
+    fn delay(&mut self) -> Result<ArcItem, ParseError> {
+        self.expect('(')?;
+        let min_delay_ms = self.duration()?;
+        self.expect(',')?;
+        let max_delay_ms = self.duration()?;
+        self.expect(')')?;
+        Ok(ArcItem::Delay(TimeBounds::new(min_delay_ms, max_delay_ms)))
+    }
+
+    /// Parse a (possibly negative) duration (e.g., `1d12h` or `-2h`) in milliseconds,
+    /// or `*`/`∞`/`inf` for an unbounded duration
+    fn duration(&mut self) -> Result<Option<i64>, ParseError> {
+        self.skip_ws();
+        for unbounded in ["*", "∞", "inf"] {
+            if self.rest().starts_with(unbounded) {
+                self.pos += unbounded.len();
+                return Ok(None);
+            }
+        }
+        let sign = if self.rest().starts_with('-') {
+            self.pos += 1;
+            -1
+        } else {
+            1
+        };
+        let start = self.pos;
+        let mut total: i64 = 0;
+        loop {
+            let digits = self
+                .rest()
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            if digits == 0 {
+                break;
+            }
+            let amount_start = self.pos;
+            self.pos += digits;
+            let amount = self.input[amount_start..self.pos]
+                .parse::<i64>()
+                .map_err(|e| self.error_at(amount_start, format!("Invalid duration: {e}")))?;
+            // Check longer unit names first, so that `ms` is not parsed as `m`
+            let unit = DURATION_UNITS
+                .iter()
+                .filter(|(name, _)| self.rest().starts_with(name))
+                .max_by_key(|(name, _)| name.len());
+            let factor = match unit {
+                Some((name, factor)) => {
+                    self.pos += name.len();
+                    *factor
+                }
+                None if amount == 0 => 0,
+                None => {
+                    return Err(self.error("Expected a duration unit (w, d, h, m, s or ms)"));
+                }
+            };
+            total = amount
+                .checked_mul(sign * factor)
+                .and_then(|ms| total.checked_add(ms))
+                .ok_or_else(|| self.error_at(amount_start, "Duration is too large"))?;
+        }
+        if self.pos == start {
+            return Err(self.error("Expected a duration (e.g., 14d or 1h30m) or *"));
+        }
+        Ok(Some(total))
+    }
+
+    fn association(&mut self) -> Result<ObjectTypeAssociation, ParseError> {
+        const SEPARATORS: [(&str, bool); 4] =
+            [("->", false), ("<-", true), (">", false), ("<", true)];
+        let stop = SEPARATORS.map(|(sep, _)| sep);
+        let first = self.name("object type", &stop)?;
+        self.skip_ws();
+        let Some((sep, reversed)) = SEPARATORS
+            .into_iter()
+            .find(|(sep, _)| self.rest().starts_with(sep))
+        else {
+            return Ok(ObjectTypeAssociation::Simple { object_type: first });
+        };
+        self.pos += sep.len();
+        let second = self.name("object type", &stop)?;
+        Ok(ObjectTypeAssociation::O2O {
+            first,
+            second,
+            reversed,
+        })
+    }
+}
+
+/// Parse a single arc (including its optional time bounds) in the textual model format
+pub fn parse_constraint(input: &str) -> Result<OCDeclareConstraint, ParseError> {
+    Parser::new(input, 1).constraint()
+}
+
+/// Parse an event predicate (i.e., the part between `[` and `]` of a node)
+pub fn parse_predicate(input: &str) -> Result<EventPredicate, ParseError> {
+    let mut parser = Parser::new(input, 1);
+    let predicate = parser.predicate_or()?;
+    parser.skip_ws();
+    if let Some(c) = parser.peek() {
+        return Err(parser.error(format!("Unexpected '{c}' after end of predicate")));
+    }
+    Ok(predicate)
+}
+
+/// Parse a model in the textual model format (one arc per line)
+pub fn parse_model(input: &str) -> Result<Vec<OCDeclareConstraint>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim_start();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(i, line)| Parser::new(line, i + 1).constraint())
+        .collect()
+}
+
+fn print_quoted(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn print_name(name: &str, is_object_type: bool) -> String {
+    let needs_quotes = name.is_empty()
+        || name.trim() != name
+        || name.starts_with('#')
+        || name.contains([',', '(', ')', '[', ']', '"', '\\', '\n', '\r'])
+        || (is_object_type && name.contains(['<', '>']));
+    if needs_quotes {
+        print_quoted(name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Print an object type association in the textual model format
+pub fn print_association(assoc: &ObjectTypeAssociation) -> String {
+    match assoc {
+        ObjectTypeAssociation::Simple { object_type } => print_name(object_type, true),
+        ObjectTypeAssociation::O2O {
+            first,
+            second,
+            reversed,
+        } => format!(
+            "{}{}{}",
+            print_name(first, true),
+            if *reversed { "<" } else { ">" },
+            print_name(second, true)
+        ),
+    }
+}
+
+/// Print a duration (in milliseconds) of time bounds in the textual model format (e.g., `1d12h` or `-2h`)
+pub fn print_duration(duration_ms: i64) -> String {
+    if duration_ms == 0 {
+        return "0s".to_string();
+    }
+    let sign = if duration_ms < 0 { "-" } else { "" };
+    let mut rest = duration_ms.unsigned_abs();
+    let mut ret = sign.to_string();
+    // Weeks are only used when they divide the duration, so that e.g. 10 days are not printed as 1w3d
+    let units = if rest.is_multiple_of(DURATION_UNITS[0].1 as u64) {
+        &DURATION_UNITS[..]
+    } else {
+        &DURATION_UNITS[1..]
+    };
+    for (name, factor) in units {
+        let factor = *factor as u64;
+        if rest >= factor {
+            ret.push_str(&format!("{}{name}", rest / factor));
+            rest %= factor;
+        }
+    }
+    ret
+}
+
+/// Print an event predicate in the textual model format (i.e., the part between `[` and `]` of a node)
+///
+/// Nested conjunctions and disjunctions are grouped by parentheses, such that parsing the printed predicate
+/// yields the same predicate if it is normalized (see [`EventPredicate::normalized`]).
+/// Otherwise, parsing yields the normalized predicate.
+pub fn print_predicate(predicate: &EventPredicate) -> String {
+    // Single-element conjunctions/disjunctions are printed as their element
+    fn unwrap_single(p: &EventPredicate) -> &EventPredicate {
+        match p {
+            EventPredicate::And { predicates } | EventPredicate::Or { predicates }
+                if predicates.len() == 1 =>
+            {
+                unwrap_single(&predicates[0])
+            }
+            _ => p,
+        }
+    }
+    let predicate = unwrap_single(predicate);
+    let print_grouped = |p: &EventPredicate| match unwrap_single(p) {
+        p @ (EventPredicate::And { predicates } | EventPredicate::Or { predicates })
+            if !predicates.is_empty() =>
+        {
+            format!("({})", print_predicate(p))
+        }
+        p => print_predicate(p),
+    };
+    match predicate {
+        EventPredicate::Compare {
+            attribute,
+            op,
+            value,
+        } => {
+            let is_bare = !attribute.is_empty()
+                && !attribute.starts_with('"')
+                && !attribute
+                    .chars()
+                    .any(|c| c.is_whitespace() || PREDICATE_SPECIAL_CHARS.contains(&c))
+                && !["and", "or", "not"]
+                    .iter()
+                    .any(|k| attribute.eq_ignore_ascii_case(k));
+            let value = match value {
+                PredicateValue::Number(n) => n.to_string(),
+                PredicateValue::String(s) => print_quoted(s),
+                PredicateValue::Boolean(b) => b.to_string(),
+                PredicateValue::Time(t) => format!("@{}", t.to_rfc3339()),
+            };
+            format!(
+                "{} {} {}",
+                if is_bare {
+                    attribute.clone()
+                } else {
+                    print_quoted(attribute)
+                },
+                op.symbol(),
+                value
+            )
+        }
+        EventPredicate::And { predicates } if predicates.is_empty() => "()".to_string(),
+        EventPredicate::Or { predicates } if predicates.is_empty() => "not ()".to_string(),
+        EventPredicate::And { predicates } => predicates
+            .iter()
+            .map(print_grouped)
+            .collect::<Vec<_>>()
+            .join(" and "),
+        // Conjunctions bind stronger than disjunctions
+        EventPredicate::Or { predicates } => predicates
+            .iter()
+            .map(|p| match unwrap_single(p) {
+                p @ EventPredicate::And { predicates } if !predicates.is_empty() => {
+                    print_predicate(p)
+                }
+                _ => print_grouped(p),
+            })
+            .collect::<Vec<_>>()
+            .join(" or "),
+        EventPredicate::Not { predicate } => format!("not {}", print_grouped(predicate)),
+    }
+}
+
+fn print_node(node: &OCDeclareNode, predicate: Option<&EventPredicate>) -> String {
+    match predicate {
+        Some(predicate) => format!(
+            "{}[{}]",
+            print_name(node.as_str(), false),
+            print_predicate(predicate)
+        ),
+        None => print_name(node.as_str(), false),
+    }
+}
+
+/// Print an arc in the textual model format
+///
+/// The output follows `OCDeclareArc::as_template_string`, but quotes names where necessary
+/// so that [`parse_constraint`] yields the same arc again.
+pub fn print_arc(arc: &OCDeclareArc) -> String {
+    print_arc_with_predicates(arc, None, None)
+}
+
+fn print_arc_with_predicates(
+    arc: &OCDeclareArc,
+    from_predicate: Option<&EventPredicate>,
+    to_predicate: Option<&EventPredicate>,
+) -> String {
+    let mut parts = vec![
+        print_node(&arc.from, from_predicate),
+        print_node(&arc.to, to_predicate),
+    ];
+    for (name, assocs) in [
+        ("Each", &arc.label.each),
+        ("All", &arc.label.all),
+        ("Any", &arc.label.any),
+    ] {
+        if !assocs.is_empty() {
+            parts.push(format!(
+                "{name}({})",
+                assocs
+                    .iter()
+                    .map(print_association)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ));
+        }
+    }
+    format!(
+        "{}({},{},{})",
+        arc.arc_type.get_name(),
+        parts.join(", "),
+        arc.counts
+            .0
+            .map(|c| c.to_string())
+            .unwrap_or("*".to_string()),
+        arc.counts
+            .1
+            .map(|c| c.to_string())
+            .unwrap_or("∞".to_string()),
+    )
+}
+
+/// Print a constraint in the textual model format, i.e., the arc with its node predicates and time bounds (if any)
+pub fn print_constraint(constraint: &OCDeclareConstraint) -> String {
+    let arc = print_arc_with_predicates(
+        &constraint.arc,
+        constraint.from_predicate.as_ref(),
+        constraint.to_predicate.as_ref(),
+    );
+    match &constraint.time_bounds {
+        Some(bounds) => format!(
+            "{},Delay({},{}))",
+            &arc[..arc.len() - 1],
+            bounds
+                .min_delay_ms
+                .map(print_duration)
+                .unwrap_or("*".to_string()),
+            bounds
+                .max_delay_ms
+                .map(print_duration)
+                .unwrap_or("∞".to_string()),
+        ),
+        None => arc,
+    }
+}
+
+/// Print a model in the textual model format (one arc per line)
+pub fn print_model(constraints: &[OCDeclareConstraint]) -> String {
+    constraints
+        .iter()
+        .map(|constraint| print_constraint(constraint) + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        constraint::{OCDeclareConstraint, TimeBounds},
+        predicate::{ComparisonOp, EventPredicate, PredicateValue},
+        OCDeclareArc, OCDeclareArcLabel, OCDeclareArcType, OCDeclareNode, ObjectTypeAssociation,
+    };
+
+    use super::{
+        parse_constraint, parse_model, parse_predicate, print_arc, print_duration, print_model,
+        print_predicate,
+    };
+
+    #[test]
+    fn parse_template_string() {
+        let constraint = parse_constraint(
+            "EF(Load Truck, Depart, Each(orders), All(items), Any(employees),1,∞)",
+        )
+        .unwrap();
+        assert_eq!(constraint.time_bounds, None);
+        let arc = constraint.arc;
+        assert_eq!(arc.from.as_str(), "Load Truck");
+        assert_eq!(arc.to.as_str(), "Depart");
+        assert_eq!(arc.arc_type, OCDeclareArcType::EF);
+        assert_eq!(arc.label.each.len(), 1);
+        assert_eq!(arc.label.all.len(), 1);
+        assert_eq!(arc.label.any.len(), 1);
+        assert_eq!(arc.counts, (Some(1), None));
+    }
+
+    #[test]
+    fn round_trip() {
+        let simple = |ot: &str| ObjectTypeAssociation::Simple {
+            object_type: ot.to_string(),
+        };
+        let model: Vec<OCDeclareConstraint> = vec![
+            OCDeclareArc {
+                from: OCDeclareNode::new("<init> orders"),
+                to: OCDeclareNode::new("pack, \"label\""),
+                arc_type: OCDeclareArcType::DP,
+                label: OCDeclareArcLabel {
+                    each: vec![simple("orders"), simple("a->b")],
+                    any: vec![ObjectTypeAssociation::O2O {
+                        first: "orders".to_string(),
+                        second: "customers".to_string(),
+                        reversed: true,
+                    }],
+                    all: vec![],
+                },
+                counts: (None, Some(0)),
+            }
+            .into(),
+            OCDeclareArc {
+                from: OCDeclareNode::new("a"),
+                to: OCDeclareNode::new("<exit> orders"),
+                arc_type: OCDeclareArcType::ASS,
+                label: OCDeclareArcLabel {
+                    each: vec![],
+                    any: vec![],
+                    all: vec![],
+                },
+                counts: (Some(2), None),
+            }
+            .into(),
+            OCDeclareConstraint::from(OCDeclareArc {
+                from: OCDeclareNode::new("confirm order"),
+                to: OCDeclareNode::new("pay order"),
+                arc_type: OCDeclareArcType::EF,
+                label: OCDeclareArcLabel {
+                    each: vec![simple("orders")],
+                    any: vec![],
+                    all: vec![],
+                },
+                counts: (Some(1), None),
+            })
+            .with_time_bounds(TimeBounds::new(
+                Some(90_000),
+                Some(10 * 24 * 60 * 60 * 1000),
+            )),
+        ];
+        let text = print_model(&model);
+        assert_eq!(parse_model(&text).unwrap(), model);
+    }
+
+    #[test]
+    fn o2o_associations() {
+        let o2o = |first: &str, second: &str, reversed| ObjectTypeAssociation::O2O {
+            first: first.to_string(),
+            second: second.to_string(),
+            reversed,
+        };
+        let arc = OCDeclareArc {
+            from: OCDeclareNode::new("place order"),
+            to: OCDeclareNode::new("ship order"),
+            arc_type: OCDeclareArcType::EF,
+            label: OCDeclareArcLabel {
+                each: vec![o2o("orders", "items", false)],
+                any: vec![o2o("orders", "customers", true)],
+                all: vec![],
+            },
+            counts: (Some(1), None),
+        };
+        let text = arc.as_template_string();
+        assert_eq!(
+            text,
+            "EF(place order, ship order, Each(orders>items), Any(orders<customers),1,∞)"
+        );
+        assert_eq!(parse_constraint(&text).unwrap().arc, arc);
+        assert_eq!(print_arc(&arc), text);
+        assert_eq!(
+            parse_constraint(
+                "EF(place order, ship order, Each(orders -> items), Any(orders<-customers),1,∞)"
+            )
+            .unwrap()
+            .arc,
+            arc
+        );
+    }
+
+    #[test]
+    fn error_position() {
+        let err = parse_model("# comment\n\nEF(a, b, Each(orders), Some(items),1,1)").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 24));
+        let err = parse_model("XY(a, b,1,1)").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        let err = parse_model("EF(a, b, Each(orders),1)").unwrap_err();
+        assert_eq!(err.line, 1);
+        let err = parse_model("EF(a, b, Each(orders),Delay(*,1d),1,1)").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 23));
+    }
+
+    #[test]
+    fn time_bounds() {
+        let constraint = parse_constraint("EF(a, b, Each(orders),1,∞, Delay(1h30m, 2w))").unwrap();
+        assert_eq!(
+            constraint.time_bounds,
+            Some(TimeBounds::new(
+                Some(90 * 60 * 1000),
+                Some(14 * 24 * 60 * 60 * 1000)
+            ))
+        );
+        assert_eq!(
+            constraint.as_template_string(),
+            "EF(a, b, Each(orders),1,∞,Delay(1h30m,2w))"
+        );
+        let constraint = parse_constraint("EP(a, b,1,1,Delay(*,500ms))").unwrap();
+        assert_eq!(
+            constraint.time_bounds,
+            Some(TimeBounds::new(None, Some(500)))
+        );
+        assert!(parse_constraint("EF(a, b,1,1,Delay(1x,*))").is_err());
+        // Negative durations
+        let constraint = parse_constraint("AS(a, b,1,1,Delay(-1h30m, 0s))").unwrap();
+        assert_eq!(
+            constraint.time_bounds,
+            Some(TimeBounds::new(Some(-90 * 60 * 1000), Some(0)))
+        );
+        assert_eq!(
+            constraint.as_template_string(),
+            "AS(a, b,1,1,Delay(-1h30m,0s))"
+        );
+        assert_eq!(print_duration(-14 * 24 * 60 * 60 * 1000), "-2w");
+        assert!(print_duration(i64::MIN).starts_with('-'));
+        assert!(parse_constraint("EF(a, b,1,1,Delay(-,*))").is_err());
+    }
+
+    #[test]
+    fn predicates() {
+        let constraint = parse_constraint(
+            "EF(pay order[price > 1000 && (method = \"card\" or !express)], approve payment, Each(orders),1,∞)",
+        );
+        // `express` alone is not a comparison
+        assert!(constraint.is_err());
+        let constraint = parse_constraint(
+            "EF(pay order[price > 1000 && (method = \"card\" or !(express = true))], approve payment, Each(orders),1,∞)",
+        )
+        .unwrap();
+        let compare = |attribute: &str, op, value| EventPredicate::Compare {
+            attribute: attribute.to_string(),
+            op,
+            value,
+        };
+        assert_eq!(
+            constraint.from_predicate,
+            Some(EventPredicate::And {
+                predicates: vec![
+                    compare("price", ComparisonOp::Gt, PredicateValue::Number(1000.0)),
+                    EventPredicate::Or {
+                        predicates: vec![
+                            compare(
+                                "method",
+                                ComparisonOp::Eq,
+                                PredicateValue::String("card".to_string())
+                            ),
+                            EventPredicate::Not {
+                                predicate: Box::new(compare(
+                                    "express",
+                                    ComparisonOp::Eq,
+                                    PredicateValue::Boolean(true)
+                                )),
+                            },
+                        ],
+                    },
+                ],
+            })
+        );
+        assert_eq!(constraint.to_predicate, None);
+        assert_eq!(
+            constraint.as_template_string(),
+            "EF(pay order[price > 1000 and (method = \"card\" or not express = true)], approve payment, Each(orders),1,∞)"
+        );
+        assert_eq!(
+            parse_constraint(&constraint.as_template_string()).unwrap(),
+            constraint
+        );
+
+        let predicate =
+            parse_predicate("\"due date\" <= @2024-01-01T00:00:00Z or x != -1.5").unwrap();
+        assert_eq!(
+            print_predicate(&predicate),
+            "\"due date\" <= @2024-01-01T00:00:00+00:00 or x != -1.5"
+        );
+        let err = parse_predicate("price >").unwrap_err();
+        assert_eq!(err.column, 8);
+    }
+
+    #[test]
+    fn predicate_round_trip() {
+        let compare = |attribute: &str| EventPredicate::Compare {
+            attribute: attribute.to_string(),
+            op: ComparisonOp::Eq,
+            value: PredicateValue::Number(1.0),
+        };
+        let and = |predicates: Vec<EventPredicate>| EventPredicate::And { predicates };
+        let or = |predicates: Vec<EventPredicate>| EventPredicate::Or { predicates };
+        let not = |predicate: EventPredicate| EventPredicate::Not {
+            predicate: Box::new(predicate),
+        };
+        let (a, b, c) = (compare("a"), compare("b"), compare("c"));
+        // Nested conjunctions and disjunctions are kept
+        let nested = [
+            and(vec![a.clone(), and(vec![b.clone(), c.clone()])]),
+            or(vec![or(vec![a.clone(), b.clone()]), c.clone()]),
+            and(vec![
+                or(vec![a.clone(), b.clone()]),
+                or(vec![c.clone(), not(and(vec![a.clone(), b.clone()]))]),
+            ]),
+            or(vec![
+                and(vec![a.clone(), b.clone()]),
+                and(vec![]),
+                not(and(vec![])),
+            ]),
+            not(or(vec![a.clone(), and(vec![b.clone(), c.clone()])])),
+            not(not(a.clone())),
+        ];
+        for predicate in nested {
+            let printed = print_predicate(&predicate);
+            assert_eq!(parse_predicate(&printed).unwrap(), predicate, "{printed}");
+        }
+        assert_eq!(
+            print_predicate(&and(vec![a.clone(), and(vec![b.clone(), c.clone()])])),
+            "a = 1 and (b = 1 and c = 1)"
+        );
+        // Single-element conjunctions/disjunctions (and the empty disjunction) are normalized
+        let single = [
+            and(vec![a.clone()]),
+            or(vec![and(vec![or(vec![a.clone(), b.clone()])])]),
+            and(vec![and(vec![or(vec![a.clone(), b.clone()])]), c.clone()]),
+            or(vec![c.clone(), or(vec![and(vec![a.clone(), b.clone()])])]),
+            not(and(vec![or(vec![a.clone(), b.clone()])])),
+            or(vec![]),
+        ];
+        for predicate in single {
+            let printed = print_predicate(&predicate);
+            let normalized = predicate.clone().normalized();
+            assert_ne!(normalized, predicate);
+            assert_eq!(parse_predicate(&printed).unwrap(), normalized, "{printed}");
+            assert_eq!(print_predicate(&normalized), printed);
+            // Predicates of constraints are normalized
+            let constraint = parse_constraint("EF(a, b,1,∞)")
+                .unwrap()
+                .with_predicates(Some(predicate), None);
+            assert_eq!(
+                parse_constraint(&constraint.as_template_string()).unwrap(),
+                constraint
+            );
+        }
+        assert_eq!(
+            print_predicate(&and(vec![and(vec![or(vec![a.clone(), b.clone()])]), c])),
+            "(a = 1 or b = 1) and c = 1"
+        );
+    }
+}
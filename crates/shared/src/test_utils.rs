@@ -0,0 +1,191 @@
+use chrono::{Duration, TimeZone, Utc};
+use itertools::Itertools;
+use process_mining::{
+    import_ocel_json_from_slice,
+    ocel::{linked_ocel::IndexLinkedOCEL, ocel_struct::OCEL},
+};
+use serde_json::{json, Value};
+
+use crate::{preprocess_ocel, template::parse_constraint, OCDeclareArc};
+
+/// Small order management OCEL used in unit tests
+///
+/// - `o1`: place order -> pay order -> ship order (with item `i1`)
+/// - `o2`: place order -> ship order (with items `i1`, `i2`)
+/// - `o3`: place order -> pay order -> pay order
+pub(crate) const TEST_OCEL_JSON: &str = r#"{
+    "objectTypes": [{"name": "order", "attributes": []}, {"name": "item", "attributes": []}],
+    "eventTypes": [
+        {"name": "place order", "attributes": [{"name": "price", "type": "float"}]},
+        {"name": "pay order", "attributes": []},
+        {"name": "ship order", "attributes": []}
+    ],
+    "objects": [
+        {"id": "o1", "type": "order", "attributes": [], "relationships": [{"objectId": "i1", "qualifier": "contains"}]},
+        {"id": "o2", "type": "order", "attributes": [], "relationships": [{"objectId": "i1", "qualifier": "contains"}, {"objectId": "i2", "qualifier": "contains"}]},
+        {"id": "o3", "type": "order", "attributes": [], "relationships": []},
+        {"id": "i1", "type": "item", "attributes": [], "relationships": []},
+        {"id": "i2", "type": "item", "attributes": [], "relationships": []}
+    ],
+    "events": [
+        {"id": "e1", "type": "place order", "time": "2024-01-01T08:00:00Z", "attributes": [{"name": "price", "value": 500.0}], "relationships": [{"objectId": "o1", "qualifier": ""}, {"objectId": "i1", "qualifier": ""}]},
+        {"id": "e2", "type": "place order", "time": "2024-01-02T08:00:00Z", "attributes": [{"name": "price", "value": 2000.0}], "relationships": [{"objectId": "o2", "qualifier": ""}, {"objectId": "i1", "qualifier": ""}, {"objectId": "i2", "qualifier": ""}]},
+        {"id": "e3", "type": "pay order", "time": "2024-01-03T08:00:00Z", "attributes": [], "relationships": [{"objectId": "o1", "qualifier": ""}]},
+        {"id": "e4", "type": "place order", "time": "2024-01-04T08:00:00Z", "attributes": [{"name": "price", "value": 1500.0}], "relationships": [{"objectId": "o3", "qualifier": ""}]},
+        {"id": "e5", "type": "ship order", "time": "2024-01-05T08:00:00Z", "attributes": [], "relationships": [{"objectId": "o1", "qualifier": ""}, {"objectId": "i1", "qualifier": ""}]},
+        {"id": "e6", "type": "ship order", "time": "2024-01-06T08:00:00Z", "attributes": [], "relationships": [{"objectId": "o2", "qualifier": ""}, {"objectId": "i1", "qualifier": ""}, {"objectId": "i2", "qualifier": ""}]},
+        {"id": "e7", "type": "pay order", "time": "2024-01-20T08:00:00Z", "attributes": [], "relationships": [{"objectId": "o3", "qualifier": ""}]},
+        {"id": "e8", "type": "pay order", "time": "2024-01-21T08:00:00Z", "attributes": [], "relationships": [{"objectId": "o3", "qualifier": ""}]}
+    ]
+}"#;
+
+pub(crate) fn test_ocel() -> IndexLinkedOCEL {
+    let ocel = import_ocel_json_from_slice(TEST_OCEL_JSON.as_bytes()).unwrap();
+    preprocess_ocel(ocel)
+}
+
+/// Deterministic pseudo-random order-to-cash OCEL, used for differential tests
+///
+/// Customers place orders with (possibly shared) items, linked through O2O relationships (`customer -> order -> item`).
+/// Orders are paid (sometimes twice or not at all), items are picked and orders are shipped (sometimes before payment),
+/// such that all arc types, labels and count bounds have satisfied and violated source events.
+/// Some events share their timestamp.
+pub(crate) fn generated_ocel() -> IndexLinkedOCEL {
+    preprocess_ocel(generated_raw_ocel())
+}
+
+/// The OCEL of [`generated_ocel`] without preprocessing
+pub(crate) fn generated_raw_ocel() -> OCEL {
+    let mut state: u64 = 42;
+    let mut next = |n: u64| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) % n
+    };
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut objects = Vec::new();
+    let mut events = Vec::new();
+    let add_event = |events: &mut Vec<Value>, ev_type: &str, hours: i64, obs: &[String]| {
+        let rels: Vec<Value> = obs
+            .iter()
+            .map(|o| json!({"objectId": o, "qualifier": ""}))
+            .collect();
+        events.push(json!({
+            "id": format!("e{}", events.len()),
+            "type": ev_type,
+            "time": (start + Duration::hours(hours)).to_rfc3339(),
+            "attributes": [],
+            "relationships": rels,
+        }));
+    };
+    let num_items = 12;
+    for i in 0..num_items {
+        objects.push(
+            json!({"id": format!("i{i}"), "type": "item", "attributes": [], "relationships": []}),
+        );
+    }
+    let num_customers = 5;
+    let mut customer_orders: Vec<Vec<Value>> = vec![Vec::new(); num_customers];
+    for o in 0..30 {
+        let order = format!("o{o}");
+        let customer = next(num_customers as u64) as usize;
+        customer_orders[customer].push(json!({"objectId": order, "qualifier": "places"}));
+        let items: Vec<String> = (0..1 + next(3))
+            .map(|_| format!("i{}", next(num_items)))
+            .unique()
+            .collect();
+        let rels: Vec<Value> = items
+            .iter()
+            .map(|i| json!({"objectId": i, "qualifier": "contains"}))
+            .collect();
+        objects
+            .push(json!({"id": order, "type": "order", "attributes": [], "relationships": rels}));
+        // Orders start every 6 hours, and steps take (a multiple of) 2 hours, leading to equal timestamps
+        let mut t = 6 * o as i64;
+        let mut obs = vec![order.clone(), format!("c{customer}")];
+        obs.extend(items.iter().cloned());
+        add_event(&mut events, "place order", t, &obs);
+        let payments = [0, 1, 1, 1, 2][next(5) as usize];
+        let ship_before_payment = next(6) == 0;
+        if ship_before_payment {
+            t += 2;
+            add_event(&mut events, "ship order", t, std::slice::from_ref(&order));
+        }
+        for _ in 0..payments {
+            t += 2 * (1 + next(3) as i64);
+            add_event(
+                &mut events,
+                "pay order",
+                t,
+                &[order.clone(), format!("c{customer}")],
+            );
+        }
+        for item in &items {
+            if next(4) != 0 {
+                t += 2 * next(2) as i64;
+                add_event(&mut events, "pick item", t, &[order.clone(), item.clone()]);
+            }
+        }
+        if !ship_before_payment && next(5) != 0 {
+            t += 2 * (1 + next(4) as i64);
+            let mut obs = vec![order.clone()];
+            obs.extend(
+                items
+                    .iter()
+                    .take(1 + next(items.len() as u64) as usize)
+                    .cloned(),
+            );
+            add_event(&mut events, "ship order", t, &obs);
+        }
+    }
+    for (c, orders) in customer_orders.into_iter().enumerate() {
+        objects.push(json!({"id": format!("c{c}"), "type": "customer", "attributes": [], "relationships": orders}));
+    }
+    let types = |names: &[&str]| -> Vec<Value> {
+        names
+            .iter()
+            .map(|n| json!({"name": n, "attributes": []}))
+            .collect()
+    };
+    let ocel = json!({
+        "objectTypes": types(&["customer", "order", "item"]),
+        "eventTypes": types(&["place order", "pay order", "pick item", "ship order"]),
+        "objects": objects,
+        "events": events,
+    });
+    import_ocel_json_from_slice(ocel.to_string().as_bytes()).unwrap()
+}
+
+/// Arcs of all types between all activities of [`generated_ocel`], for a range of labels (including O2O associations)
+/// and the given count bounds (e.g., `1,∞`)
+pub(crate) fn generated_arcs(counts: &[&str]) -> Vec<OCDeclareArc> {
+    let acts = ["place order", "pay order", "pick item", "ship order"];
+    let labels = [
+        "",
+        "Each(order)",
+        "All(order)",
+        "Any(item)",
+        "Each(order, customer)",
+        "All(order, item)",
+        "Any(customer, item)",
+        "Each(customer), All(item)",
+        "All(customer), Any(item)",
+        "Each(order>item)",
+        "Any(order<customer)",
+        "Each(item<order), Any(customer)",
+    ];
+    let mut ret = Vec::new();
+    for arc_type in ["AS", "EF", "EP", "DF", "DP"] {
+        for (from, to) in acts.iter().cartesian_product(acts.iter()) {
+            for (label, counts) in labels.iter().cartesian_product(counts.iter()) {
+                let parts = [*from, *to, *label, *counts]
+                    .into_iter()
+                    .filter(|p| !p.is_empty());
+                let constraint = format!("{arc_type}({})", parts.collect::<Vec<_>>().join(", "));
+                ret.push(parse_constraint(&constraint).unwrap().arc);
+            }
+        }
+    }
+    ret
+}
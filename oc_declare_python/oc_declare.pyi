@@ -2,20 +2,123 @@
 # It is generated based on the provided Rust (PyO3) source code.
 # It enables static type checking and IDE autocompletion.
 
-from typing import List, Literal, Optional
+from typing import Any, Callable, Dict, List, Literal, Optional, Tuple, Union
 
 __all__ = [
     "ProcessedOCEL",
     "OCDeclareArc",
+    "ObjectTypeAssociation",
+    "DiscoveryOptions",
     "import_ocel2",
     "discover",
+    "discover_with_removed",
+    "reduce",
     "check_conformance",
+    "check_conformance_many",
+    "violation_report",
+    "violation_table",
+    "parse_model",
+    "model_to_string",
+    "model_from_json",
+    "model_to_json",
+    "diff_models",
+    "detect_drift",
+    "ViolationReport",
+    "EventViolation",
+    "BindingViolation",
+    "BoundObject",
+    "ArcDiff",
+    "RemovedArc",
+    "ArcMetrics",
+    "DriftReport",
+    "DriftPoint",
 ]
 
 class ProcessedOCEL:
     """Pre-Processed OCEL"""
-    # This is an opaque class, typically instantiated by `import_ocel2`
-    ...
+    # This is an opaque class, typically instantiated by `import_ocel2`, `from_dataframes` or `from_pm4py`
+
+    @staticmethod
+    def from_dataframes(
+        events: Any,
+        objects: Any,
+        relations: Any,
+        /,
+        o2o: Optional[Any] = None,
+        object_changes: Optional[Any] = None,
+    ) -> ProcessedOCEL:
+        """
+        Construct and preprocess an OCEL from pandas DataFrames (in the layout of pm4py's `OCEL` class)
+
+        `events` requires the columns `ocel:eid`, `ocel:activity` and `ocel:timestamp`, `objects` the columns `ocel:oid` and `ocel:type`,
+        `relations` (E2O) the columns `ocel:eid`, `ocel:oid` and optionally `ocel:qualifier`,
+        `o2o` the columns `ocel:oid`, `ocel:oid_2` and optionally `ocel:qualifier`
+        and `object_changes` the columns `ocel:oid`, `ocel:timestamp` and `ocel:field` (with the new value in the column of the changed attribute).
+        All other columns (not prefixed with `ocel:`) are used as event/object attributes.
+        """
+        ...
+
+    @staticmethod
+    def from_pm4py(ocel: Any, /) -> ProcessedOCEL:
+        """Construct and preprocess an OCEL from a pm4py `OCEL` object (e.g., after filtering it with pm4py)"""
+        ...
+
+    def to_snapshot(self) -> bytes:
+        """
+        Serialize the pre-processed OCEL to a binary snapshot
+
+        Loading a snapshot with `ProcessedOCEL.from_snapshot` is much faster than importing and pre-processing the original OCEL again.
+        """
+        ...
+
+    @staticmethod
+    def from_snapshot(data: bytes, /) -> ProcessedOCEL:
+        """Load a pre-processed OCEL from a binary snapshot (created by `to_snapshot`)"""
+        ...
+
+    def save_snapshot(self, path: str, /) -> None:
+        """Save a binary snapshot of the pre-processed OCEL to a file"""
+        ...
+
+    @staticmethod
+    def load_snapshot(path: str, /) -> ProcessedOCEL:
+        """Load a pre-processed OCEL from a binary snapshot file (created by `save_snapshot`)"""
+        ...
+
+    def __reduce__(self) -> Tuple[Any, ...]:
+        """Support for pickling (using the binary snapshot format)"""
+        ...
+
+class ObjectTypeAssociation:
+    """How the objects of an object type are associated with the source/target events of an OC-DECLARE arc"""
+
+    class Simple(ObjectTypeAssociation):
+        """Objects of the type directly involved in the event (E2O)"""
+
+        def __init__(self, object_type: str) -> None: ...
+        @property
+        def object_type(self) -> str: ...
+
+    class O2O(ObjectTypeAssociation):
+        """
+        Objects of type `second` related (O2O) to the objects of type `first` involved in the event
+        (with `reversed`, the O2O relationship is followed in reverse direction, i.e., from `second` to `first` objects)
+        """
+
+        def __init__(self, first: str, second: str, reversed: bool = False) -> None: ...
+        @property
+        def first(self) -> str: ...
+        @property
+        def second(self) -> str: ...
+        @property
+        def reversed(self) -> bool: ...
+
+    def __repr__(self) -> str: ...
+    def __str__(self) -> str:
+        """String representation of the association as used in OC-DECLARE arcs (e.g., "orders" or "orders>items")"""
+        ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
 
 class OCDeclareArc:
     """An individual OC-DECLARE constraint arc"""
@@ -28,9 +131,13 @@ class OCDeclareArc:
         min_count: Optional[int],
         max_count: Optional[int],
         /,
-        all_ots: List[str] = ...,
-        each_ots: List[str] = ...,
-        any_ots: List[str] = ...
+        all_ots: List[Union[str, ObjectTypeAssociation]] = ...,
+        each_ots: List[Union[str, ObjectTypeAssociation]] = ...,
+        any_ots: List[Union[str, ObjectTypeAssociation]] = ...,
+        min_delay_ms: Optional[int] = ...,
+        max_delay_ms: Optional[int] = ...,
+        from_predicate: Optional[str] = ...,
+        to_predicate: Optional[str] = ...
     ) -> None:
         """Construct a new OC-DECLARE arc"""
         ...
@@ -42,6 +149,38 @@ class OCDeclareArc:
     def __repr__(self) -> str: ...
     def __str__(self) -> str: ...
 
+    def __eq__(self, other: object) -> bool:
+        """Arcs are equal if their constraints are equal (quality metrics are ignored)"""
+        ...
+
+    def __hash__(self) -> int:
+        """Hash of the serialized constraint, consistent with `__eq__` (quality metrics are ignored)
+
+        Arcs are mutable: the result of mutating an arc while it is in a set or used as a dict key is undefined.
+        """
+        ...
+
+    def __getnewargs__(self) -> Tuple[str, str, str, Optional[int], Optional[int]]:
+        """Arguments for `__new__` when unpickling (the remaining fields are restored by `__setstate__`)"""
+        ...
+
+    def __getstate__(self) -> str:
+        """Support for pickling and copying (state as JSON string)"""
+        ...
+
+    def __setstate__(self, state: str) -> None:
+        """Support for unpickling (from the state returned by `__getstate__`)"""
+        ...
+
+    def to_json(self) -> str:
+        """Serialize the arc (including its quality metrics, if any) to JSON"""
+        ...
+
+    @staticmethod
+    def from_json(json: str, /) -> OCDeclareArc:
+        """Deserialize an arc from JSON (as created by `to_json`)"""
+        ...
+
     @property
     def from_activity(self) -> str:
         """Get the source activity of the arc."""
@@ -73,33 +212,33 @@ class OCDeclareArc:
         ...
 
     @property
-    def all_ots(self) -> List[str]:
-        """Get the object types involved with the 'ALL' quantifier."""
+    def all_ots(self) -> List[ObjectTypeAssociation]:
+        """Get the object type associations involved with the 'ALL' quantifier."""
         ...
 
     @all_ots.setter
-    def all_ots(self, all_ots: List[str]) -> None:
-        """Set the object types involved with the 'ALL' quantifier."""
+    def all_ots(self, all_ots: List[Union[str, ObjectTypeAssociation]]) -> None:
+        """Set the object type associations (or object type names for simple associations) involved with the 'ALL' quantifier."""
         ...
 
     @property
-    def each_ots(self) -> List[str]:
-        """Get the object types involved with the 'EACH' quantifier."""
+    def each_ots(self) -> List[ObjectTypeAssociation]:
+        """Get the object type associations involved with the 'EACH' quantifier."""
         ...
 
     @each_ots.setter
-    def each_ots(self, each_ots: List[str]) -> None:
-        """Set the object types involved with the 'EACH' quantifier."""
+    def each_ots(self, each_ots: List[Union[str, ObjectTypeAssociation]]) -> None:
+        """Set the object type associations (or object type names for simple associations) involved with the 'EACH' quantifier."""
         ...
 
     @property
-    def any_ots(self) -> List[str]:
-        """Get the object types involved with the 'ANY' quantifier."""
+    def any_ots(self) -> List[ObjectTypeAssociation]:
+        """Get the object type associations involved with the 'ANY' quantifier."""
         ...
 
     @any_ots.setter
-    def any_ots(self, any_ots: List[str]) -> None:
-        """Set the object types involved with the 'ANY' quantifier."""
+    def any_ots(self, any_ots: List[Union[str, ObjectTypeAssociation]]) -> None:
+        """Set the object type associations (or object type names for simple associations) involved with the 'ANY' quantifier."""
         ...
 
     @property
@@ -122,9 +261,293 @@ class OCDeclareArc:
         """Set the maximum count for the arc."""
         ...
 
+    @property
+    def min_delay_ms(self) -> Optional[int]:
+        """Get the minimum time distance (in milliseconds) between source and matching target events."""
+        ...
+
+    @min_delay_ms.setter
+    def min_delay_ms(self, min_delay_ms: Optional[int]) -> None:
+        """Set the minimum time distance (in milliseconds) between source and matching target events."""
+        ...
+
+    @property
+    def max_delay_ms(self) -> Optional[int]:
+        """Get the maximum time distance (in milliseconds) between source and matching target events."""
+        ...
+
+    @max_delay_ms.setter
+    def max_delay_ms(self, max_delay_ms: Optional[int]) -> None:
+        """Set the maximum time distance (in milliseconds) between source and matching target events."""
+        ...
+
+    @property
+    def from_predicate(self) -> Optional[str]:
+        """Get the event attribute predicate of the source activity (e.g., "price > 1000")."""
+        ...
+
+    @from_predicate.setter
+    def from_predicate(self, from_predicate: Optional[str]) -> None:
+        """Set the event attribute predicate of the source activity (e.g., "price > 1000")."""
+        ...
+
+    @property
+    def to_predicate(self) -> Optional[str]:
+        """Get the event attribute predicate of the target activity (e.g., "price > 1000")."""
+        ...
+
+    @to_predicate.setter
+    def to_predicate(self, to_predicate: Optional[str]) -> None:
+        """Set the event attribute predicate of the target activity (e.g., "price > 1000")."""
+        ...
+
+    @property
+    def metrics(self) -> Optional[ArcMetrics]:
+        """Get the quality metrics of the arc (only set for arcs discovered with metrics enabled)."""
+        ...
+
+
+class DiscoveryOptions:
+    """Options for discovering OC-DECLARE constraints"""
+
+    def __init__(
+        self,
+        *,
+        noise_threshold: Optional[float] = ...,
+        o2o_mode: Optional[Literal['None', 'Direct', 'Reversed', 'Bidirectional']] = ...,
+        acts_to_use: Optional[List[str]] = ...,
+        counts_for_generation: Optional[Tuple[Optional[int], Optional[int]]] = ...,
+        counts_for_filter: Optional[Tuple[Optional[int], Optional[int]]] = ...,
+        reduction: Optional[Literal['None', 'OcArcs', 'Transitive', 'Entailment']] = ...,
+        time_bounds: Optional[bool] = ...,
+        metrics: Optional[bool] = ...
+    ) -> None:
+        """
+        Construct new discovery options
+
+        Options which are not specified use the default values.
+        """
+        ...
+
+    def __repr__(self) -> str: ...
+
+    @property
+    def noise_threshold(self) -> float:
+        """Maximum fraction of violating source events for a discovered constraint."""
+        ...
+
+    @noise_threshold.setter
+    def noise_threshold(self, noise_threshold: float) -> None:
+        """Set the maximum fraction of violating source events for a discovered constraint."""
+        ...
+
+    @property
+    def o2o_mode(self) -> Literal['None', 'Direct', 'Reversed', 'Bidirectional']:
+        """Which object-to-object (O2O) relationships to use ('None', 'Direct', 'Reversed' or 'Bidirectional')."""
+        ...
+
+    @o2o_mode.setter
+    def o2o_mode(self, o2o_mode: Literal['None', 'Direct', 'Reversed', 'Bidirectional']) -> None:
+        """Set which object-to-object (O2O) relationships to use ('None', 'Direct', 'Reversed' or 'Bidirectional')."""
+        ...
+
+    @property
+    def acts_to_use(self) -> Optional[List[str]]:
+        """Activities to consider (all activities if None)."""
+        ...
+
+    @acts_to_use.setter
+    def acts_to_use(self, acts_to_use: Optional[List[str]]) -> None:
+        """Set the activities to consider (all activities if None)."""
+        ...
+
+    @property
+    def counts_for_generation(self) -> Tuple[Optional[int], Optional[int]]:
+        """(min, max) counts used for generating candidate constraints."""
+        ...
+
+    @counts_for_generation.setter
+    def counts_for_generation(self, counts_for_generation: Tuple[Optional[int], Optional[int]]) -> None:
+        """Set the (min, max) counts used for generating candidate constraints."""
+        ...
+
+    @property
+    def counts_for_filter(self) -> Tuple[Optional[int], Optional[int]]:
+        """(min, max) counts used for filtering candidate constraints."""
+        ...
+
+    @counts_for_filter.setter
+    def counts_for_filter(self, counts_for_filter: Tuple[Optional[int], Optional[int]]) -> None:
+        """Set the (min, max) counts used for filtering candidate constraints."""
+        ...
+
+    @property
+    def reduction(self) -> Literal['None', 'OcArcs', 'Transitive', 'Entailment']:
+        """Reduction applied to the discovered constraints ('None', 'OcArcs', 'Transitive' or 'Entailment')."""
+        ...
+
+    @reduction.setter
+    def reduction(self, reduction: Literal['None', 'OcArcs', 'Transitive', 'Entailment']) -> None:
+        """Set the reduction applied to the discovered constraints ('None', 'OcArcs', 'Transitive' or 'Entailment')."""
+        ...
+
+    @property
+    def time_bounds(self) -> bool:
+        """Whether time bounds are proposed for the discovered EF/EP/DF/DP constraints."""
+        ...
+
+    @time_bounds.setter
+    def time_bounds(self, time_bounds: bool) -> None:
+        """Set whether time bounds are proposed for the discovered EF/EP/DF/DP constraints."""
+        ...
+
+    @property
+    def metrics(self) -> bool:
+        """Whether quality metrics are computed for the discovered constraints (see `OCDeclareArc.metrics`)."""
+        ...
+
+    @metrics.setter
+    def metrics(self, metrics: bool) -> None:
+        """Set whether quality metrics are computed for the discovered constraints (see `OCDeclareArc.metrics`)."""
+        ...
+
+
+class BoundObject:
+    """An object bound in the label of a violated OC-DECLARE arc"""
+    object_id: str
+    """Object ID"""
+    object_type: str
+    """Object type of the object"""
+    association: str
+    """Object type association (as template string) through which the object was bound"""
+
+class BindingViolation:
+    """A violated object binding of a source event"""
+    each_objects: List[BoundObject]
+    """The objects bound through the 'EACH' quantifier (one per 'EACH' object type association)"""
+    all_objects: List[BoundObject]
+    """The objects bound through the 'ALL' quantifier"""
+    any_objects: List[BoundObject]
+    """The objects bound through the 'ANY' quantifier"""
+    matching_events: List[str]
+    """IDs of the matching target events"""
+    observed_count: int
+    """Observed number of matching target events"""
+    min_count: Optional[int]
+    """Required minimum number of matching target events"""
+    max_count: Optional[int]
+    """Required maximum number of matching target events"""
+    kind: Literal['TooFew', 'TooMany']
+    """Kind of the violation"""
+
+class EventViolation:
+    """A source event violating an OC-DECLARE arc"""
+    source_event: str
+    """ID of the source event"""
+    bindings: List[BindingViolation]
+    """All violated bindings of the source event"""
+
+class ViolationReport:
+    """Violation report of an OC-DECLARE arc, listing all violating source events"""
+    num_source_events: int
+    """Number of checked source events"""
+    num_violating_events: int
+    """Number of violating source events"""
+    violation_fraction: float
+    """Fraction of violating source events"""
+    violations: List[EventViolation]
+    """All violating source events"""
+    def __repr__(self) -> str: ...
+
+class ArcDiff:
+    """Change of a single OC-DECLARE arc between an old and a new model"""
+    kind: Literal['Added', 'Removed', 'Unchanged', 'Strengthened', 'Weakened', 'Modified']
+    """Kind of the change"""
+    old_index: Optional[int]
+    """Index of the arc in the old model (None for added arcs)"""
+    new_index: Optional[int]
+    """Index of the arc in the new model (None for removed arcs)"""
+    old_arc: Optional[OCDeclareArc]
+    """The arc in the old model (None for added arcs)"""
+    new_arc: Optional[OCDeclareArc]
+    """The arc in the new model (None for removed arcs)"""
+    changed_aspects: List[Literal['ArcType', 'Label', 'Counts', 'TimeBounds', 'Predicates']]
+    """Changed aspects of the arc"""
+    def __repr__(self) -> str: ...
+
+class RemovedArc:
+    """An OC-DECLARE arc removed by a reduction, together with the two arcs implying it"""
+    arc: OCDeclareArc
+    """The removed arc (a -> c)"""
+    a: OCDeclareArc
+    """The implying arc starting at the source activity of the removed arc (a -> b)"""
+    b: OCDeclareArc
+    """The implying arc ending at the target activity of the removed arc (b -> c)"""
+    rule: Literal['DominatedByPair', 'StrictlyDominatedByPair', 'FollowsChain', 'PrecedesChain', 'AssociationChain']
+    """Rule by which the arc was removed"""
+    explanation: str
+    """Human-readable explanation why the arc was removed"""
+    def __repr__(self) -> str: ...
+
+class ArcMetrics:
+    """Quality metrics of a discovered OC-DECLARE arc"""
+    num_source_events: int
+    """Number of source events the arc applies to"""
+    num_satisfying_events: int
+    """Number of source events satisfying the arc"""
+    violation_fraction: Optional[float]
+    """Fraction of violating source events (None if there are no source events)"""
+    lift: Optional[float]
+    """
+    Fraction of satisfying source events divided by the fraction of all events with the target activity,
+    i.e., `(num_satisfying_events / num_source_events) / (num_target_events / num_events)`,
+    counting only real events (not the `<init>`/`<exit>` events added by the pre-processing)
+    (None if there are no source events or no real events of the target activity)
+    """
+    def __repr__(self) -> str: ...
+
+class DriftPoint:
+    """Conformance of one OC-DECLARE arc in one time window"""
+    constraint: int
+    """Index of the arc in the checked model"""
+    window: int
+    """Index of the time window"""
+    window_start: str
+    """Start of the time window (RFC 3339, inclusive)"""
+    window_end: str
+    """End of the time window (RFC 3339, exclusive)"""
+    num_source_events: int
+    """Number of source events in the window"""
+    num_violating_events: int
+    """Number of violating source events in the window"""
+    violation_fraction: Optional[float]
+    """Fraction of violating source events (None if there are no source events in the window)"""
+    holds: bool
+    """Whether the arc holds in the window"""
+    changes: List[Literal['ViolationIncrease', 'ViolationDecrease', 'Emerged', 'Disappeared']]
+    """Changes compared to the previous window with source events"""
+
+class DriftReport:
+    """Conformance of a set of OC-DECLARE arcs over time windows"""
+    @property
+    def points(self) -> List[DriftPoint]:
+        """All points of the time series (ordered by arc, then window)"""
+        ...
+    @property
+    def num_windows(self) -> int:
+        """Number of time windows"""
+        ...
+    def to_records(self) -> List[Dict[str, Any]]:
+        """Get the time series as a list of dicts (one per arc and window), e.g., for `pandas.DataFrame`"""
+        ...
+    def to_json(self) -> str:
+        """Get the JSON representation of the report (as used by the editor)"""
+        ...
+    def __repr__(self) -> str: ...
+
 
 def import_ocel2(path: str, /) -> ProcessedOCEL:
-    """Import an OCEL 2.0 file (.xml or .json) and preprocess it for use with OC-DECLARE"""
+    """Import an OCEL 2.0 file (.xml, .json or .sqlite) and preprocess it for use with OC-DECLARE"""
     ...
 
 def discover(
@@ -132,9 +555,36 @@ def discover(
     /,
     noise_thresh: float = ...,
     acts_to_use: Optional[List[str]] = ...,
-    o2o_mode: Optional[Literal['None', 'Direct', 'Reversed', 'Bidirectional']] = ...
+    o2o_mode: Optional[Literal['None', 'Direct', 'Reversed', 'Bidirectional']] = ...,
+    options: Optional[DiscoveryOptions] = ...
 ) -> List[OCDeclareArc]:
-    """Discover OC-DECLARE constraints given a pre-processed OCEL and a noise threshold"""
+    """
+    Discover OC-DECLARE constraints given a pre-processed OCEL and a noise threshold
+
+    If `options` are passed, they are applied as-is (including the reduction) and the other parameters are ignored.
+    """
+    ...
+
+def discover_with_removed(
+    processed_ocel: ProcessedOCEL, /, options: DiscoveryOptions
+) -> Tuple[List[OCDeclareArc], List[RemovedArc]]:
+    """
+    Discover OC-DECLARE constraints using the given options,
+    additionally returning the arcs removed by the reduction and why they were removed
+    """
+    ...
+
+def reduce(
+    constraints: List[OCDeclareArc],
+    /,
+    reduction: Literal['None', 'OcArcs', 'Transitive', 'Entailment'] = ...,
+) -> Tuple[List[OCDeclareArc], List[RemovedArc]]:
+    """
+    Reduce OC-DECLARE constraints by removing arcs implied by other arcs,
+    returning the remaining constraints and the removed arcs together with why they were removed
+
+    Constraints with additional restrictions (e.g., time bounds) are kept as is.
+    """
     ...
 
 def check_conformance(
@@ -146,4 +596,98 @@ def check_conformance(
 
     Returns 1 if all source events fulfill the constraint and 0 if all source events violate the constraint.
     """
-    ...
\ No newline at end of file
+    ...
+
+def check_conformance_many(
+    processed_ocel: ProcessedOCEL,
+    constraints: List[OCDeclareArc],
+    /,
+    progress: Optional[Callable[[int, int], None]] = None,
+) -> List[float]:
+    """
+    Evaluate multiple OC-DECLARE constraints given a pre-processed OCEL
+    yielding the fraction of relevant events satisfying each constraint (as in `check_conformance`)
+
+    The constraints are evaluated in parallel, without holding the GIL.
+    If given, `progress` is called with the number of evaluated constraints and the total number of constraints
+    every time a constraint was evaluated.
+    """
+    ...
+
+def violation_report(
+    processed_ocel: ProcessedOCEL, constraint: OCDeclareArc, /
+) -> ViolationReport:
+    """
+    Evaluate an OC-DECLARE constraint given a pre-processed OCEL
+    yielding a detailed report of all violating source events and the object bindings causing the violation
+    """
+    ...
+
+def violation_table(
+    processed_ocel: ProcessedOCEL,
+    constraints: List[OCDeclareArc],
+    /,
+    output: Literal["pyarrow", "pandas"] = "pyarrow",
+) -> Any:
+    """
+    Evaluate OC-DECLARE constraints given a pre-processed OCEL
+    yielding a table with one row per constraint and source event (as a `pyarrow.Table` or `pandas.DataFrame`)
+
+    Columns: `constraint_index`, `constraint`, `event_id`, `activity`, `timestamp` (UTC), `objects` (all objects of the source event),
+    `required_min`, `required_max` (None for ∞), `min_observed_count`/`max_observed_count` (over all bindings of the source event),
+    `num_bindings`, `num_violated_bindings` and `violated`.
+    The constraints are evaluated in parallel, without holding the GIL.
+    """
+    ...
+
+def parse_model(model_text: str, /) -> List[OCDeclareArc]:
+    """
+    Parse OC-DECLARE constraints from the textual model format (one constraint per line)
+
+    Each line uses the same syntax as the string representation of an OC-DECLARE arc, e.g.,
+    `EF(Load Truck, Depart, Each(orders), All(items),1,∞)`.
+    Empty lines and lines starting with `#` are ignored.
+    """
+    ...
+
+def model_to_string(constraints: List[OCDeclareArc], /) -> str:
+    """Convert OC-DECLARE constraints to the textual model format (one constraint per line)"""
+    ...
+
+def model_from_json(json: str, /) -> List[OCDeclareArc]:
+    """Parse OC-DECLARE constraints from a JSON model (a list of arcs, as written by the CLI or `model_to_json`)"""
+    ...
+
+def model_to_json(constraints: List[OCDeclareArc], /) -> str:
+    """Convert OC-DECLARE constraints to a JSON model (a list of arcs, including their quality metrics, if any)"""
+    ...
+
+def diff_models(
+    old_constraints: List[OCDeclareArc], new_constraints: List[OCDeclareArc], /
+) -> List[ArcDiff]:
+    """
+    Compute the semantic diff between an old and a new OC-DECLARE model
+
+    Arcs are matched by their activity pair and arc type, and matched arcs are classified as unchanged, strengthened, weakened or modified.
+    Returns one entry for every arc of the old model (in order), followed by the arcs added in the new model.
+    """
+    ...
+
+def detect_drift(
+    processed_ocel: ProcessedOCEL,
+    constraints: List[OCDeclareArc],
+    /,
+    window_ms: int,
+    step_ms: Optional[int] = None,
+    noise_threshold: float = 0.2,
+    z_threshold: float = 1.96,
+) -> DriftReport:
+    """
+    Evaluate OC-DECLARE constraints per time window to detect concept drift
+
+    The log is split into windows of `window_ms` milliseconds, starting every `step_ms` milliseconds (default: `window_ms`, i.e., non-overlapping windows).
+    Changes of the violation rate between consecutive windows are flagged if the absolute z-score of a two-proportion z-test is at least `z_threshold`.
+    An arc holds in a window if its violation fraction is at most `noise_threshold`.
+    Raises a `ValueError` for non-positive window lengths or steps, and for more than 10000 windows.
+    """
+    ...
@@ -1,24 +1,548 @@
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use chrono::{DateTime, FixedOffset};
 use pyo3::{
     exceptions::{PyIOError, PyValueError},
     prelude::*,
+    types::{PyBool, PyBytes, PyDict, PyFloat, PyInt, PyString},
 };
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use shared::{
-    process_mining::{self, ocel::linked_ocel::IndexLinkedOCEL},
-    O2OMode, OCDeclareArcLabel, OCDeclareArcType, OCDeclareDiscoveryOptions, OCDeclareNode,
-    ObjectTypeAssociation,
+    constraint::{OCDeclareConstraint, TimeBounds},
+    diagnostics::{get_source_event_conformance, SourceEventConformance},
+    discovery::{discover_with_provenance, DiscoveryO2OMode},
+    predicate::EventPredicate,
+    process_mining::{
+        self,
+        ocel::{linked_ocel::IndexLinkedOCEL, ocel_struct::OCELAttributeValue},
+    },
+    reduction::ReductionMode,
+    tables::{
+        ocel_from_tables, parse_timestamp, EventRow, ObjectChangeRow, ObjectRow, RelationRow,
+    },
+    template::{parse_predicate, print_association, print_constraint, print_predicate},
+    OCDeclareArcLabel, OCDeclareArcType, OCDeclareNode,
 };
 
-#[pyclass]
+#[pyclass(module = "oc_declare")]
 /// Pre-Processed OCEL
 struct ProcessedOCEL {
     locel: IndexLinkedOCEL,
 }
 
+/// Names of the special columns of the DataFrames (defaults as used by pm4py)
+struct ColumnNames {
+    event_id: String,
+    activity: String,
+    timestamp: String,
+    object_id: String,
+    object_type: String,
+    qualifier: String,
+    changed_field: String,
+}
+
+impl Default for ColumnNames {
+    fn default() -> Self {
+        Self {
+            event_id: "ocel:eid".to_string(),
+            activity: "ocel:activity".to_string(),
+            timestamp: "ocel:timestamp".to_string(),
+            object_id: "ocel:oid".to_string(),
+            object_type: "ocel:type".to_string(),
+            qualifier: "ocel:qualifier".to_string(),
+            changed_field: "ocel:field".to_string(),
+        }
+    }
+}
+
+impl ColumnNames {
+    /// Column names configured on a pm4py `OCEL` object
+    fn from_pm4py(ocel: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let mut ret = Self::default();
+        for (attr, name) in [
+            ("event_id_column", &mut ret.event_id),
+            ("event_activity", &mut ret.activity),
+            ("event_timestamp", &mut ret.timestamp),
+            ("object_id_column", &mut ret.object_id),
+            ("object_type_column", &mut ret.object_type),
+            ("qualifier", &mut ret.qualifier),
+            ("changed_field", &mut ret.changed_field),
+        ] {
+            if ocel.hasattr(attr)? {
+                *name = ocel.getattr(attr)?.extract()?;
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Whether a column holds attribute values (i.e., is neither special nor prefixed with `ocel:`)
+    fn is_attribute(&self, column: &str) -> bool {
+        !column.starts_with("ocel:")
+            && ![
+                &self.event_id,
+                &self.activity,
+                &self.timestamp,
+                &self.object_id,
+                &self.object_type,
+                &self.qualifier,
+                &self.changed_field,
+            ]
+            .iter()
+            .any(|c| c.as_str() == column)
+    }
+}
+
+/// The columns of a pandas DataFrame as lists of Python values
+struct DataFrame<'py> {
+    len: usize,
+    columns: Vec<(String, Vec<Bound<'py, PyAny>>)>,
+}
+
+impl<'py> DataFrame<'py> {
+    fn new(df: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let mut columns = Vec::new();
+        for column in df.getattr("columns")?.try_iter()? {
+            let column = column?;
+            let values = df.get_item(&column)?.call_method0("tolist")?.extract()?;
+            columns.push((column.str()?.to_string(), values));
+        }
+        Ok(Self {
+            len: df.len()?,
+            columns,
+        })
+    }
+
+    fn column(&self, name: &str) -> PyResult<&[Bound<'py, PyAny>]> {
+        self.columns
+            .iter()
+            .find(|(c, _)| c == name)
+            .map(|(_, values)| values.as_slice())
+            .ok_or_else(|| PyErr::new::<PyValueError, _>(format!("Missing column '{name}'.")))
+    }
+
+    fn strings(&self, name: &str) -> PyResult<Vec<String>> {
+        self.column(name)?
+            .iter()
+            .map(|v| Ok(v.str()?.to_string()))
+            .collect()
+    }
+
+    /// Non-missing attribute values of every row
+    fn attributes(
+        &self,
+        columns: &ColumnNames,
+    ) -> PyResult<Vec<Vec<(String, OCELAttributeValue)>>> {
+        let mut ret = vec![Vec::new(); self.len];
+        for (name, values) in self.columns.iter().filter(|(c, _)| columns.is_attribute(c)) {
+            for (row, value) in ret.iter_mut().zip(values) {
+                if let Some(value) = to_attribute_value(value)? {
+                    row.push((name.clone(), value));
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    fn timestamps(&self, name: &str) -> PyResult<Vec<DateTime<FixedOffset>>> {
+        self.column(name)?
+            .iter()
+            .map(|v| {
+                to_timestamp(v)?.ok_or_else(|| {
+                    PyErr::new::<PyValueError, _>(format!(
+                        "Invalid timestamp {v} in column '{name}'."
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Relationship rows (missing qualifiers become empty strings)
+    fn relations(&self, source: &str, target: &str, qualifier: &str) -> PyResult<Vec<RelationRow>> {
+        let qualifiers: Vec<String> = match self.column(qualifier) {
+            Ok(values) => values
+                .iter()
+                .map(|v| match to_attribute_value(v)? {
+                    Some(_) => Ok(v.str()?.to_string()),
+                    None => Ok(String::new()),
+                })
+                .collect::<PyResult<_>>()?,
+            Err(_) => vec![String::new(); self.len],
+        };
+        Ok(self
+            .strings(source)?
+            .into_iter()
+            .zip(self.strings(target)?)
+            .zip(qualifiers)
+            .map(|((source_id, object_id), qualifier)| RelationRow {
+                source_id,
+                object_id,
+                qualifier,
+            })
+            .collect())
+    }
+}
+
+/// Convert a Python value to an attribute value (`None` for missing values, i.e., `None`, `NaN` or `NaT`)
+fn to_attribute_value(value: &Bound<'_, PyAny>) -> PyResult<Option<OCELAttributeValue>> {
+    if value.is_none() {
+        Ok(None)
+    } else if value.is_instance_of::<PyBool>() {
+        Ok(Some(OCELAttributeValue::Boolean(value.extract()?)))
+    } else if value.is_instance_of::<PyInt>() {
+        Ok(Some(match value.extract::<i64>() {
+            Ok(i) => OCELAttributeValue::Integer(i),
+            Err(_) => OCELAttributeValue::Float(value.extract()?),
+        }))
+    } else if let Ok(f) = value.cast::<PyFloat>() {
+        let f = f.value();
+        Ok((!f.is_nan()).then_some(OCELAttributeValue::Float(f)))
+    } else if let Ok(s) = value.cast::<PyString>() {
+        Ok(Some(OCELAttributeValue::String(s.to_string())))
+    } else if value.hasattr("isoformat")? {
+        Ok(to_timestamp(value)?.map(OCELAttributeValue::Time))
+    } else {
+        Ok(Some(OCELAttributeValue::String(value.str()?.to_string())))
+    }
+}
+
+/// Convert a Python `datetime`/`pandas.Timestamp` or ISO 8601 string to a timestamp (`None` for `NaT`/`None`)
+fn to_timestamp(value: &Bound<'_, PyAny>) -> PyResult<Option<DateTime<FixedOffset>>> {
+    if value.is_none() {
+        return Ok(None);
+    }
+    let s: String = if value.is_instance_of::<PyString>() {
+        value.extract()?
+    } else {
+        value.call_method0("isoformat")?.extract()?
+    };
+    Ok(parse_timestamp(&s))
+}
+
+impl ProcessedOCEL {
+    fn from_dataframes_with_columns(
+        py: Python<'_>,
+        columns: &ColumnNames,
+        events: &Bound<'_, PyAny>,
+        objects: &Bound<'_, PyAny>,
+        relations: &Bound<'_, PyAny>,
+        o2o: Option<&Bound<'_, PyAny>>,
+        object_changes: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        let events_df = DataFrame::new(events)?;
+        let events: Vec<EventRow> = events_df
+            .strings(&columns.event_id)?
+            .into_iter()
+            .zip(events_df.strings(&columns.activity)?)
+            .zip(events_df.timestamps(&columns.timestamp)?)
+            .zip(events_df.attributes(columns)?)
+            .map(|(((id, activity), time), attributes)| EventRow {
+                id,
+                activity,
+                time,
+                attributes,
+            })
+            .collect();
+        let objects_df = DataFrame::new(objects)?;
+        let objects: Vec<ObjectRow> = objects_df
+            .strings(&columns.object_id)?
+            .into_iter()
+            .zip(objects_df.strings(&columns.object_type)?)
+            .zip(objects_df.attributes(columns)?)
+            .map(|((id, object_type), attributes)| ObjectRow {
+                id,
+                object_type,
+                attributes,
+            })
+            .collect();
+        let e2o = DataFrame::new(relations)?.relations(
+            &columns.event_id,
+            &columns.object_id,
+            &columns.qualifier,
+        )?;
+        let o2o = match o2o {
+            Some(o2o) => DataFrame::new(o2o)?.relations(
+                &columns.object_id,
+                &format!("{}_2", columns.object_id),
+                &columns.qualifier,
+            )?,
+            None => Vec::new(),
+        };
+        let mut changes = Vec::new();
+        if let Some(object_changes) = object_changes {
+            let df = DataFrame::new(object_changes)?;
+            let ids = df.strings(&columns.object_id)?;
+            let times = df.timestamps(&columns.timestamp)?;
+            let fields = df.strings(&columns.changed_field)?;
+            for (i, ((object_id, time), field)) in
+                ids.into_iter().zip(times).zip(fields).enumerate()
+            {
+                // The new value is stored in the column of the changed attribute
+                let value = match df.column(&field) {
+                    Ok(values) => to_attribute_value(&values[i])?,
+                    Err(_) => None,
+                };
+                if let Some(value) = value {
+                    changes.push(ObjectChangeRow {
+                        object_id,
+                        time,
+                        field,
+                        value,
+                    });
+                }
+            }
+        }
+        let locel = py.detach(|| {
+            shared::preprocess_ocel(ocel_from_tables(events, objects, e2o, o2o, changes))
+        });
+        Ok(Self { locel })
+    }
+}
+
+#[pymethods]
+impl ProcessedOCEL {
+    #[staticmethod]
+    #[pyo3(signature = (events: "pandas.DataFrame", objects: "pandas.DataFrame", relations: "pandas.DataFrame", /, o2o: "Optional[pandas.DataFrame]" = None, object_changes: "Optional[pandas.DataFrame]" = None) -> "ProcessedOCEL")]
+    /// Construct and preprocess an OCEL from pandas DataFrames (in the layout of pm4py's `OCEL` class)
+    ///
+    /// `events` requires the columns `ocel:eid`, `ocel:activity` and `ocel:timestamp`, `objects` the columns `ocel:oid` and `ocel:type`,
+    /// `relations` (E2O) the columns `ocel:eid`, `ocel:oid` and optionally `ocel:qualifier`,
+    /// `o2o` the columns `ocel:oid`, `ocel:oid_2` and optionally `ocel:qualifier`
+    /// and `object_changes` the columns `ocel:oid`, `ocel:timestamp` and `ocel:field` (with the new value in the column of the changed attribute).
+    /// All other columns (not prefixed with `ocel:`) are used as event/object attributes.
+    pub fn from_dataframes(
+        py: Python<'_>,
+        events: &Bound<'_, PyAny>,
+        objects: &Bound<'_, PyAny>,
+        relations: &Bound<'_, PyAny>,
+        o2o: Option<&Bound<'_, PyAny>>,
+        object_changes: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        Self::from_dataframes_with_columns(
+            py,
+            &ColumnNames::default(),
+            events,
+            objects,
+            relations,
+            o2o,
+            object_changes,
+        )
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (ocel: "pm4py.objects.ocel.obj.OCEL", /) -> "ProcessedOCEL")]
+    /// Construct and preprocess an OCEL from a pm4py `OCEL` object (e.g., after filtering it with pm4py)
+    pub fn from_pm4py(py: Python<'_>, ocel: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let columns = ColumnNames::from_pm4py(ocel)?;
+        let optional = |attr: &str| -> PyResult<Option<Bound<'_, PyAny>>> {
+            if ocel.hasattr(attr)? {
+                Ok(Some(ocel.getattr(attr)?).filter(|df| !df.is_none()))
+            } else {
+                Ok(None)
+            }
+        };
+        Self::from_dataframes_with_columns(
+            py,
+            &columns,
+            &ocel.getattr("events")?,
+            &ocel.getattr("objects")?,
+            &ocel.getattr("relations")?,
+            optional("o2o")?.as_ref(),
+            optional("object_changes")?.as_ref(),
+        )
+    }
+
+    #[pyo3(signature = () -> "bytes")]
+    /// Serialize the pre-processed OCEL to a binary snapshot
+    ///
+    /// Loading a snapshot with `ProcessedOCEL.from_snapshot` is much faster than importing and pre-processing the original OCEL again.
+    pub fn to_snapshot<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let data = py.detach(|| shared::snapshot::write_snapshot(&self.locel));
+        PyBytes::new(py, &data)
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (data: "bytes", /) -> "ProcessedOCEL")]
+    /// Load a pre-processed OCEL from a binary snapshot (created by `to_snapshot`)
+    pub fn from_snapshot(py: Python<'_>, data: &[u8]) -> PyResult<Self> {
+        let locel = py
+            .detach(|| shared::snapshot::read_snapshot(data))
+            .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+        Ok(Self { locel })
+    }
+
+    #[pyo3(signature = (path: "str", /) -> "None")]
+    /// Save a binary snapshot of the pre-processed OCEL to a file
+    pub fn save_snapshot(&self, py: Python<'_>, path: String) -> PyResult<()> {
+        py.detach(|| std::fs::write(path, shared::snapshot::write_snapshot(&self.locel)))
+            .map_err(|e| PyErr::new::<PyIOError, _>(e.to_string()))
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (path: "str", /) -> "ProcessedOCEL")]
+    /// Load a pre-processed OCEL from a binary snapshot file (created by `save_snapshot`)
+    pub fn load_snapshot(py: Python<'_>, path: String) -> PyResult<Self> {
+        let data = py
+            .detach(|| std::fs::read(path))
+            .map_err(|e| PyErr::new::<PyIOError, _>(e.to_string()))?;
+        Self::from_snapshot(py, &data)
+    }
+
+    /// Support for pickling (using the binary snapshot format)
+    pub fn __reduce__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let from_snapshot = py.get_type::<Self>().getattr("from_snapshot")?;
+        (from_snapshot, (self.to_snapshot(py),))
+            .into_pyobject(py)
+            .map(|t| t.into_any())
+    }
+}
+
+fn parse_optional_predicate(predicate: Option<String>) -> PyResult<Option<EventPredicate>> {
+    predicate
+        .map(|p| parse_predicate(&p).map_err(|e| PyErr::new::<PyValueError, _>(e.to_string())))
+        .transpose()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[pyclass(module = "oc_declare", eq, hash, frozen)]
+/// How the objects of an object type are associated with the source/target events of an OC-DECLARE arc
+enum ObjectTypeAssociation {
+    /// Objects of the type directly involved in the event (E2O)
+    Simple { object_type: String },
+    /// Objects of type `second` related (O2O) to the objects of type `first` involved in the event
+    /// (with `reversed`, the O2O relationship is followed in reverse direction, i.e., from `second` to `first` objects)
+    #[pyo3(constructor = (first, second, reversed = false))]
+    O2O {
+        first: String,
+        second: String,
+        reversed: bool,
+    },
+}
+
+impl From<shared::ObjectTypeAssociation> for ObjectTypeAssociation {
+    fn from(value: shared::ObjectTypeAssociation) -> Self {
+        match value {
+            shared::ObjectTypeAssociation::Simple { object_type } => Self::Simple { object_type },
+            shared::ObjectTypeAssociation::O2O {
+                first,
+                second,
+                reversed,
+            } => Self::O2O {
+                first,
+                second,
+                reversed,
+            },
+        }
+    }
+}
+
+impl From<ObjectTypeAssociation> for shared::ObjectTypeAssociation {
+    fn from(value: ObjectTypeAssociation) -> Self {
+        match value {
+            ObjectTypeAssociation::Simple { object_type } => Self::Simple { object_type },
+            ObjectTypeAssociation::O2O {
+                first,
+                second,
+                reversed,
+            } => Self::O2O {
+                first,
+                second,
+                reversed,
+            },
+        }
+    }
+}
+
+#[pymethods]
+impl ObjectTypeAssociation {
+    pub fn __repr__(&self) -> String {
+        format!("ObjectTypeAssociation: {}", self.__str__())
+    }
+
+    /// String representation of the association as used in OC-DECLARE arcs (e.g., "orders" or "orders>items")
+    pub fn __str__(&self) -> String {
+        print_association(&self.clone().into())
+    }
+}
+
+/// An object type association given either as object type name (i.e., a simple association) or as [`ObjectTypeAssociation`]
+#[derive(FromPyObject)]
+enum AssociationArg {
+    Association(ObjectTypeAssociation),
+    ObjectType(String),
+}
+
+fn to_associations(ots: Vec<AssociationArg>) -> Vec<shared::ObjectTypeAssociation> {
+    ots.into_iter()
+        .map(|ot| match ot {
+            AssociationArg::Association(a) => a.into(),
+            AssociationArg::ObjectType(object_type) => {
+                shared::ObjectTypeAssociation::Simple { object_type }
+            }
+        })
+        .collect()
+}
+
+fn from_associations(ots: &[shared::ObjectTypeAssociation]) -> Vec<ObjectTypeAssociation> {
+    ots.iter()
+        .cloned()
+        .map(ObjectTypeAssociation::from)
+        .collect()
+}
+
 #[derive(Debug, Clone)]
-#[pyclass]
+#[pyclass(module = "oc_declare")]
 /// An individual OC-DECLARE constraint arc
 struct OCDeclareArc {
-    arc: shared::OCDeclareArc,
+    constraint: OCDeclareConstraint,
+    /// Quality metrics (only set for arcs discovered with metrics enabled)
+    metrics: Option<ArcMetrics>,
+}
+
+impl From<OCDeclareConstraint> for OCDeclareArc {
+    fn from(constraint: OCDeclareConstraint) -> Self {
+        Self {
+            constraint,
+            metrics: None,
+        }
+    }
+}
+
+/// JSON representation of an [`OCDeclareArc`]
+///
+/// Same format as the JSON models of the CLI, with the quality metrics (if any) as an additional field.
+#[derive(Serialize, Deserialize)]
+struct ArcJson {
+    #[serde(flatten)]
+    constraint: OCDeclareConstraint,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metrics: Option<shared::discovery::ArcMetrics>,
+}
+
+impl From<&OCDeclareArc> for ArcJson {
+    fn from(arc: &OCDeclareArc) -> Self {
+        Self {
+            constraint: arc.constraint.clone(),
+            metrics: arc
+                .metrics
+                .as_ref()
+                .map(shared::discovery::ArcMetrics::from),
+        }
+    }
+}
+
+impl From<ArcJson> for OCDeclareArc {
+    fn from(value: ArcJson) -> Self {
+        Self {
+            constraint: value.constraint,
+            metrics: value.metrics.map(ArcMetrics::from),
+        }
+    }
+}
+
+fn json_error(e: serde_json::Error) -> PyErr {
+    PyErr::new::<PyValueError, _>(format!("Invalid JSON: {e}"))
 }
 
 #[pymethods]
@@ -26,32 +550,27 @@ impl OCDeclareArc {
     #[new]
     /// Construct a new OC-DECLARE arc
     ///
-    #[pyo3(signature = (from_act: "str", to_act: "str", arc_type: "Literal['AS', 'EF', 'EP', 'DF', 'DP']", min_count: "Optional[int]", max_count: "Optional[int]", /, all_ots: "list[str]"= vec![], each_ots: "list[str]"= vec![], any_ots: "list[str]"= vec![]) -> "OCDeclareArc")]
+    #[pyo3(signature = (from_act: "str", to_act: "str", arc_type: "Literal['AS', 'EF', 'EP', 'DF', 'DP']", min_count: "Optional[int]", max_count: "Optional[int]", /, all_ots: "list[Union[str, ObjectTypeAssociation]]"= vec![], each_ots: "list[Union[str, ObjectTypeAssociation]]"= vec![], any_ots: "list[Union[str, ObjectTypeAssociation]]"= vec![], min_delay_ms: "Optional[int]" = None, max_delay_ms: "Optional[int]" = None, from_predicate: "Optional[str]" = None, to_predicate: "Optional[str]" = None) -> "OCDeclareArc")]
     pub fn new(
         from_act: String,
         to_act: String,
         arc_type: String,
         min_count: Option<usize>,
         max_count: Option<usize>,
-        all_ots: Vec<String>,
-        each_ots: Vec<String>,
-        any_ots: Vec<String>,
+        all_ots: Vec<AssociationArg>,
+        each_ots: Vec<AssociationArg>,
+        any_ots: Vec<AssociationArg>,
+        min_delay_ms: Option<i64>,
+        max_delay_ms: Option<i64>,
+        from_predicate: Option<String>,
+        to_predicate: Option<String>,
     ) -> PyResult<Self> {
         let arc_type = OCDeclareArcType::parse_str(&arc_type)
             .ok_or(PyErr::new::<PyValueError, _>("Invalid arc type."))?;
         let label = OCDeclareArcLabel {
-            each: each_ots
-                .into_iter()
-                .map(|ot| ObjectTypeAssociation::Simple { object_type: ot })
-                .collect(),
-            any: any_ots
-                .into_iter()
-                .map(|ot| ObjectTypeAssociation::Simple { object_type: ot })
-                .collect(),
-            all: all_ots
-                .into_iter()
-                .map(|ot| ObjectTypeAssociation::Simple { object_type: ot })
-                .collect(),
+            each: to_associations(each_ots),
+            any: to_associations(any_ots),
+            all: to_associations(all_ots),
         };
         let arc = shared::OCDeclareArc {
             from: OCDeclareNode::new(from_act),
@@ -60,11 +579,18 @@ impl OCDeclareArc {
             label,
             counts: (min_count, max_count),
         };
-        Ok(Self { arc: arc })
+        Ok(Self::from(
+            OCDeclareConstraint::new(arc)
+                .with_time_bounds(TimeBounds::new(min_delay_ms, max_delay_ms))
+                .with_predicates(
+                    parse_optional_predicate(from_predicate)?,
+                    parse_optional_predicate(to_predicate)?,
+                ),
+        ))
     }
     /// Get string representation of OC-DECLARE arc
     pub fn to_string(&self) -> String {
-        self.arc.as_template_string()
+        self.constraint.as_template_string()
     }
 
     pub fn __repr__(&self) -> String {
@@ -75,69 +601,112 @@ impl OCDeclareArc {
         self.to_string()
     }
 
+    /// Arcs are equal if their constraints are equal (quality metrics are ignored)
+    pub fn __eq__(&self, other: PyRef<'_, Self>) -> bool {
+        self.constraint == other.constraint
+    }
+
+    /// Hash of the serialized constraint, consistent with `__eq__` (quality metrics are ignored)
+    ///
+    /// Arcs are mutable: the result of mutating an arc while it is in a set or used as a dict key is undefined.
+    pub fn __hash__(&self) -> PyResult<u64> {
+        let json = serde_json::to_string(&self.constraint).map_err(json_error)?;
+        let mut hasher = DefaultHasher::new();
+        json.hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+
+    /// Arguments for `__new__` when unpickling (the remaining fields are restored by `__setstate__`)
+    pub fn __getnewargs__(&self) -> (String, String, String, Option<usize>, Option<usize>) {
+        (
+            self.from_activity(),
+            self.to_activity(),
+            self.arc_type_name(),
+            self.min_count(),
+            self.max_count(),
+        )
+    }
+
+    /// Support for pickling and copying (state as JSON string)
+    pub fn __getstate__(&self) -> PyResult<String> {
+        self.to_json()
+    }
+
+    /// Support for unpickling (from the state returned by `__getstate__`)
+    pub fn __setstate__(&mut self, state: &str) -> PyResult<()> {
+        *self = Self::from_json(state)?;
+        Ok(())
+    }
+
+    #[pyo3(signature = () -> "str")]
+    /// Serialize the arc (including its quality metrics, if any) to JSON
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&ArcJson::from(self)).map_err(json_error)
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (json: "str", /) -> "OCDeclareArc")]
+    /// Deserialize an arc from JSON (as created by `to_json`)
+    pub fn from_json(json: &str) -> PyResult<Self> {
+        serde_json::from_str::<ArcJson>(json)
+            .map(Self::from)
+            .map_err(json_error)
+    }
+
+    /// Get the quality metrics of the arc (only set for arcs discovered with metrics enabled).
+    #[getter]
+    pub fn metrics(&self) -> Option<ArcMetrics> {
+        self.metrics.clone()
+    }
+
     // Write getters/setters for all fields of OCDeclareArc
     // Add documentation to each
     /// Get the source activity of the arc.
     #[getter]
     pub fn from_activity(&self) -> String {
-        self.arc.from.as_str().to_string()
+        self.constraint.arc.from.as_str().to_string()
     }
 
     /// Get the target activity of the arc.
     #[getter]
     pub fn to_activity(&self) -> String {
-        self.arc.to.as_str().to_string()
+        self.constraint.arc.to.as_str().to_string()
     }
 
     /// Get the type of the arc (e.g., "EF", "DF", "AS").
     #[getter]
     pub fn arc_type_name(&self) -> String {
-        self.arc.arc_type.get_name().to_string()
+        self.constraint.arc.arc_type.get_name().to_string()
     }
 
-    /// Get the object types involved with the 'ALL' quantifier.
+    /// Get the object type associations involved with the 'ALL' quantifier.
     #[getter]
-    pub fn all_ots(&self) -> Vec<String> {
-        self.arc
-            .label
-            .all
-            .iter()
-            .map(|ota| ota.as_template_string())
-            .collect()
+    pub fn all_ots(&self) -> Vec<ObjectTypeAssociation> {
+        from_associations(&self.constraint.arc.label.all)
     }
 
-    /// Get the object types involved with the 'EACH' quantifier.
+    /// Get the object type associations involved with the 'EACH' quantifier.
     #[getter]
-    pub fn each_ots(&self) -> Vec<String> {
-        self.arc
-            .label
-            .each
-            .iter()
-            .map(|ota| ota.as_template_string())
-            .collect()
+    pub fn each_ots(&self) -> Vec<ObjectTypeAssociation> {
+        from_associations(&self.constraint.arc.label.each)
     }
 
-    /// Get the object types involved with the 'ANY' quantifier.
+    /// Get the object type associations involved with the 'ANY' quantifier.
     #[getter]
-    pub fn any_ots(&self) -> Vec<String> {
-        self.arc
-            .label
-            .any
-            .iter()
-            .map(|ota| ota.as_template_string())
-            .collect()
+    pub fn any_ots(&self) -> Vec<ObjectTypeAssociation> {
+        from_associations(&self.constraint.arc.label.any)
     }
 
     /// Get the minimum count for the arc.
     #[getter]
     pub fn min_count(&self) -> Option<usize> {
-        self.arc.counts.0
+        self.constraint.arc.counts.0
     }
 
     /// Get the maximum count for the arc.
     #[getter]
     pub fn max_count(&self) -> Option<usize> {
-        self.arc.counts.1
+        self.constraint.arc.counts.1
     }
 
     // Next, setters
@@ -145,109 +714,715 @@ impl OCDeclareArc {
     /// Set the source activity of the arc.
     #[setter]
     pub fn set_from_activity(&mut self, from_act: String) {
-        self.arc.from = OCDeclareNode::new(from_act);
+        self.constraint.arc.from = OCDeclareNode::new(from_act);
     }
 
     /// Set the target activity of the arc.
     #[setter]
     pub fn set_to_activity(&mut self, to_act: String) {
-        self.arc.to = OCDeclareNode::new(to_act);
+        self.constraint.arc.to = OCDeclareNode::new(to_act);
     }
 
     /// Set the type of the arc (e.g., "EF", "DF", "AS").
     #[setter]
     pub fn set_arc_type(&mut self, arc_type: String) -> PyResult<()> {
-        self.arc.arc_type =
+        self.constraint.arc.arc_type =
             OCDeclareArcType::parse_str(&arc_type)
                 .ok_or(PyErr::new::<PyValueError, _>("Invalid arc type."))?;
         Ok(())
     }
 
-    /// Set the object types involved with the 'ALL' quantifier.
+    /// Set the object type associations (or object type names for simple associations) involved with the 'ALL' quantifier.
     #[setter]
-    pub fn set_all_ots(&mut self, all_ots: Vec<String>) {
-        self.arc.label.all = all_ots
-            .into_iter()
-            .map(|ot| ObjectTypeAssociation::Simple { object_type: ot })
-            .collect();
+    pub fn set_all_ots(&mut self, all_ots: Vec<AssociationArg>) {
+        self.constraint.arc.label.all = to_associations(all_ots);
     }
 
-    /// Set the object types involved with the 'EACH' quantifier.
+    /// Set the object type associations (or object type names for simple associations) involved with the 'EACH' quantifier.
     #[setter]
-    pub fn set_each_ots(&mut self, each_ots: Vec<String>) {
-        self.arc.label.each = each_ots
-            .into_iter()
-            .map(|ot| ObjectTypeAssociation::Simple { object_type: ot })
-            .collect();
+    pub fn set_each_ots(&mut self, each_ots: Vec<AssociationArg>) {
+        self.constraint.arc.label.each = to_associations(each_ots);
     }
 
-    /// Set the object types involved with the 'ANY' quantifier.
+    /// Set the object type associations (or object type names for simple associations) involved with the 'ANY' quantifier.
     #[setter]
-    pub fn set_any_ots(&mut self, any_ots: Vec<String>) {
-        self.arc.label.any = any_ots
-            .into_iter()
-            .map(|ot| ObjectTypeAssociation::Simple { object_type: ot })
-            .collect();
+    pub fn set_any_ots(&mut self, any_ots: Vec<AssociationArg>) {
+        self.constraint.arc.label.any = to_associations(any_ots);
     }
 
     /// Set the minimum count for the arc.
     #[setter]
     pub fn set_min_count(&mut self, min_count: Option<usize>) {
-        self.arc.counts.0 = min_count;
+        self.constraint.arc.counts.0 = min_count;
     }
 
     /// Set the maximum count for the arc.
     #[setter]
     pub fn set_max_count(&mut self, max_count: Option<usize>) {
-        self.arc.counts.1 = max_count;
+        self.constraint.arc.counts.1 = max_count;
+    }
+
+    /// Get the minimum time distance (in milliseconds) between source and matching target events.
+    #[getter]
+    pub fn min_delay_ms(&self) -> Option<i64> {
+        self.constraint.time_bounds.and_then(|b| b.min_delay_ms)
+    }
+
+    /// Get the maximum time distance (in milliseconds) between source and matching target events.
+    #[getter]
+    pub fn max_delay_ms(&self) -> Option<i64> {
+        self.constraint.time_bounds.and_then(|b| b.max_delay_ms)
+    }
+
+    /// Set the minimum time distance (in milliseconds) between source and matching target events.
+    #[setter]
+    pub fn set_min_delay_ms(&mut self, min_delay_ms: Option<i64>) {
+        let bounds = TimeBounds {
+            min_delay_ms,
+            ..self.constraint.time_bounds.unwrap_or_default()
+        };
+        self.constraint.time_bounds = Some(bounds).filter(|b| !b.is_unbounded());
+    }
+
+    /// Set the maximum time distance (in milliseconds) between source and matching target events.
+    #[setter]
+    pub fn set_max_delay_ms(&mut self, max_delay_ms: Option<i64>) {
+        let bounds = TimeBounds {
+            max_delay_ms,
+            ..self.constraint.time_bounds.unwrap_or_default()
+        };
+        self.constraint.time_bounds = Some(bounds).filter(|b| !b.is_unbounded());
+    }
+
+    /// Get the event attribute predicate of the source activity (e.g., "price > 1000").
+    #[getter(from_predicate)]
+    pub fn get_from_predicate(&self) -> Option<String> {
+        self.constraint.from_predicate.as_ref().map(print_predicate)
+    }
+
+    /// Get the event attribute predicate of the target activity (e.g., "price > 1000").
+    #[getter(to_predicate)]
+    pub fn get_to_predicate(&self) -> Option<String> {
+        self.constraint.to_predicate.as_ref().map(print_predicate)
+    }
+
+    /// Set the event attribute predicate of the source activity (e.g., "price > 1000").
+    #[setter]
+    pub fn set_from_predicate(&mut self, from_predicate: Option<String>) -> PyResult<()> {
+        self.constraint.from_predicate = parse_optional_predicate(from_predicate)?;
+        Ok(())
+    }
+
+    /// Set the event attribute predicate of the target activity (e.g., "price > 1000").
+    #[setter]
+    pub fn set_to_predicate(&mut self, to_predicate: Option<String>) -> PyResult<()> {
+        self.constraint.to_predicate = parse_optional_predicate(to_predicate)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(get_all)]
+/// An object bound in the label of a violated OC-DECLARE arc
+struct BoundObject {
+    /// Object ID
+    object_id: String,
+    /// Object type of the object
+    object_type: String,
+    /// Object type association (as template string) through which the object was bound
+    association: String,
+}
+
+impl From<shared::diagnostics::BoundObject> for BoundObject {
+    fn from(value: shared::diagnostics::BoundObject) -> Self {
+        Self {
+            object_id: value.object_id,
+            object_type: value.object_type,
+            association: value.association,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(get_all)]
+/// A violated object binding of a source event
+struct BindingViolation {
+    /// The objects bound through the 'EACH' quantifier (one per 'EACH' object type association)
+    each_objects: Vec<BoundObject>,
+    /// The objects bound through the 'ALL' quantifier
+    all_objects: Vec<BoundObject>,
+    /// The objects bound through the 'ANY' quantifier
+    any_objects: Vec<BoundObject>,
+    /// IDs of the matching target events
+    matching_events: Vec<String>,
+    /// Observed number of matching target events
+    observed_count: usize,
+    /// Required minimum number of matching target events
+    min_count: Option<usize>,
+    /// Required maximum number of matching target events
+    max_count: Option<usize>,
+    /// Kind of the violation ("TooFew" or "TooMany")
+    kind: String,
+}
+
+impl From<shared::diagnostics::BindingViolation> for BindingViolation {
+    fn from(value: shared::diagnostics::BindingViolation) -> Self {
+        Self {
+            each_objects: value.each_objects.into_iter().map(BoundObject::from).collect(),
+            all_objects: value.all_objects.into_iter().map(BoundObject::from).collect(),
+            any_objects: value.any_objects.into_iter().map(BoundObject::from).collect(),
+            matching_events: value.matching_events,
+            observed_count: value.observed_count,
+            min_count: value.required_count.0,
+            max_count: value.required_count.1,
+            kind: format!("{:?}", value.kind),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(get_all)]
+/// A source event violating an OC-DECLARE arc
+struct EventViolation {
+    /// ID of the source event
+    source_event: String,
+    /// All violated bindings of the source event
+    bindings: Vec<BindingViolation>,
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(get_all)]
+/// Violation report of an OC-DECLARE arc, listing all violating source events
+struct ViolationReport {
+    /// Number of checked source events
+    num_source_events: usize,
+    /// Number of violating source events
+    num_violating_events: usize,
+    /// Fraction of violating source events
+    violation_fraction: f64,
+    /// All violating source events
+    violations: Vec<EventViolation>,
+}
+
+impl From<shared::diagnostics::ArcViolationReport> for ViolationReport {
+    fn from(value: shared::diagnostics::ArcViolationReport) -> Self {
+        Self {
+            num_source_events: value.num_source_events,
+            num_violating_events: value.num_violating_events,
+            violation_fraction: value.violation_fraction,
+            violations: value
+                .violations
+                .into_iter()
+                .map(|v| EventViolation {
+                    source_event: v.source_event,
+                    bindings: v.bindings.into_iter().map(BindingViolation::from).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl ViolationReport {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "ViolationReport: {} of {} source events violated",
+            self.num_violating_events, self.num_source_events
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(get_all)]
+/// Change of a single OC-DECLARE arc between an old and a new model
+struct ArcDiff {
+    /// Kind of the change ("Added", "Removed", "Unchanged", "Strengthened", "Weakened" or "Modified")
+    kind: String,
+    /// Index of the arc in the old model (None for added arcs)
+    old_index: Option<usize>,
+    /// Index of the arc in the new model (None for removed arcs)
+    new_index: Option<usize>,
+    /// The arc in the old model (None for added arcs)
+    old_arc: Option<OCDeclareArc>,
+    /// The arc in the new model (None for removed arcs)
+    new_arc: Option<OCDeclareArc>,
+    /// Changed aspects of the arc ("ArcType", "Label", "Counts", "TimeBounds" or "Predicates")
+    changed_aspects: Vec<String>,
+}
+
+#[pymethods]
+impl ArcDiff {
+    pub fn __repr__(&self) -> String {
+        let arc = self.new_arc.as_ref().or(self.old_arc.as_ref());
+        format!(
+            "ArcDiff: {} {}",
+            self.kind,
+            arc.map(|a| a.to_string()).unwrap_or_default()
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(get_all)]
+/// Quality metrics of a discovered OC-DECLARE arc
+struct ArcMetrics {
+    /// Number of source events the arc applies to
+    num_source_events: usize,
+    /// Number of source events satisfying the arc
+    num_satisfying_events: usize,
+    /// Fraction of violating source events (None if there are no source events)
+    violation_fraction: Option<f64>,
+    /// Fraction of satisfying source events divided by the fraction of all events with the target activity,
+    /// i.e., `(num_satisfying_events / num_source_events) / (num_target_events / num_events)`,
+    /// counting only real events (not the `<init>`/`<exit>` events added by the pre-processing)
+    /// (None if there are no source events or no real events of the target activity)
+    lift: Option<f64>,
+}
+
+impl From<shared::discovery::ArcMetrics> for ArcMetrics {
+    fn from(value: shared::discovery::ArcMetrics) -> Self {
+        Self {
+            num_source_events: value.num_source_events,
+            num_satisfying_events: value.num_satisfying_events,
+            violation_fraction: value.violation_fraction,
+            lift: value.lift,
+        }
+    }
+}
+
+impl From<&ArcMetrics> for shared::discovery::ArcMetrics {
+    fn from(value: &ArcMetrics) -> Self {
+        Self {
+            num_source_events: value.num_source_events,
+            num_satisfying_events: value.num_satisfying_events,
+            violation_fraction: value.violation_fraction,
+            lift: value.lift,
+        }
+    }
+}
+
+#[pymethods]
+impl ArcMetrics {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "ArcMetrics: {}/{} satisfying source events, lift {}",
+            self.num_satisfying_events,
+            self.num_source_events,
+            self.lift.map(|l| format!("{l:.3}")).unwrap_or("-".to_string())
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(get_all)]
+/// An OC-DECLARE arc removed by a reduction, together with the two arcs implying it
+struct RemovedArc {
+    /// The removed arc (a -> c)
+    arc: OCDeclareArc,
+    /// The implying arc starting at the source activity of the removed arc (a -> b)
+    a: OCDeclareArc,
+    /// The implying arc ending at the target activity of the removed arc (b -> c)
+    b: OCDeclareArc,
+    /// Rule by which the arc was removed ("FollowsChain", "PrecedesChain" or "AssociationChain")
+    rule: String,
+    /// Human-readable explanation why the arc was removed
+    explanation: String,
+}
+
+impl From<shared::reduction::RemovedArc> for RemovedArc {
+    fn from(value: shared::reduction::RemovedArc) -> Self {
+        let to_py = |arc| OCDeclareArc::from(OCDeclareConstraint::new(arc));
+        Self {
+            arc: to_py(value.arc),
+            a: to_py(value.a),
+            b: to_py(value.b),
+            rule: format!("{:?}", value.rule),
+            explanation: value.explanation,
+        }
+    }
+}
+
+#[pymethods]
+impl RemovedArc {
+    pub fn __repr__(&self) -> String {
+        format!("RemovedArc: {} ({})", self.arc.to_string(), self.explanation)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(get_all)]
+/// Conformance of one OC-DECLARE arc in one time window
+struct DriftPoint {
+    /// Index of the arc in the checked model
+    constraint: usize,
+    /// Index of the time window
+    window: usize,
+    /// Start of the time window (RFC 3339, inclusive)
+    window_start: String,
+    /// End of the time window (RFC 3339, exclusive)
+    window_end: String,
+    /// Number of source events in the window
+    num_source_events: usize,
+    /// Number of violating source events in the window
+    num_violating_events: usize,
+    /// Fraction of violating source events (None if there are no source events in the window)
+    violation_fraction: Option<f64>,
+    /// Whether the arc holds in the window
+    holds: bool,
+    /// Changes compared to the previous window with source events
+    /// ("ViolationIncrease", "ViolationDecrease", "Emerged" or "Disappeared")
+    changes: Vec<String>,
+}
+
+#[pyclass]
+/// Conformance of a set of OC-DECLARE arcs over time windows
+struct DriftReport {
+    report: shared::drift::DriftReport,
+}
+
+#[pymethods]
+impl DriftReport {
+    #[getter]
+    /// All points of the time series (ordered by arc, then window)
+    pub fn points(&self) -> Vec<DriftPoint> {
+        self.report
+            .points
+            .iter()
+            .map(|p| {
+                let window = &self.report.windows[p.window];
+                DriftPoint {
+                    constraint: p.constraint,
+                    window: p.window,
+                    window_start: window.start.to_rfc3339(),
+                    window_end: window.end.to_rfc3339(),
+                    num_source_events: p.num_source_events,
+                    num_violating_events: p.num_violating_events,
+                    violation_fraction: p.violation_fraction,
+                    holds: p.holds,
+                    changes: p.changes.iter().map(|c| format!("{c:?}")).collect(),
+                }
+            })
+            .collect()
+    }
+
+    #[getter]
+    /// Number of time windows
+    pub fn num_windows(&self) -> usize {
+        self.report.windows.len()
+    }
+
+    /// Get the time series as a list of dicts (one per arc and window), e.g., for `pandas.DataFrame`
+    pub fn to_records<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.points()
+            .into_iter()
+            .map(|p| {
+                let record = PyDict::new(py);
+                record.set_item("constraint", p.constraint)?;
+                record.set_item("window", p.window)?;
+                record.set_item("window_start", p.window_start)?;
+                record.set_item("window_end", p.window_end)?;
+                record.set_item("num_source_events", p.num_source_events)?;
+                record.set_item("num_violating_events", p.num_violating_events)?;
+                record.set_item("violation_fraction", p.violation_fraction)?;
+                record.set_item("holds", p.holds)?;
+                record.set_item("changes", p.changes)?;
+                Ok(record)
+            })
+            .collect()
+    }
+
+    /// Get the JSON representation of the report (as used by the editor)
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.report).map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "DriftReport: {} windows, {} changes",
+            self.report.windows.len(),
+            self.report.changes().count()
+        )
     }
 }
 
 #[pyfunction]
 #[pyo3(signature = (path: "str", /) -> "ProcessedOCEL")]
-/// Import an OCEL 2.0 file (.xml or .json) and preprocess it for use with OC-DECLARE
+/// Import an OCEL 2.0 file (.xml, .json or .sqlite) and preprocess it for use with OC-DECLARE
 fn import_ocel2(path: String) -> PyResult<ProcessedOCEL> {
     let ocel = if path.ends_with(".xml") {
         process_mining::import_ocel_xml_file(path)
     } else if path.ends_with(".json") {
         process_mining::import_ocel_json_from_path(path)
             .map_err(|e| PyErr::new::<PyIOError, _>(e.to_string()))?
+    } else if path.ends_with(".sqlite") || path.ends_with(".sqlite3") || path.ends_with(".db") {
+        process_mining::import_ocel_sqlite_from_path(path)
+            .map_err(|e| PyErr::new::<PyIOError, _>(e.to_string()))?
     } else {
         return Err(PyErr::new::<PyIOError, _>(
-            "Invalid format! Currently only .json and .xml files are supported.",
+            "Invalid format! Currently only .json, .xml and .sqlite files are supported.",
         ));
     };
     let locel = shared::preprocess_ocel(ocel);
     Ok(ProcessedOCEL { locel })
 }
 
+fn parse_o2o_mode(o2o_mode: &str) -> PyResult<DiscoveryO2OMode> {
+    match o2o_mode {
+        "None" => Ok(DiscoveryO2OMode::None),
+        "Direct" => Ok(DiscoveryO2OMode::Direct),
+        "Reversed" => Ok(DiscoveryO2OMode::Reversed),
+        "Bidirectional" => Ok(DiscoveryO2OMode::Bidirectional),
+        _ => Err(PyErr::new::<PyValueError, _>("Invalid O2O mode. Valid options are: 'None', 'Direct', 'Reversed', 'Bidirectional'.")),
+    }
+}
+
+fn parse_reduction_mode(reduction: &str) -> PyResult<ReductionMode> {
+    match reduction {
+        "None" => Ok(ReductionMode::None),
+        "OcArcs" => Ok(ReductionMode::OcArcs),
+        "Transitive" => Ok(ReductionMode::Transitive),
+        "Entailment" => Ok(ReductionMode::Entailment),
+        _ => Err(PyErr::new::<PyValueError, _>(
+            "Invalid reduction mode. Valid options are: 'None', 'OcArcs', 'Transitive', 'Entailment'.",
+        )),
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass]
+/// Options for discovering OC-DECLARE constraints
+struct DiscoveryOptions {
+    options: shared::discovery::DiscoveryOptions,
+}
+
+#[pymethods]
+impl DiscoveryOptions {
+    #[new]
+    /// Construct new discovery options
+    ///
+    /// Options which are not specified use the default values.
+    #[pyo3(signature = (*, noise_threshold: "Optional[float]" = None, o2o_mode: "Optional[Literal['None', 'Direct', 'Reversed', 'Bidirectional']]" = None, acts_to_use: "Optional[list[str]]" = None, counts_for_generation: "Optional[tuple[Optional[int], Optional[int]]]" = None, counts_for_filter: "Optional[tuple[Optional[int], Optional[int]]]" = None, reduction: "Optional[Literal['None', 'OcArcs', 'Transitive', 'Entailment']]" = None, time_bounds: "Optional[bool]" = None, metrics: "Optional[bool]" = None) -> "DiscoveryOptions")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        noise_threshold: Option<f64>,
+        o2o_mode: Option<String>,
+        acts_to_use: Option<Vec<String>>,
+        counts_for_generation: Option<(Option<usize>, Option<usize>)>,
+        counts_for_filter: Option<(Option<usize>, Option<usize>)>,
+        reduction: Option<String>,
+        time_bounds: Option<bool>,
+        metrics: Option<bool>,
+    ) -> PyResult<Self> {
+        let mut options = shared::discovery::DiscoveryOptions::default();
+        if let Some(noise_threshold) = noise_threshold {
+            options.noise_threshold = noise_threshold;
+        }
+        if let Some(o2o_mode) = o2o_mode {
+            options.o2o_mode = parse_o2o_mode(&o2o_mode)?;
+        }
+        if acts_to_use.is_some() {
+            options.acts_to_use = acts_to_use;
+        }
+        if let Some(counts) = counts_for_generation {
+            options.counts_for_generation = counts;
+        }
+        if let Some(counts) = counts_for_filter {
+            options.counts_for_filter = counts;
+        }
+        if let Some(reduction) = reduction {
+            options.reduction = parse_reduction_mode(&reduction)?;
+        }
+        if let Some(time_bounds) = time_bounds {
+            options.time_bounds = time_bounds;
+        }
+        if let Some(metrics) = metrics {
+            options.metrics = metrics;
+        }
+        Ok(Self { options })
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self.options)
+    }
+
+    /// Maximum fraction of violating source events for a discovered constraint.
+    #[getter]
+    pub fn noise_threshold(&self) -> f64 {
+        self.options.noise_threshold
+    }
+
+    /// Which object-to-object (O2O) relationships to use ('None', 'Direct', 'Reversed' or 'Bidirectional').
+    #[getter]
+    pub fn o2o_mode(&self) -> String {
+        format!("{:?}", self.options.o2o_mode)
+    }
+
+    /// Activities to consider (all activities if None).
+    #[getter]
+    pub fn acts_to_use(&self) -> Option<Vec<String>> {
+        self.options.acts_to_use.clone()
+    }
+
+    /// (min, max) counts used for generating candidate constraints.
+    #[getter]
+    pub fn counts_for_generation(&self) -> (Option<usize>, Option<usize>) {
+        self.options.counts_for_generation
+    }
+
+    /// (min, max) counts used for filtering candidate constraints.
+    #[getter]
+    pub fn counts_for_filter(&self) -> (Option<usize>, Option<usize>) {
+        self.options.counts_for_filter
+    }
+
+    /// Reduction applied to the discovered constraints ('None', 'OcArcs', 'Transitive' or 'Entailment').
+    #[getter]
+    pub fn reduction(&self) -> String {
+        format!("{:?}", self.options.reduction)
+    }
+
+    /// Whether time bounds are proposed for the discovered EF/EP/DF/DP constraints.
+    #[getter]
+    pub fn time_bounds(&self) -> bool {
+        self.options.time_bounds
+    }
+
+    /// Set the maximum fraction of violating source events for a discovered constraint.
+    #[setter]
+    pub fn set_noise_threshold(&mut self, noise_threshold: f64) {
+        self.options.noise_threshold = noise_threshold;
+    }
+
+    /// Set which object-to-object (O2O) relationships to use ('None', 'Direct', 'Reversed' or 'Bidirectional').
+    #[setter]
+    pub fn set_o2o_mode(&mut self, o2o_mode: String) -> PyResult<()> {
+        self.options.o2o_mode = parse_o2o_mode(&o2o_mode)?;
+        Ok(())
+    }
+
+    /// Set the activities to consider (all activities if None).
+    #[setter]
+    pub fn set_acts_to_use(&mut self, acts_to_use: Option<Vec<String>>) {
+        self.options.acts_to_use = acts_to_use;
+    }
+
+    /// Set the (min, max) counts used for generating candidate constraints.
+    #[setter]
+    pub fn set_counts_for_generation(&mut self, counts: (Option<usize>, Option<usize>)) {
+        self.options.counts_for_generation = counts;
+    }
+
+    /// Set the (min, max) counts used for filtering candidate constraints.
+    #[setter]
+    pub fn set_counts_for_filter(&mut self, counts: (Option<usize>, Option<usize>)) {
+        self.options.counts_for_filter = counts;
+    }
+
+    /// Set the reduction applied to the discovered constraints ('None', 'OcArcs', 'Transitive' or 'Entailment').
+    #[setter]
+    pub fn set_reduction(&mut self, reduction: String) -> PyResult<()> {
+        self.options.reduction = parse_reduction_mode(&reduction)?;
+        Ok(())
+    }
+
+    /// Set whether time bounds are proposed for the discovered EF/EP/DF/DP constraints.
+    #[setter]
+    pub fn set_time_bounds(&mut self, time_bounds: bool) {
+        self.options.time_bounds = time_bounds;
+    }
+
+    /// Whether quality metrics are computed for the discovered constraints (see `OCDeclareArc.metrics`).
+    #[getter]
+    pub fn metrics(&self) -> bool {
+        self.options.metrics
+    }
+
+    /// Set whether quality metrics are computed for the discovered constraints (see `OCDeclareArc.metrics`).
+    #[setter]
+    pub fn set_metrics(&mut self, metrics: bool) {
+        self.options.metrics = metrics;
+    }
+}
+
 #[pyfunction]
-#[pyo3(signature = (processed_ocel: "ProcessedOCEL", /, noise_thresh: "double" = 0.2, acts_to_use: "Optional[list[str]]" = None, o2o_mode: "Optional[Literal['None', 'Direct', 'Reversed', 'Bidirectional']]"  = None) -> "list[OCDeclareArc]")]
+#[pyo3(signature = (processed_ocel: "ProcessedOCEL", /, noise_thresh: "double" = 0.2, acts_to_use: "Optional[list[str]]" = None, o2o_mode: "Optional[Literal['None', 'Direct', 'Reversed', 'Bidirectional']]"  = None, options: "Optional[DiscoveryOptions]" = None) -> "list[OCDeclareArc]")]
 /// Discover OC-DECLARE constraints given a pre-processed OCEL and a noise threshold
+///
+/// If `options` are passed, they are applied as-is (including the reduction) and the other parameters are ignored.
 fn discover(
     processed_ocel: &ProcessedOCEL,
     noise_thresh: f64,
     acts_to_use: Option<Vec<String>>,
     o2o_mode: Option<String>,
+    options: Option<DiscoveryOptions>,
 ) -> PyResult<Vec<OCDeclareArc>> {
-    let mut options = OCDeclareDiscoveryOptions::default();
-    options.noise_threshold = noise_thresh;
-    options.acts_to_use = acts_to_use;
-    if let Some(o2o_mode) = o2o_mode {
-        options.o2o_mode = match o2o_mode.as_str() {
-            "None" => O2OMode::None,
-            "Direct" => O2OMode::Direct,
-            "Reversed" => O2OMode::Reversed,
-            "Bidirectional" => O2OMode::Bidirectional,
-            _ => return Err(PyErr::new::<PyValueError, _>("Invalid O2O mode. Valid options are: 'None', 'Direct', 'Reversed', 'Bidirectional'.")),
-        };
-    }
-    let discovered_constraints =
-        shared::discover_behavior_constraints(&processed_ocel.locel, options);
-    Ok(discovered_constraints
+    let options = match options {
+        Some(options) => options.options,
+        None => {
+            let mut options = shared::discovery::DiscoveryOptions {
+                noise_threshold: noise_thresh,
+                acts_to_use,
+                ..Default::default()
+            };
+            if let Some(o2o_mode) = o2o_mode {
+                options.o2o_mode = parse_o2o_mode(&o2o_mode)?;
+            }
+            options
+        }
+    };
+    let result = discover_with_provenance(&processed_ocel.locel, &options);
+    Ok(discovered_arcs(result.constraints, result.metrics))
+}
+
+/// Convert discovered constraints to arcs, attaching the metrics (if computed)
+fn discovered_arcs(
+    constraints: Vec<OCDeclareConstraint>,
+    metrics: Vec<shared::discovery::ArcMetrics>,
+) -> Vec<OCDeclareArc> {
+    let mut metrics = metrics.into_iter().map(ArcMetrics::from);
+    constraints
         .into_iter()
-        .map(|arc| OCDeclareArc { arc })
-        .collect())
+        .map(|constraint| OCDeclareArc {
+            constraint,
+            metrics: metrics.next(),
+        })
+        .collect()
+}
+
+#[pyfunction]
+#[pyo3(signature = (processed_ocel: "ProcessedOCEL", /, options: "DiscoveryOptions") -> "tuple[list[OCDeclareArc], list[RemovedArc]]")]
+/// Discover OC-DECLARE constraints using the given options,
+/// additionally returning the arcs removed by the reduction and why they were removed
+fn discover_with_removed(
+    processed_ocel: &ProcessedOCEL,
+    options: DiscoveryOptions,
+) -> (Vec<OCDeclareArc>, Vec<RemovedArc>) {
+    let result = discover_with_provenance(&processed_ocel.locel, &options.options);
+    (
+        discovered_arcs(result.constraints, result.metrics),
+        result.removed.into_iter().map(RemovedArc::from).collect(),
+    )
+}
+
+#[pyfunction]
+#[pyo3(signature = (constraints: "list[OCDeclareArc]", /, reduction: "Literal['None', 'OcArcs', 'Transitive', 'Entailment']" = "OcArcs") -> "tuple[list[OCDeclareArc], list[RemovedArc]]")]
+/// Reduce OC-DECLARE constraints by removing arcs implied by other arcs,
+/// returning the remaining constraints and the removed arcs together with why they were removed
+///
+/// Constraints with additional restrictions (e.g., time bounds) are kept as is.
+fn reduce(
+    constraints: Vec<OCDeclareArc>,
+    reduction: &str,
+) -> PyResult<(Vec<OCDeclareArc>, Vec<RemovedArc>)> {
+    let (reduced, removed) = parse_reduction_mode(reduction)?
+        .apply_to_constraints_explained(constraints.into_iter().map(|c| c.constraint).collect());
+    Ok((
+        reduced
+            .into_iter()
+            .map(OCDeclareArc::from)
+            .collect(),
+        removed.into_iter().map(RemovedArc::from).collect(),
+    ))
 }
 
 #[pyfunction]
@@ -257,7 +1432,248 @@ fn discover(
 ///
 /// Returns 1 if all source events fulfill the constraint and 0 if all source events violate the constraint.
 fn check_conformance(processed_ocel: &ProcessedOCEL, constraint: OCDeclareArc) -> PyResult<f64> {
-    return Ok(1.0 - constraint.arc.get_for_all_evs_perf(&processed_ocel.locel));
+    return Ok(1.0 - constraint.constraint.get_for_all_evs_perf(&processed_ocel.locel));
+}
+
+#[pyfunction]
+#[pyo3(signature = (processed_ocel: "ProcessedOCEL", constraints: "list[OCDeclareArc]", /, progress: "Optional[Callable[[int, int], None]]" = None) -> "list[float]")]
+/// Evaluate multiple OC-DECLARE constraints given a pre-processed OCEL
+/// yielding the fraction of relevant events satisfying each constraint (as in `check_conformance`)
+///
+/// The constraints are evaluated in parallel, without holding the GIL.
+/// If given, `progress` is called with the number of evaluated constraints and the total number of constraints
+/// every time a constraint was evaluated.
+fn check_conformance_many(
+    py: Python<'_>,
+    processed_ocel: &ProcessedOCEL,
+    constraints: Vec<OCDeclareArc>,
+    progress: Option<Bound<'_, PyAny>>,
+) -> PyResult<Vec<f64>> {
+    let constraints: Vec<OCDeclareConstraint> =
+        constraints.into_iter().map(|c| c.constraint).collect();
+    let locel = &processed_ocel.locel;
+    let evaluate = |constraint: &OCDeclareConstraint| 1.0 - constraint.get_for_all_evs_perf(locel);
+    let Some(progress) = progress else {
+        return Ok(py.detach(|| constraints.par_iter().map(evaluate).collect()));
+    };
+    let total = constraints.len();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    // Wrapped to be usable from the closures releasing the GIL
+    let receiver = std::sync::Mutex::new(receiver);
+    std::thread::scope(|scope| {
+        let worker = scope.spawn(|| {
+            constraints
+                .par_iter()
+                .map_with(sender, |sender, constraint| {
+                    let res = evaluate(constraint);
+                    // The receiver only stops listening if a progress callback failed
+                    let _ = sender.send(());
+                    res
+                })
+                .collect::<Vec<f64>>()
+        });
+        // Progress callbacks are called from this thread, which only acquires the GIL to call them
+        for done in 1..=total {
+            if py.detach(|| receiver.lock().unwrap().recv()).is_err() {
+                break;
+            }
+            if let Err(e) = progress.call1((done, total)) {
+                drop(receiver);
+                py.detach(|| worker.join())
+                    .expect("Conformance checking panicked");
+                return Err(e);
+            }
+        }
+        Ok(py
+            .detach(|| worker.join())
+            .expect("Conformance checking panicked"))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (processed_ocel: "ProcessedOCEL", constraint: "OCDeclareArc", /) -> "ViolationReport")]
+/// Evaluate an OC-DECLARE constraint given a pre-processed OCEL
+/// yielding a detailed report of all violating source events and the object bindings causing the violation
+fn violation_report(processed_ocel: &ProcessedOCEL, constraint: OCDeclareArc) -> PyResult<ViolationReport> {
+    let report = shared::diagnostics::get_violation_report(&constraint.constraint, &processed_ocel.locel);
+    Ok(report.into())
+}
+
+#[pyfunction]
+#[pyo3(signature = (processed_ocel: "ProcessedOCEL", constraints: "list[OCDeclareArc]", /, output: "Literal['pyarrow', 'pandas']" = "pyarrow") -> "Any")]
+/// Evaluate OC-DECLARE constraints given a pre-processed OCEL
+/// yielding a table with one row per constraint and source event (as a `pyarrow.Table` or `pandas.DataFrame`)
+///
+/// Columns: `constraint_index`, `constraint`, `event_id`, `activity`, `timestamp` (UTC), `objects` (all objects of the source event),
+/// `required_min`, `required_max` (None for ∞), `min_observed_count`/`max_observed_count` (over all bindings of the source event),
+/// `num_bindings`, `num_violated_bindings` and `violated`.
+/// The constraints are evaluated in parallel, without holding the GIL.
+fn violation_table<'py>(
+    py: Python<'py>,
+    processed_ocel: &ProcessedOCEL,
+    constraints: Vec<OCDeclareArc>,
+    output: &str,
+) -> PyResult<Bound<'py, PyAny>> {
+    let (module, constructor) = match output {
+        "pyarrow" => ("pyarrow", "table"),
+        "pandas" => ("pandas", "DataFrame"),
+        _ => {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Invalid output! Use 'pyarrow' or 'pandas'.",
+            ))
+        }
+    };
+    let constraints: Vec<OCDeclareConstraint> =
+        constraints.into_iter().map(|c| c.constraint).collect();
+    let locel = &processed_ocel.locel;
+    let results: Vec<Vec<SourceEventConformance>> = py.detach(|| {
+        constraints
+            .par_iter()
+            .map(|constraint| get_source_event_conformance(constraint, locel))
+            .collect()
+    });
+    let num_rows = results.iter().map(|rows| rows.len()).sum();
+    let mut constraint_index = Vec::with_capacity(num_rows);
+    let mut constraint_string = Vec::with_capacity(num_rows);
+    let mut event_id = Vec::with_capacity(num_rows);
+    let mut activity = Vec::with_capacity(num_rows);
+    let mut timestamp = Vec::with_capacity(num_rows);
+    let mut objects = Vec::with_capacity(num_rows);
+    let mut required_min = Vec::with_capacity(num_rows);
+    let mut required_max = Vec::with_capacity(num_rows);
+    let mut min_observed_count = Vec::with_capacity(num_rows);
+    let mut max_observed_count = Vec::with_capacity(num_rows);
+    let mut num_bindings = Vec::with_capacity(num_rows);
+    let mut num_violated_bindings = Vec::with_capacity(num_rows);
+    let mut violated = Vec::with_capacity(num_rows);
+    for (i, (constraint, rows)) in constraints.iter().zip(results).enumerate() {
+        let constraint = print_constraint(constraint);
+        for row in rows {
+            constraint_index.push(i);
+            constraint_string.push(constraint.clone());
+            event_id.push(row.event_id);
+            activity.push(row.activity);
+            timestamp.push(row.timestamp.to_utc());
+            objects.push(row.objects);
+            required_min.push(row.required_count.0);
+            required_max.push(row.required_count.1);
+            min_observed_count.push(row.min_observed_count);
+            max_observed_count.push(row.max_observed_count);
+            num_bindings.push(row.num_bindings);
+            num_violated_bindings.push(row.num_violated_bindings);
+            violated.push(row.violated);
+        }
+    }
+    let columns = PyDict::new(py);
+    columns.set_item("constraint_index", constraint_index)?;
+    columns.set_item("constraint", constraint_string)?;
+    columns.set_item("event_id", event_id)?;
+    columns.set_item("activity", activity)?;
+    columns.set_item("timestamp", timestamp)?;
+    columns.set_item("objects", objects)?;
+    columns.set_item("required_min", required_min)?;
+    columns.set_item("required_max", required_max)?;
+    columns.set_item("min_observed_count", min_observed_count)?;
+    columns.set_item("max_observed_count", max_observed_count)?;
+    columns.set_item("num_bindings", num_bindings)?;
+    columns.set_item("num_violated_bindings", num_violated_bindings)?;
+    columns.set_item("violated", violated)?;
+    py.import(module)?.call_method1(constructor, (columns,))
+}
+
+#[pyfunction]
+#[pyo3(signature = (model_text: "str", /) -> "list[OCDeclareArc]")]
+/// Parse OC-DECLARE constraints from the textual model format (one constraint per line)
+///
+/// Each line uses the same syntax as the string representation of an OC-DECLARE arc, e.g.,
+/// `EF(Load Truck, Depart, Each(orders), All(items),1,∞)`.
+/// Empty lines and lines starting with `#` are ignored.
+fn parse_model(model_text: String) -> PyResult<Vec<OCDeclareArc>> {
+    let constraints = shared::template::parse_model(&model_text)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+    Ok(constraints
+        .into_iter()
+        .map(OCDeclareArc::from)
+        .collect())
+}
+
+#[pyfunction]
+#[pyo3(signature = (constraints: "list[OCDeclareArc]", /) -> "str")]
+/// Convert OC-DECLARE constraints to the textual model format (one constraint per line)
+fn model_to_string(constraints: Vec<OCDeclareArc>) -> String {
+    let constraints: Vec<OCDeclareConstraint> =
+        constraints.into_iter().map(|c| c.constraint).collect();
+    shared::template::print_model(&constraints)
+}
+
+#[pyfunction]
+#[pyo3(signature = (json: "str", /) -> "list[OCDeclareArc]")]
+/// Parse OC-DECLARE constraints from a JSON model (a list of arcs, as written by the CLI or `model_to_json`)
+fn model_from_json(json: &str) -> PyResult<Vec<OCDeclareArc>> {
+    let arcs: Vec<ArcJson> = serde_json::from_str(json).map_err(json_error)?;
+    Ok(arcs.into_iter().map(OCDeclareArc::from).collect())
+}
+
+#[pyfunction]
+#[pyo3(signature = (constraints: "list[OCDeclareArc]", /) -> "str")]
+/// Convert OC-DECLARE constraints to a JSON model (a list of arcs, including their quality metrics, if any)
+fn model_to_json(constraints: Vec<OCDeclareArc>) -> PyResult<String> {
+    let arcs: Vec<ArcJson> = constraints.iter().map(ArcJson::from).collect();
+    serde_json::to_string_pretty(&arcs).map_err(json_error)
+}
+
+#[pyfunction]
+#[pyo3(signature = (old_constraints: "list[OCDeclareArc]", new_constraints: "list[OCDeclareArc]", /) -> "list[ArcDiff]")]
+/// Compute the semantic diff between an old and a new OC-DECLARE model
+///
+/// Arcs are matched by their activity pair and arc type, and matched arcs are classified as unchanged, strengthened, weakened or modified.
+/// Returns one entry for every arc of the old model (in order), followed by the arcs added in the new model.
+fn diff_models(old_constraints: Vec<OCDeclareArc>, new_constraints: Vec<OCDeclareArc>) -> Vec<ArcDiff> {
+    let old: Vec<OCDeclareConstraint> = old_constraints.into_iter().map(|c| c.constraint).collect();
+    let new: Vec<OCDeclareConstraint> = new_constraints.into_iter().map(|c| c.constraint).collect();
+    let arc_at = |model: &[OCDeclareConstraint], index: Option<usize>| {
+        index.map(|i| OCDeclareArc::from(model[i].clone()))
+    };
+    shared::diff::diff_models(&old, &new)
+        .arcs
+        .into_iter()
+        .map(|d| ArcDiff {
+            kind: format!("{:?}", d.kind),
+            old_index: d.old_index,
+            new_index: d.new_index,
+            old_arc: arc_at(&old, d.old_index),
+            new_arc: arc_at(&new, d.new_index),
+            changed_aspects: d.changed_aspects.iter().map(|a| format!("{a:?}")).collect(),
+        })
+        .collect()
+}
+
+#[pyfunction]
+#[pyo3(signature = (processed_ocel: "ProcessedOCEL", constraints: "list[OCDeclareArc]", /, window_ms: "int", step_ms: "Optional[int]" = None, noise_threshold: "double" = 0.2, z_threshold: "double" = 1.96) -> "DriftReport")]
+/// Evaluate OC-DECLARE constraints per time window to detect concept drift
+///
+/// The log is split into windows of `window_ms` milliseconds, starting every `step_ms` milliseconds (default: `window_ms`, i.e., non-overlapping windows).
+/// Changes of the violation rate between consecutive windows are flagged if the absolute z-score of a two-proportion z-test is at least `z_threshold`.
+/// An arc holds in a window if its violation fraction is at most `noise_threshold`.
+fn detect_drift(
+    processed_ocel: &ProcessedOCEL,
+    constraints: Vec<OCDeclareArc>,
+    window_ms: i64,
+    step_ms: Option<i64>,
+    noise_threshold: f64,
+    z_threshold: f64,
+) -> PyResult<DriftReport> {
+    let constraints: Vec<OCDeclareConstraint> =
+        constraints.into_iter().map(|c| c.constraint).collect();
+    let options = shared::drift::DriftOptions {
+        window_ms,
+        step_ms,
+        noise_threshold,
+        z_threshold,
+    };
+    let report = shared::drift::detect_drift(&constraints, &processed_ocel.locel, &options)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+    Ok(DriftReport { report })
 }
 
 /// OC-DECLARE Binding for Python
@@ -265,8 +1681,30 @@ fn check_conformance(processed_ocel: &ProcessedOCEL, constraint: OCDeclareArc) -
 fn oc_declare(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<ProcessedOCEL>()?;
     m.add_class::<OCDeclareArc>()?;
+    m.add_class::<ObjectTypeAssociation>()?;
+    m.add_class::<DiscoveryOptions>()?;
+    m.add_class::<ViolationReport>()?;
+    m.add_class::<EventViolation>()?;
+    m.add_class::<BindingViolation>()?;
+    m.add_class::<BoundObject>()?;
+    m.add_class::<ArcDiff>()?;
+    m.add_class::<RemovedArc>()?;
+    m.add_class::<ArcMetrics>()?;
+    m.add_class::<DriftReport>()?;
+    m.add_class::<DriftPoint>()?;
     m.add_function(wrap_pyfunction!(import_ocel2, m)?)?;
     m.add_function(wrap_pyfunction!(discover, m)?)?;
+    m.add_function(wrap_pyfunction!(discover_with_removed, m)?)?;
+    m.add_function(wrap_pyfunction!(reduce, m)?)?;
     m.add_function(wrap_pyfunction!(check_conformance, m)?)?;
+    m.add_function(wrap_pyfunction!(check_conformance_many, m)?)?;
+    m.add_function(wrap_pyfunction!(violation_report, m)?)?;
+    m.add_function(wrap_pyfunction!(violation_table, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_model, m)?)?;
+    m.add_function(wrap_pyfunction!(model_to_string, m)?)?;
+    m.add_function(wrap_pyfunction!(model_from_json, m)?)?;
+    m.add_function(wrap_pyfunction!(model_to_json, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_models, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_drift, m)?)?;
     Ok(())
 }
@@ -2,11 +2,18 @@ import { CustomEdge } from "@/edges/types";
 import { ActivityNode } from "@/nodes/types";
 import { useEdges, useReactFlow } from "@xyflow/react";
 import { useContext, useEffect, useRef, useState } from "react";
+import type { ArcMetrics } from "../../../crates/shared/bindings/ArcMetrics";
+import type { Conflict } from "../../../crates/shared/bindings/Conflict";
+import type { DriftReport } from "../../../crates/shared/bindings/DriftReport";
+import type { ModelDiff } from "../../../crates/shared/bindings/ModelDiff";
 import type { OCDeclareArc } from "../../../crates/shared/bindings/OCDeclareArc";
+import type { RemovedArc } from "../../../crates/shared/bindings/RemovedArc";
 
+import { downloadBlob } from "@/lib/download-blob";
 import { OCELInfo, OCELInfoContext } from "@/lib/ocel-info";
 import { addArcsToFlow, flowEdgeToOCDECLARE } from "@/lib/type-conversions";
 import { Button } from "../ui/button";
+import { Dialog, DialogContent, DialogDescription, DialogHeader, DialogTitle } from "../ui/dialog";
 import { Input } from "../ui/input";
 
 import WASMWorker from "../../lib/worker?worker";
@@ -15,14 +22,19 @@ import toast from "react-hot-toast";
 const worker = new WASMWorker();
 export default function BackendButton() {
     const inputRef = useRef<HTMLInputElement>(null);
+    // Model file (JSON list of arcs, as used by "Load Save") to compare the current model with
+    const compareInputRef = useRef<HTMLInputElement>(null);
     const flow = useReactFlow<ActivityNode, CustomEdge>();
     const selectedEdges = useEdges<CustomEdge>().filter(e => e.selected)
     const [status, setStatus] = useState<"initial" | "ocel-loaded">("initial");
     const [loadingToast, setLoadingToast] = useState<string>();
+    // Arcs removed by the reduction of the last discovery (with their provenance)
+    const [removedArcs, setRemovedArcs] = useState<RemovedArc[]>([]);
+    const [showRemovedArcs, setShowRemovedArcs] = useState(false);
     const { setOcelInfo } = useContext(OCELInfoContext);
     // TODO: Extract types & Add error handling
     useEffect(() => {
-        const messageListener = (e: MessageEvent<{ type: "ocel-loaded", info: OCELInfo } | { type: "ocel-unloaded" } | { type: "discovered", discoveredArcs: OCDeclareArc[] } | { type: "edges-evaluated", edgeIDs: string[], violFracs: number[] } | { type: "" }>) => {
+        const messageListener = (e: MessageEvent<{ type: "ocel-loaded", info: OCELInfo } | { type: "ocel-unloaded" } | { type: "discovered", discoveredArcs: OCDeclareArc[], metrics: ArcMetrics[], removedArcs: RemovedArc[] } | { type: "edges-evaluated", edgeIDs: string[], violFracs: number[] } | { type: "models-diffed", newEdgeIDs: string[], diff: ModelDiff } | { type: "drift-detected", edgeIDs: string[], report: DriftReport } | { type: "consistency-checked", edgeIDs: string[], conflicts: Conflict[] } | { type: "error", error: { name: string, message: string } } | { type: "" }>) => {
             if (e.data.type === "ocel-loaded") {
                 setOcelInfo(e.data.info);
                 setStatus("ocel-loaded");
@@ -32,10 +44,11 @@ export default function BackendButton() {
             } else if (e.data.type === "discovered") {
                 if(loadingToast){
                     toast.dismiss(loadingToast);
-                    toast.success("Discovery finished! Found "+ e.data.discoveredArcs.length + " constraints.");
+                    toast.success("Discovery finished! Found "+ e.data.discoveredArcs.length + " constraints" + (e.data.removedArcs.length > 0 ? " (" + e.data.removedArcs.length + " implied constraints removed)." : "."));
                     setLoadingToast(undefined);
                 }
-                addArcsToFlow(e.data.discoveredArcs, flow);
+                setRemovedArcs(e.data.removedArcs);
+                addArcsToFlow(e.data.discoveredArcs, flow, e.data.metrics);
                 console.log(e.data.discoveredArcs);
             } else if (e.data.type === "edges-evaluated") {
                 if(loadingToast){
@@ -46,6 +59,42 @@ export default function BackendButton() {
                 for (let i = 0; i < e.data.edgeIDs.length; i++) {
                     flow.updateEdgeData(e.data.edgeIDs[i], { violationInfo: { violationPercentage: 100 * e.data.violFracs[i] } });
                 }
+            } else if (e.data.type === "models-diffed") {
+                // Highlight the changes of the edges in the new model (removed arcs have no edge)
+                for (const arc of e.data.diff.arcs) {
+                    if (arc.new_index !== null) {
+                        flow.updateEdgeData(e.data.newEdgeIDs[arc.new_index], { diffInfo: { kind: arc.kind } });
+                    }
+                }
+                const numChanged = e.data.diff.arcs.filter(a => a.kind !== "Unchanged" && a.kind !== "Removed").length;
+                const numRemoved = e.data.diff.arcs.filter(a => a.kind === "Removed").length;
+                toast.success("Comparison finished! " + numChanged + " constraints added or changed" + (numRemoved > 0 ? ", " + numRemoved + " constraints removed." : "."));
+            } else if (e.data.type === "drift-detected") {
+                const { edgeIDs, report } = e.data;
+                if(loadingToast){
+                    toast.dismiss(loadingToast);
+                    toast.success("Drift detection finished! Found " + report.points.filter(p => p.changes.length > 0).length + " changes.");
+                    setLoadingToast(undefined);
+                }
+                // Export the time series as CSV (one row per edge and window)
+                const rows = report.points.map(p => [report.windows[p.window].start, report.windows[p.window].end, edgeIDs[p.constraint], p.num_source_events, p.num_violating_events, p.violation_fraction ?? "", p.holds, p.changes.join(";")].join(","));
+                const csv = ["window_start,window_end,edge_id,num_source_events,num_violating_events,violation_fraction,holds,changes", ...rows].join("\n");
+                downloadBlob(new Blob([csv], { type: "text/csv" }), "oc-DECLARE-drift.csv");
+            } else if (e.data.type === "consistency-checked") {
+                const { edgeIDs, conflicts } = e.data;
+                if (conflicts.length === 0) {
+                    toast.success("No conflicting constraints found.");
+                }
+                for (const conflict of conflicts) {
+                    console.warn("Conflicting edges", conflict.arcs.map(i => edgeIDs[i]), conflict.explanation);
+                    toast(conflict.explanation, { icon: "⚠️", duration: 10000 });
+                }
+            } else if (e.data.type === "error") {
+                if(loadingToast){
+                    toast.dismiss(loadingToast);
+                    setLoadingToast(undefined);
+                }
+                toast.error(e.data.error.message);
             } else {
                 console.warn("Unknown message type: " + e.data.type);
             }
@@ -106,23 +155,75 @@ export default function BackendButton() {
             }}>
                 Evaluate {selectedEdges.length === 0 ? "All" : ""}
             </Button>
+            <Button title="Evaluate all (selected) constraint arcs per week of the loaded OCEL and export the time series" variant="outline" onClick={() => {
+                const edges = (selectedEdges.length > 0 ? selectedEdges : flow.getEdges());
+                const edgeIDs = edges.map(e => e.id);
+                const edgesConverted = edges.map(e => flowEdgeToOCDECLARE(e, flow));
+                setLoadingToast(toast.loading("Detecting drift..."));
+                worker.postMessage({ type: "detect-drift", edges: edgesConverted, edgeIDs })
+            }}>
+                Drift
+            </Button>
             </>
         }
-        <Button title="Reset the violation status of all constraints" variant="ghost" onClick={() => {
-            flow.setEdges(eds => [...eds].map(e => ({ ...e, data: { ...e.data!, violationInfo: undefined } })))
+        <Button title="Check all (selected) constraint arcs for conflicts (i.e., arcs which can not be satisfied together)" variant="outline" onClick={() => {
+            const edges = (selectedEdges.length > 0 ? selectedEdges : flow.getEdges());
+            const edgeIDs = edges.map(e => e.id);
+            const edgesConverted = edges.map(e => flowEdgeToOCDECLARE(e, flow));
+            worker.postMessage({ type: "check-consistency", edges: edgesConverted, edgeIDs })
+        }}>Check</Button>
+        <Input type="file" accept=".json" ref={compareInputRef} className="hidden" onChange={async (ev) => {
+            const file = ev.currentTarget.files?.[0];
+            ev.currentTarget.value = "";
+            if (file === undefined) {
+                return;
+            }
+            try {
+                const oldEdges: OCDeclareArc[] = JSON.parse(await file.text());
+                const edges = flow.getEdges();
+                const newEdgeIDs = edges.map(e => e.id);
+                const newEdges = edges.map(e => flowEdgeToOCDECLARE(e, flow));
+                worker.postMessage({ type: "diff-models", oldEdges, newEdges, newEdgeIDs });
+            } catch (e) {
+                toast.error("Invalid model file: " + String(e));
+            }
+        }} />
+        <Button title="Compare the current constraints with a saved model (JSON) and highlight added and changed constraints" variant="outline" onClick={() => compareInputRef.current?.click()}>Compare…</Button>
+        <Button title="Reset the violation and comparison status of all constraints" variant="ghost" onClick={() => {
+            flow.setEdges(eds => [...eds].map(e => ({ ...e, data: { ...e.data!, violationInfo: undefined, diffInfo: undefined } })))
         }}>Reset</Button>
 
         {status === "ocel-loaded" &&
             <Button title="Automatically discover constraints from the loaded OCEL" onClick={async () => {
                 try {
                     setLoadingToast(toast.loading("Discovering constraints. This might take a while..."));
-                    worker.postMessage({ type: "discover" });
+                    worker.postMessage({ type: "discover", options: { noise_threshold: 0.2, counts_for_filter: [1, 5], reduction: "OcArcs", metrics: true } });
                 } catch (e) {
                     console.error(e);
                 }
             }}>Discover</Button>
         }
+        {removedArcs.length > 0 && <Button title="Show why constraints were removed by the reduction after the last discovery" variant="ghost" onClick={() => setShowRemovedArcs(true)}>
+            Removed ({removedArcs.length})
+        </Button>}
 </div>
+        <Dialog open={showRemovedArcs} onOpenChange={setShowRemovedArcs}>
+            <DialogContent className="max-h-[80vh] overflow-y-auto">
+                <DialogHeader>
+                    <DialogTitle>Removed Constraints</DialogTitle>
+                    <DialogDescription>
+                        Discovered constraints which were removed by the reduction, as they are dominated by (or implied by) other discovered constraints.
+                    </DialogDescription>
+                </DialogHeader>
+                <ul className="text-sm flex flex-col gap-y-2">
+                    {removedArcs.map((removed, i) => <li key={i}>
+                        <span className="font-semibold">{removed.arc.arc_type === "ASS" ? "AS" : removed.arc.arc_type}({removed.arc.from}, {removed.arc.to})</span>
+                        <span className="text-xs text-gray-500 ml-1">[{removed.rule}]</span>
+                        <div className="text-xs">{removed.explanation}</div>
+                    </li>)}
+                </ul>
+            </DialogContent>
+        </Dialog>
     </div>
     </>
 
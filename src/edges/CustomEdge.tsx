@@ -24,8 +24,17 @@ import dfSvg from "./icons/df.svg?url";
 import dpSvg from "./icons/dp.svg?url";
 import efSvg from "./icons/ef.svg?url";
 import epSvg from "./icons/ep.svg?url";
+import type { ArcChangeKind } from "crates/shared/bindings/ArcChangeKind";
 import { ALL_EDGE_TYPES, CustomEdge as CustomEdgeType, EdgeType, getMarkersForEdge } from './types';
 const DISTANCE_FACTOR = 16;
+const DIFF_COLORS: Record<ArcChangeKind, string> = {
+    Added: "#16a34a",
+    Removed: "#dc2626",
+    Unchanged: "#6b7280",
+    Strengthened: "#2563eb",
+    Weakened: "#d97706",
+    Modified: "#9333ea",
+};
 const interactionWidth = 20;
 
 
@@ -240,6 +249,10 @@ export default function CustomEdge(edge: EdgeProps<CustomEdgeType> & { data: { t
                             <span style={{ color: "var(--violation-color)" }} className="text-gray-500 block -mt-[1px] font-medium text-[5pt] ">{Math.round(100 * (100 - data.violationInfo.violationPercentage)) / 100}%</span>
                         </div>} />
                 }
+                {data.diffInfo !== undefined && data.diffInfo.kind !== "Unchanged" &&
+                    <EdgeLabel transform={`translate(${labelX}px,${labelY}px)  translate(-50%, -50%)  rotate(${Math.round(slopeDegree)}deg)   translate(0,-6.5pt)`}
+                        label={<span style={{ color: DIFF_COLORS[data.diffInfo.kind] }} className="block font-medium text-[5pt]">{data.diffInfo.kind}</span>} />
+                }
                 {/* <EdgeLabel transform={`translate(-50%, -50%) translate(${modifiedPos.sourceX}px,${modifiedPos.sourceY}px) ${(targetPos === Position.Top) ? "translate(8px,9px)" : targetPos === Position.Left ? "translate(12px,-11px)" : targetPos === Position.Bottom ? "translate(8px,-9px)" : "translate(-11px,-11px)"} `}
                     label={"1"} /> */}
                 <EdgeLabel
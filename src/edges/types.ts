@@ -1,4 +1,5 @@
 import type { Edge } from '@xyflow/react';
+import { ArcChangeKind } from 'crates/shared/bindings/ArcChangeKind';
 import { OCDeclareArcLabel } from 'crates/shared/bindings/OCDeclareArcLabel';
 
 export const ALL_EDGE_TYPES = [
@@ -13,7 +14,7 @@ export const ALL_EDGE_TYPES = [
     "ndf-rev",
     "ass"] as const;
 export type EdgeType = typeof ALL_EDGE_TYPES[number];
-export type CustomEdge = Edge<{ type: EdgeType, objectTypes: OCDeclareArcLabel, cardinality?: [number | null, number | null], violationInfo?: { violationPercentage: number } }>;
+export type CustomEdge = Edge<{ type: EdgeType, objectTypes: OCDeclareArcLabel, cardinality?: [number | null, number | null], violationInfo?: { violationPercentage: number }, diffInfo?: { kind: ArcChangeKind } }>;
 export type AppNode = CustomEdge;
 
 
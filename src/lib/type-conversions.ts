@@ -1,6 +1,7 @@
 import { CustomEdge, EdgeType, getMarkersForEdge } from "@/edges/types";
 import { ActivityNode } from "@/nodes/types";
 import { ReactFlowInstance } from "@xyflow/react";
+import { ArcMetrics } from "crates/shared/bindings/ArcMetrics";
 import { OCDeclareArc } from "crates/shared/bindings/OCDeclareArc";
 import { OCDeclareArcType } from "crates/shared/bindings/OCDeclareArcType";
 import { OCDeclareNode } from "crates/shared/bindings/OCDeclareNode";
@@ -57,11 +58,12 @@ export function flowEdgeToOCDECLARE(e: CustomEdge, flow: ReactFlowInstance<Activ
 import { v4 as uuidv4 } from 'uuid';
 import { applyLayoutToNodes } from "./automatic-layout";
 // import { ObjectTypeAssociation } from "crates/shared/bindings/ObjectTypeAssociation";
-export async function addArcsToFlow(discoverdArcs: OCDeclareArc[], flow: ReactFlowInstance<ActivityNode, CustomEdge>) {
+// If metrics are given (one per arc), the violation percentages of the arcs are shown directly
+export async function addArcsToFlow(discoverdArcs: OCDeclareArc[], flow: ReactFlowInstance<ActivityNode, CustomEdge>, metrics?: ArcMetrics[]) {
     const nodeNameToIDs: Record<string, string> = {};
     const edges: CustomEdge[] = [];
     const nodes: ActivityNode[] = [];
-    for (const arc of discoverdArcs) {
+    for (const [i, arc] of discoverdArcs.entries()) {
         const edgeType = translateArcTypeFromRsToTs(arc.arc_type);
         // const NON_RESOURCE_TYPES = ["orders", "items", "packages","Offer","Application"];
         // const isNotOnlyResource = arc.label.all.map(oi => getLastOT(oi)).find(ot => NON_RESOURCE_TYPES.includes(ot)) || arc.label.each.map(oi => getLastOT(oi)).find(ot => NON_RESOURCE_TYPES.includes(ot)) || arc.label.any.map(oi => getLastOT(oi)).find(ot => NON_RESOURCE_TYPES.includes(ot));
@@ -86,7 +88,9 @@ export async function addArcsToFlow(discoverdArcs: OCDeclareArc[], flow: ReactFl
             //     continue;
             // }
             const edgeID = uuidv4();
-            edges.push({ id: edgeID, source: sourceID, target: targetID, data: { type: edgeType, objectTypes: arc.label, cardinality: arc.counts }, ...getMarkersForEdge(edgeType, edgeID) })
+            const violationFraction = metrics?.[i]?.violation_fraction;
+            const violationInfo = violationFraction != null ? { violationPercentage: 100 * violationFraction } : undefined;
+            edges.push({ id: edgeID, source: sourceID, target: targetID, data: { type: edgeType, objectTypes: arc.label, cardinality: arc.counts, violationInfo }, ...getMarkersForEdge(edgeType, edgeID) })
         // }
         // }
     }
@@ -1,9 +1,27 @@
-
+import { Conflict } from "crates/shared/bindings/Conflict";
+import { DiscoveryOptions } from "crates/shared/bindings/DiscoveryOptions";
+import { DiscoveryResult } from "crates/shared/bindings/DiscoveryResult";
+import { DriftOptions } from "crates/shared/bindings/DriftOptions";
+import { DriftReport } from "crates/shared/bindings/DriftReport";
+import { ModelDiff } from "crates/shared/bindings/ModelDiff";
 import { OCDeclareArc } from "crates/shared/bindings/OCDeclareArc";
-import init, { discover_oc_declare_constraints, get_all_edge_violation_percentage_perf, get_edge_as_template_text, get_ot_act_involvements, initThreadPool, load_ocel_json, load_ocel_xml, unload_ocel } from "../../crates/backend-wasm/pkg/backend_wasm";
+import { OCDeclareConstraint } from "crates/shared/bindings/OCDeclareConstraint";
+import init, { get_default_discovery_options, get_default_drift_options, get_edge_as_template_text, get_edge_conflicts, get_model_diff, initThreadPool, OCELSession } from "../../crates/backend-wasm/pkg/backend_wasm";
+
+// Loaded OCELs, by session ID (a single session called "default" is used, unless an ID is specified)
+const sessions = new Map<string, OCELSession>();
+const DEFAULT_SESSION = "default";
+
+// Errors thrown by the WASM backend have the error variant as name and a details object (see BackendError)
+function postError(sessionID: string, e: unknown) {
+    console.error(e);
+    const error = e instanceof Error ? { name: e.name, message: e.message, details: (e as Error & { details?: unknown }).details } : { name: "Error", message: String(e) };
+    postMessage({ type: "error", sessionID, error });
+}
 
 // listen for messages from UI thread
-onmessage = function (e: MessageEvent<{ type: "load-ocel", file: File } | { type: "unload-ocel" } | { type: "discover" } | { type: "evaluate-edges", edges: OCDeclareArc[], edgeIDs: string[] } | { type: "" }>) {
+onmessage = function (e: MessageEvent<({ type: "load-ocel", file: File } | { type: "unload-ocel" } | { type: "discover", options?: Partial<DiscoveryOptions> } | { type: "evaluate-edges", edges: OCDeclareArc[], edgeIDs: string[] } | { type: "detect-drift", edges: OCDeclareConstraint[], edgeIDs: string[], options?: Partial<DriftOptions> } | { type: "diff-models", oldEdges: OCDeclareConstraint[], newEdges: OCDeclareConstraint[], newEdgeIDs: string[] } | { type: "check-consistency", edges: OCDeclareConstraint[], edgeIDs: string[] } | { type: "" }) & { sessionID?: string }>) {
+    const sessionID = e.data.sessionID ?? DEFAULT_SESSION;
     if (e.data.type === "load-ocel") {
         const data = e.data;
         // read contents of file
@@ -17,36 +35,85 @@ onmessage = function (e: MessageEvent<{ type: "load-ocel", file: File } | { type
             } catch (e) {
                 console.log("Thread pool error: ", e);
             }
-            data.file.name.endsWith(".json") ? load_ocel_json(x) : load_ocel_xml(x)
-            // console.log("Got ocel pointer: " + ocelRef.current);
-            // setStatus("ocel-loaded");
-            const otActInvolvement = JSON.parse(get_ot_act_involvements());
+            try {
+                sessions.get(sessionID)?.free();
+                sessions.delete(sessionID);
+                const session = data.file.name.endsWith(".json") ? OCELSession.load_ocel_json(x) : OCELSession.load_ocel_xml(x);
+                sessions.set(sessionID, session);
+                const otActInvolvement = JSON.parse(session.get_ot_act_involvements());
 
-            this.postMessage({ type: "ocel-loaded", info: otActInvolvement });
+                this.postMessage({ type: "ocel-loaded", sessionID, info: otActInvolvement });
+            } catch (e) {
+                postError(sessionID, e);
+            }
         };
         reader.readAsArrayBuffer(e.data.file);
-    } else if (e.data.type === "unload-ocel") {
-        unload_ocel();
-        this.postMessage({ type: "ocel-unloaded" });
-    } else if (e.data.type === "discover") {
-
-        let now = Date.now();
-        const res = discover_oc_declare_constraints(0.2);
-        const discoveredArcs: OCDeclareArc[] = JSON.parse(res);
-        console.log("Discovery took " + ((Date.now() - now) / 1000) + "s");
-        this.postMessage({ type: "discovered", discoveredArcs });
-    } else if (e.data.type === "evaluate-edges") {
-        const edgeJSON = JSON.stringify(e.data.edges);
-        console.log(edgeJSON);
-        for(const x of e.data.edges){
-            console.log(get_edge_as_template_text(JSON.stringify(x)));
+        return;
+    }
+    if (e.data.type === "diff-models") {
+        // Diffing only needs the models, not a loaded OCEL
+        try {
+            const diff: ModelDiff = JSON.parse(get_model_diff(JSON.stringify(e.data.oldEdges), JSON.stringify(e.data.newEdges)));
+            this.postMessage({ type: "models-diffed", sessionID, newEdgeIDs: e.data.newEdgeIDs, diff });
+        } catch (e) {
+            postError(sessionID, e);
         }
-        const beginning = Date.now();
-        const violFracs = get_all_edge_violation_percentage_perf(edgeJSON);
-        console.log("TOTAL Evaluation took " + ((Date.now() - beginning) / 1000) + "s");
-        this.postMessage({ type: "edges-evaluated", edgeIDs: e.data.edgeIDs, violFracs })
+        return;
     }
-    else {
-        console.warn("Unknown message type: " + e.data.type);
+    if (e.data.type === "check-consistency") {
+        try {
+            const conflicts: Conflict[] = JSON.parse(get_edge_conflicts(JSON.stringify(e.data.edges)));
+            this.postMessage({ type: "consistency-checked", sessionID, edgeIDs: e.data.edgeIDs, conflicts });
+        } catch (e) {
+            postError(sessionID, e);
+        }
+        return;
+    }
+    const session = sessions.get(sessionID);
+    if (session === undefined) {
+        postError(sessionID, new Error("No OCEL loaded for session " + sessionID));
+        return;
+    }
+    try {
+        if (e.data.type === "unload-ocel") {
+            session.free();
+            sessions.delete(sessionID);
+            this.postMessage({ type: "ocel-unloaded", sessionID });
+        } else if (e.data.type === "discover") {
+
+            let now = Date.now();
+            // Options not specified by the message use the backend defaults
+            const options: DiscoveryOptions = { ...JSON.parse(get_default_discovery_options()), ...e.data.options };
+            const res = session.discover_oc_declare_constraints_with_provenance(JSON.stringify(options));
+            const { constraints: discoveredArcs, metrics, removed: removedArcs }: DiscoveryResult = JSON.parse(res);
+            console.log("Discovery took " + ((Date.now() - now) / 1000) + "s");
+            this.postMessage({ type: "discovered", sessionID, discoveredArcs, metrics, removedArcs });
+        } else if (e.data.type === "evaluate-edges") {
+            const edgeJSON = JSON.stringify(e.data.edges);
+            console.log(edgeJSON);
+            // Warn about conflicting arcs before evaluating them (they are still evaluated)
+            const conflicts: Conflict[] = JSON.parse(get_edge_conflicts(edgeJSON));
+            if (conflicts.length > 0) {
+                this.postMessage({ type: "consistency-checked", sessionID, edgeIDs: e.data.edgeIDs, conflicts });
+            }
+            for(const x of e.data.edges){
+                console.log(get_edge_as_template_text(JSON.stringify(x)));
+            }
+            const beginning = Date.now();
+            const violFracs = session.get_all_edge_violation_percentage_perf(edgeJSON);
+            console.log("TOTAL Evaluation took " + ((Date.now() - beginning) / 1000) + "s");
+            this.postMessage({ type: "edges-evaluated", sessionID, edgeIDs: e.data.edgeIDs, violFracs })
+        }
+        else if (e.data.type === "detect-drift") {
+            // Options not specified by the message use the backend defaults
+            const options: DriftOptions = { ...JSON.parse(get_default_drift_options()), ...e.data.options };
+            const report: DriftReport = JSON.parse(session.get_edge_drift(JSON.stringify(e.data.edges), JSON.stringify(options)));
+            this.postMessage({ type: "drift-detected", sessionID, edgeIDs: e.data.edgeIDs, report });
+        }
+        else {
+            console.warn("Unknown message type: " + e.data.type);
+        }
+    } catch (e) {
+        postError(sessionID, e);
     }
-};
\ No newline at end of file
+};